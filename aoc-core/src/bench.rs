@@ -0,0 +1,276 @@
+//! Repeated timing of puzzle stages, with results saved for comparison between commits.
+
+use std::any::Any;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use crate::parse::ParseError;
+use crate::runner::{solve_part, Outcome};
+use crate::solver::{Part, Puzzle};
+
+/// A timed step of solving a puzzle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Stage {
+    Parse,
+    Part(Part),
+}
+
+impl fmt::Display for Stage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Stage::Parse => write!(f, "parse"),
+            Stage::Part(part) => write!(f, "part{}", part),
+        }
+    }
+}
+
+impl std::str::FromStr for Stage {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "parse" => Ok(Stage::Parse),
+            "part1" => Ok(Stage::Part(Part::One)),
+            "part2" => Ok(Stage::Part(Part::Two)),
+            _ => Err(format!("unknown stage `{}`", s)),
+        }
+    }
+}
+
+/// Why a puzzle couldn't be benchmarked.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BenchError {
+    Parse(ParseError),
+    /// A part failed instead of answering.
+    Failed(Part, String),
+}
+
+impl From<ParseError> for BenchError {
+    fn from(err: ParseError) -> Self {
+        BenchError::Parse(err)
+    }
+}
+
+/// Summary of the wall times of repeated runs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    pub runs: usize,
+    pub mean: Duration,
+    pub min: Duration,
+    /// Population standard deviation.
+    pub stddev: Duration,
+}
+
+impl Stats {
+    /// Summarises at least one sample.
+    pub fn from_samples(samples: &[Duration]) -> Self {
+        assert!(!samples.is_empty(), "no samples to summarise");
+
+        let runs = samples.len();
+        let mean = samples.iter().sum::<Duration>() / runs as u32;
+        let variance = samples
+            .iter()
+            .map(|sample| (sample.as_secs_f64() - mean.as_secs_f64()).powi(2))
+            .sum::<f64>()
+            / runs as f64;
+
+        Stats {
+            runs,
+            mean,
+            min: *samples.iter().min().expect("samples aren't empty"),
+            stddev: Duration::from_secs_f64(variance.sqrt()),
+        }
+    }
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "mean {:.2?}, min {:.2?}, stddev {:.2?} over {} runs",
+            self.mean, self.min, self.stddev, self.runs
+        )
+    }
+}
+
+/// Timing statistics for one stage of a puzzle.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Measurement {
+    pub year: u32,
+    pub day: u32,
+    pub stage: Stage,
+    pub stats: Stats,
+}
+
+/// Parses the input and solves each part `runs` times, timing every stage separately.
+pub fn bench(
+    puzzle: &Arc<dyn Puzzle>,
+    input: &str,
+    parts: &[Part],
+    runs: usize,
+) -> Result<Vec<Measurement>, BenchError> {
+    let measurement = |stage, samples: &[Duration]| Measurement {
+        year: puzzle.year(),
+        day: puzzle.day(),
+        stage,
+        stats: Stats::from_samples(samples),
+    };
+
+    let mut samples = Vec::with_capacity(runs);
+    let mut parsed: Option<Box<dyn Any + Send + Sync>> = None;
+    for _ in 0..runs.max(1) {
+        let start = Instant::now();
+        parsed = Some(puzzle.parse(input)?);
+        samples.push(start.elapsed());
+    }
+    let parsed: Arc<dyn Any + Send + Sync> = Arc::from(parsed.expect("input parsed at least once"));
+
+    let mut measurements = vec![measurement(Stage::Parse, &samples)];
+
+    for &part in parts {
+        samples.clear();
+        for _ in 0..runs.max(1) {
+            let start = Instant::now();
+            let outcome = solve_part(puzzle, &parsed, part, None);
+            samples.push(start.elapsed());
+
+            if let Outcome::Failed(message) = outcome {
+                return Err(BenchError::Failed(part, message));
+            }
+        }
+        measurements.push(measurement(Stage::Part(part), &samples));
+    }
+
+    Ok(measurements)
+}
+
+const HEADER: &str = "year\tday\tstage\truns\tmean_ns\tmin_ns\tstddev_ns";
+
+/// Saves measurements as tab separated values, with times in nanoseconds.
+pub fn write_results(path: &Path, measurements: &[Measurement]) -> io::Result<()> {
+    let mut text = format!("{}\n", HEADER);
+
+    for m in measurements {
+        text.push_str(&format!(
+            "{}\t{}\t{}\t{}\t{}\t{}\t{}\n",
+            m.year,
+            m.day,
+            m.stage,
+            m.stats.runs,
+            m.stats.mean.as_nanos(),
+            m.stats.min.as_nanos(),
+            m.stats.stddev.as_nanos(),
+        ));
+    }
+
+    fs::write(path, text)
+}
+
+/// Loads measurements saved by [write_results].
+pub fn read_results(path: &Path) -> io::Result<Vec<Measurement>> {
+    let text = fs::read_to_string(path)?;
+
+    text.lines()
+        .enumerate()
+        .filter(|(_, line)| *line != HEADER && !line.trim().is_empty())
+        .map(|(i, line)| {
+            parse_measurement(line).ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("line {}: invalid measurement `{}`", i + 1, line),
+                )
+            })
+        })
+        .collect()
+}
+
+fn parse_measurement(line: &str) -> Option<Measurement> {
+    let fields = line.split('\t').collect::<Vec<_>>();
+
+    let [year, day, stage, runs, mean, min, stddev] = fields.as_slice() else {
+        return None;
+    };
+
+    let nanos = |field: &str| field.parse::<u64>().ok().map(Duration::from_nanos);
+
+    Some(Measurement {
+        year: year.parse().ok()?,
+        day: day.parse().ok()?,
+        stage: stage.parse().ok()?,
+        stats: Stats {
+            runs: runs.parse().ok()?,
+            mean: nanos(mean)?,
+            min: nanos(min)?,
+            stddev: nanos(stddev)?,
+        },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solver::tests::Fixture;
+
+    #[test]
+    fn statistics() {
+        let samples = [2, 4, 4, 4, 5, 5, 7, 9].map(Duration::from_millis);
+        let stats = Stats::from_samples(&samples);
+
+        assert_eq!(8, stats.runs);
+        assert_eq!(Duration::from_millis(5), stats.mean);
+        assert_eq!(Duration::from_millis(2), stats.min);
+        assert_eq!(2000, stats.stddev.as_micros());
+    }
+
+    #[test]
+    fn stages_measured() {
+        let puzzle: Arc<dyn Puzzle> = Arc::new(Fixture::new(2021, 6));
+
+        let measurements = bench(&puzzle, "1,2,3", &[Part::Two], 3).unwrap();
+        let stages = measurements.iter().map(|m| m.stage).collect::<Vec<_>>();
+
+        assert_eq!(vec![Stage::Parse, Stage::Part(Part::Two)], stages);
+        assert!(measurements.iter().all(|m| m.stats.runs == 3));
+
+        assert!(matches!(
+            bench(&puzzle, "1,x", &Part::ALL, 3),
+            Err(BenchError::Parse(_))
+        ));
+        assert_eq!(
+            Err(BenchError::Failed(
+                Part::Two,
+                "product overflows".to_string()
+            )),
+            bench(&puzzle, "4294967296,4294967296", &Part::ALL, 3)
+        );
+    }
+
+    #[test]
+    fn results_round_trip() {
+        let measurements = vec![
+            Measurement {
+                year: 2021,
+                day: 6,
+                stage: Stage::Parse,
+                stats: Stats::from_samples(&[Duration::from_micros(10)]),
+            },
+            Measurement {
+                year: 2021,
+                day: 6,
+                stage: Stage::Part(Part::Two),
+                stats: Stats::from_samples(&[Duration::from_nanos(5), Duration::from_nanos(9)]),
+            },
+        ];
+
+        let path = std::env::temp_dir().join(format!("aoc-bench-{}.tsv", std::process::id()));
+        write_results(&path, &measurements).unwrap();
+        let loaded = read_results(&path);
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(measurements, loaded.unwrap());
+    }
+}
@@ -0,0 +1,1225 @@
+use std::fs;
+use std::ops::RangeInclusive;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use clap::{Args, Parser, Subcommand};
+
+use crate::bench::{self, BenchError};
+use crate::client::{self, Client};
+use crate::description;
+use crate::fetch::{self, Source};
+use crate::picture;
+use crate::registry::Registry;
+use crate::report::{self, DayReport, Format, PartReport, Status};
+use crate::runner::{print_solution, read_input, Outcome};
+use crate::scaffold::{self, NewDay};
+use crate::solver::{Answer, Options, Part, Puzzle};
+use crate::submit::{self, Response};
+use crate::verify::{self, Verdict};
+
+/// Advent of Code puzzle runner.
+#[derive(Parser, Debug)]
+#[command(name = "aoc", version)]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Command,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// Run one or more puzzles and print their solutions
+    Run(RunArgs),
+    /// Time repeated runs of one or more puzzles and save the statistics
+    Bench(BenchArgs),
+    /// Check answers against the expected answers recorded next to each input
+    Verify(VerifyArgs),
+    /// Download a puzzle input into the local cache
+    Fetch(FetchArgs),
+    /// Solve a part and submit its answer
+    Submit(SubmitArgs),
+    /// Generate and register the module for a new day
+    New(NewArgs),
+    /// Extract example inputs and answers from a saved puzzle description page
+    Examples(ExamplesArgs),
+    /// Draw what's behind a part's answer in the terminal or as an image
+    Draw(DrawArgs),
+}
+
+/// Arguments choosing which puzzles and parts to run.
+#[derive(Args, Debug)]
+pub struct Selection {
+    /// Event year, may be omitted when only one year is available
+    #[arg(long)]
+    pub year: Option<u32>,
+
+    /// Day or inclusive range of days, e.g. `15` or `10..17`
+    #[arg(long, value_parser = parse_days)]
+    pub day: Option<RangeInclusive<u32>>,
+
+    /// Only run the given part (1 or 2)
+    #[arg(long, value_parser = parse_part)]
+    pub part: Option<Part>,
+
+    /// Run every registered puzzle
+    #[arg(long, conflicts_with_all = ["year", "day"])]
+    pub all: bool,
+
+    /// Change puzzle settings, e.g. `tiles=10,wrap=9`
+    #[arg(long = "set", value_name = "KEY=VALUE,...")]
+    pub options: Option<Options>,
+}
+
+impl Selection {
+    /// The selected parts, both unless `--part` was given.
+    pub fn parts(&self) -> Vec<Part> {
+        match self.part {
+            Some(part) => vec![part],
+            None => Part::ALL.to_vec(),
+        }
+    }
+}
+
+#[derive(Args, Debug)]
+pub struct RunArgs {
+    #[command(flatten)]
+    pub selection: Selection,
+
+    /// Read the puzzle input from a file, or `-` for stdin, instead of the stored input
+    #[arg(long, conflicts_with = "all")]
+    pub input: Option<PathBuf>,
+
+    /// Skip any part still running after this many seconds
+    #[arg(long, value_name = "SECONDS", value_parser = parse_seconds)]
+    pub max_time: Option<Duration>,
+
+    /// Print a report of answers and timings once every puzzle has run
+    #[arg(long, value_enum, default_value_t = Format::Text)]
+    pub format: Format,
+
+    /// Write the report to a file instead of stdout
+    #[arg(long, requires = "format")]
+    pub output: Option<PathBuf>,
+
+    /// Regenerate the progress table between the markers in this README
+    #[arg(long, value_name = "README", conflicts_with_all = ["format", "output", "input"])]
+    pub update_readme: Option<PathBuf>,
+}
+
+#[derive(Args, Debug)]
+pub struct VerifyArgs {
+    #[command(flatten)]
+    pub selection: Selection,
+
+    /// Verify this input file, against the answers file in its directory, instead of the stored
+    /// input
+    #[arg(long, conflicts_with = "all")]
+    pub input: Option<PathBuf>,
+
+    /// Fail any part still running after this many seconds
+    #[arg(long, value_name = "SECONDS", value_parser = parse_seconds)]
+    pub max_time: Option<Duration>,
+}
+
+/// Arguments for talking to the Advent of Code website.
+#[derive(Args, Debug)]
+pub struct SiteArgs {
+    /// Website to talk to, e.g. a local test server
+    #[arg(long, env = "AOC_BASE_URL", default_value = client::DEFAULT_BASE_URL)]
+    pub base_url: String,
+
+    /// Session cookie of a logged in user
+    #[arg(long, env = "AOC_SESSION", hide_env_values = true)]
+    pub session: Option<String>,
+
+    /// Directory downloaded inputs are cached in
+    #[arg(long, env = "AOC_CACHE_DIR", default_value = fetch::DEFAULT_CACHE_DIR)]
+    pub cache_dir: PathBuf,
+}
+
+impl SiteArgs {
+    pub fn client(&self) -> Client {
+        Client::new(&self.base_url, self.session.clone())
+    }
+}
+
+#[derive(Args, Debug)]
+pub struct FetchArgs {
+    /// Event year
+    #[arg(long, value_parser = clap::value_parser!(u32).range(2015..))]
+    pub year: u32,
+
+    /// Day of the puzzle
+    #[arg(long, value_parser = parse_day)]
+    pub day: u32,
+
+    /// Download the input again even when it's cached
+    #[arg(long)]
+    pub refresh: bool,
+
+    /// Print the input instead of its location
+    #[arg(long)]
+    pub print: bool,
+
+    #[command(flatten)]
+    pub site: SiteArgs,
+}
+
+#[derive(Args, Debug)]
+pub struct SubmitArgs {
+    /// Event year
+    #[arg(long, value_parser = clap::value_parser!(u32).range(2015..))]
+    pub year: u32,
+
+    /// Day of the puzzle
+    #[arg(long, value_parser = parse_day)]
+    pub day: u32,
+
+    /// Part to submit (1 or 2)
+    #[arg(long, value_parser = parse_part)]
+    pub part: Part,
+
+    /// Solve this input file, or `-` for stdin, instead of the fetched input
+    #[arg(long)]
+    pub input: Option<PathBuf>,
+
+    #[command(flatten)]
+    pub site: SiteArgs,
+}
+
+#[derive(Args, Debug)]
+pub struct NewArgs {
+    /// Event year, which needs an existing `aoc<YEAR>` crate
+    #[arg(long, value_parser = clap::value_parser!(u32).range(2015..))]
+    pub year: u32,
+
+    /// Day of the puzzle
+    #[arg(long, value_parser = parse_day)]
+    pub day: u32,
+
+    /// Module name, e.g. `sonar_sweep`
+    #[arg(long, value_parser = scaffold::parse_module)]
+    pub name: String,
+
+    /// Puzzle title, derived from the module name by default
+    #[arg(long)]
+    pub title: Option<String>,
+
+    /// Workspace directory holding the year crates
+    #[arg(long, default_value = ".")]
+    pub root: PathBuf,
+}
+
+#[derive(Args, Debug)]
+pub struct ExamplesArgs {
+    /// Event year
+    #[arg(long, value_parser = clap::value_parser!(u32).range(2015..))]
+    pub year: u32,
+
+    /// Day of the puzzle
+    #[arg(long, value_parser = parse_day)]
+    pub day: u32,
+
+    /// Puzzle description page saved from the browser
+    pub page: PathBuf,
+}
+
+#[derive(Args, Debug)]
+pub struct DrawArgs {
+    /// Event year
+    #[arg(long, value_parser = clap::value_parser!(u32).range(2015..))]
+    pub year: u32,
+
+    /// Day of the puzzle
+    #[arg(long, value_parser = parse_day)]
+    pub day: u32,
+
+    /// Part to draw (1 or 2)
+    #[arg(long, value_parser = parse_part)]
+    pub part: Part,
+
+    /// Read the puzzle input from a file, or `-` for stdin, instead of the stored input
+    #[arg(long)]
+    pub input: Option<PathBuf>,
+
+    /// Save the picture as a PPM image instead of printing it
+    #[arg(long)]
+    pub image: Option<PathBuf>,
+
+    /// Width in pixels of each cell of the image
+    #[arg(long, default_value_t = 4, value_parser = clap::value_parser!(u32).range(1..=64))]
+    pub scale: u32,
+
+    /// Change puzzle settings, e.g. `tiles=10,wrap=9`
+    #[arg(long = "set", value_name = "KEY=VALUE,...")]
+    pub options: Option<Options>,
+}
+
+#[derive(Args, Debug)]
+pub struct BenchArgs {
+    #[command(flatten)]
+    pub selection: Selection,
+
+    /// Number of timed runs of each stage
+    #[arg(long, default_value_t = 10, value_parser = clap::value_parser!(u32).range(1..))]
+    pub runs: u32,
+
+    /// File the statistics are saved to
+    #[arg(long, default_value = "bench_output.txt")]
+    pub output: PathBuf,
+
+    /// Earlier results to compare the mean times against
+    #[arg(long)]
+    pub baseline: Option<PathBuf>,
+}
+
+/// Parses the command line and runs the requested command against the registry.
+///
+/// Returns a failing exit code when the selection doesn't match any registered puzzle or the
+/// input can't be read.
+pub fn run(registry: &Registry) -> ExitCode {
+    let cli = Cli::parse();
+
+    match cli.command {
+        Command::Run(args) => run_puzzles(registry, &args),
+        Command::Bench(args) => bench_puzzles(registry, &args),
+        Command::Verify(args) => verify_puzzles(registry, &args),
+        Command::Fetch(args) => fetch_puzzle_input(&args),
+        Command::Submit(args) => submit_puzzle_answer(registry, &args),
+        Command::New(args) => new_day(registry, &args),
+        Command::Examples(args) => extract_examples(registry, &args),
+        Command::Draw(args) => draw_puzzle(registry, &args),
+    }
+}
+
+fn run_puzzles(registry: &Registry, args: &RunArgs) -> ExitCode {
+    let puzzles = match select(registry, &args.selection) {
+        Ok(puzzles) => puzzles,
+        Err(message) => {
+            eprintln!("error: {}", message);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    if args.input.is_some() && puzzles.len() > 1 {
+        eprintln!(
+            "error: --input needs a single day, but {} were selected",
+            puzzles.len()
+        );
+        return ExitCode::FAILURE;
+    }
+
+    if args.output.is_some() && args.format == Format::Text {
+        eprintln!("error: --output needs a report --format");
+        return ExitCode::FAILURE;
+    }
+
+    let parts = args.selection.parts();
+
+    if args.format != Format::Text || args.update_readme.is_some() {
+        return report_puzzles(&puzzles, &parts, args);
+    }
+
+    let mut current_year = None;
+    let mut failed = false;
+    for puzzle in &puzzles {
+        let path = match &args.input {
+            Some(path) => path.as_path(),
+            None => Path::new(puzzle.input_path()),
+        };
+
+        let input = match read_input(path) {
+            Ok(input) => input,
+            Err(err) => {
+                eprintln!("error: couldn't read {}: {}", path.display(), err);
+                return ExitCode::FAILURE;
+            }
+        };
+
+        if current_year != Some(puzzle.year()) {
+            current_year = Some(puzzle.year());
+            println!("*** Advent of Code {} ***", puzzle.year());
+        }
+
+        match print_solution(puzzle, &input, &parts, args.max_time) {
+            Ok(outcomes) => {
+                failed |= outcomes.iter().any(|o| matches!(o, Outcome::Failed(_)));
+            }
+            Err(err) => {
+                eprintln!("error: {}", err.with_file(display_path(path)));
+                return ExitCode::FAILURE;
+            }
+        }
+    }
+
+    if failed {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+/// Runs the puzzles quietly, then writes the requested report.
+fn report_puzzles(puzzles: &[Arc<dyn Puzzle>], parts: &[Part], args: &RunArgs) -> ExitCode {
+    let mut reports = Vec::new();
+
+    for puzzle in puzzles {
+        let path = match &args.input {
+            Some(path) => path.as_path(),
+            None => Path::new(puzzle.input_path()),
+        };
+
+        let input = match read_input(path) {
+            Ok(input) => input,
+            Err(err) => {
+                eprintln!("error: couldn't read {}: {}", path.display(), err);
+                return ExitCode::FAILURE;
+            }
+        };
+
+        let day = report::run_puzzle(puzzle, &input, parts, args.max_time);
+        for error in report::errors(&day) {
+            eprintln!("error: {}: {}", display_path(path).display(), error);
+        }
+        reports.push(day);
+    }
+
+    let written = match &args.update_readme {
+        Some(readme) => update_readme(readme, &reports),
+        None => {
+            let text = args
+                .format
+                .render(&reports)
+                .expect("text isn't a report format");
+            match &args.output {
+                Some(path) => fs::write(path, text)
+                    .map_err(|err| format!("couldn't write {}: {}", path.display(), err)),
+                None => {
+                    print!("{}", text);
+                    Ok(())
+                }
+            }
+        }
+    };
+
+    if let Err(message) = written {
+        eprintln!("error: {}", message);
+        return ExitCode::FAILURE;
+    }
+
+    if reports.iter().any(|day| day.status == Status::Failed) {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+fn update_readme(path: &Path, reports: &[DayReport]) -> Result<(), String> {
+    let readme = fs::read_to_string(path)
+        .map_err(|err| format!("couldn't read {}: {}", path.display(), err))?;
+
+    let readme = report::update_progress(&readme, &report::progress_table(reports))
+        .map_err(|message| format!("{}: {}", path.display(), message))?;
+
+    fs::write(path, readme).map_err(|err| format!("couldn't write {}: {}", path.display(), err))
+}
+
+fn verify_puzzles(registry: &Registry, args: &VerifyArgs) -> ExitCode {
+    let puzzles = match select(registry, &args.selection) {
+        Ok(puzzles) => puzzles,
+        Err(message) => {
+            eprintln!("error: {}", message);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    if args.input.is_some() && puzzles.len() > 1 {
+        eprintln!(
+            "error: --input needs a single day, but {} were selected",
+            puzzles.len()
+        );
+        return ExitCode::FAILURE;
+    }
+
+    let parts = args.selection.parts();
+    let (mut passed, mut mismatched, mut failed, mut unknown) = (0, 0, 0, 0);
+
+    let mut current_year = None;
+    for puzzle in &puzzles {
+        let path = match &args.input {
+            Some(path) => path.as_path(),
+            None => Path::new(puzzle.input_path()),
+        };
+
+        if current_year != Some(puzzle.year()) {
+            current_year = Some(puzzle.year());
+            println!("*** Advent of Code {} ***", puzzle.year());
+        }
+        println!("--- Day {}: {} ---", puzzle.day(), puzzle.title());
+
+        for (part, verdict) in verify::verify(puzzle, path, &parts, args.max_time) {
+            println!("\tPart {}: {}", part, verdict);
+
+            match verdict {
+                Verdict::Pass => passed += 1,
+                Verdict::Mismatch { .. } => mismatched += 1,
+                Verdict::Fail(_) => failed += 1,
+                Verdict::Unknown => unknown += 1,
+            }
+        }
+    }
+
+    println!(
+        "{} passed, {} mismatched, {} failed, {} unknown",
+        passed, mismatched, failed, unknown
+    );
+
+    if mismatched + failed > 0 {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+fn fetch_puzzle_input(args: &FetchArgs) -> ExitCode {
+    let client = args.site.client();
+
+    let fetched = match fetch::fetch_input(
+        &client,
+        &args.site.cache_dir,
+        args.year,
+        args.day,
+        args.refresh,
+    ) {
+        Ok(fetched) => fetched,
+        Err(err) => {
+            eprintln!(
+                "error: couldn't fetch {} day {}: {}",
+                args.year, args.day, err
+            );
+            return ExitCode::FAILURE;
+        }
+    };
+
+    match &fetched.source {
+        Source::Downloaded => eprintln!("Downloaded input to {}", fetched.path.display()),
+        Source::Cache => eprintln!("Using cached input {}", fetched.path.display()),
+        Source::CacheFallback(err) => eprintln!(
+            "warning: {}, using cached input {}",
+            err,
+            fetched.path.display()
+        ),
+    }
+
+    if args.print {
+        print!("{}", fetched.input);
+    } else {
+        println!("{}", fetched.path.display());
+    }
+
+    ExitCode::SUCCESS
+}
+
+fn submit_puzzle_answer(registry: &Registry, args: &SubmitArgs) -> ExitCode {
+    let puzzle = match registry.get(args.year, args.day) {
+        Some(puzzle) => puzzle,
+        None => {
+            eprintln!("error: no puzzle for {} day {}", args.year, args.day);
+            return ExitCode::FAILURE;
+        }
+    };
+    let client = args.site.client();
+
+    let input = match &args.input {
+        Some(path) => {
+            read_input(path).map_err(|err| format!("couldn't read {}: {}", path.display(), err))
+        }
+        None => fetch::fetch_input(&client, &args.site.cache_dir, args.year, args.day, false)
+            .map(|fetched| {
+                if let Source::CacheFallback(err) = &fetched.source {
+                    eprintln!("warning: {}, using cached input", err);
+                }
+                fetched.input
+            })
+            .map_err(|err| format!("couldn't fetch input: {}", err)),
+    };
+    let input = match input {
+        Ok(input) => input,
+        Err(message) => {
+            eprintln!("error: {}", message);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let day = report::run_puzzle(puzzle, &input, &[args.part], None);
+    let answer = match (&day.error, day.parts.first()) {
+        (
+            None,
+            Some(PartReport {
+                answer: Some(answer),
+                ..
+            }),
+        ) => answer,
+        (Some(error), _) => {
+            eprintln!("error: couldn't parse input, {}", error);
+            return ExitCode::FAILURE;
+        }
+        (None, part) => {
+            eprintln!(
+                "error: part {} failed, {}",
+                args.part,
+                part.and_then(|p| p.error.as_deref()).unwrap_or_default()
+            );
+            return ExitCode::FAILURE;
+        }
+    };
+    println!(
+        "--- Day {}: {} ---\n\tPart {}: {}",
+        puzzle.day(),
+        puzzle.title(),
+        args.part,
+        answer
+    );
+
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("clock is after the epoch")
+        .as_secs();
+    let submitted = submit::submit_answer(
+        &client,
+        &args.site.cache_dir,
+        args.year,
+        args.day,
+        args.part,
+        answer,
+        now,
+    );
+
+    match submitted {
+        Ok(reply) if reply.response == Response::Correct => {
+            println!("{}", reply);
+            ExitCode::SUCCESS
+        }
+        Ok(reply) => {
+            println!("{}", reply);
+            ExitCode::FAILURE
+        }
+        Err(err) => {
+            eprintln!("error: {}", err);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn new_day(registry: &Registry, args: &NewArgs) -> ExitCode {
+    if let Some(puzzle) = registry.get(args.year, args.day) {
+        eprintln!(
+            "error: {} day {} is already registered as {}",
+            args.year,
+            args.day,
+            puzzle.title()
+        );
+        return ExitCode::FAILURE;
+    }
+
+    let day = NewDay {
+        year: args.year,
+        day: args.day,
+        module: args.name.clone(),
+        title: args
+            .title
+            .clone()
+            .unwrap_or_else(|| scaffold::title(&args.name)),
+    };
+
+    match scaffold::new_day(&args.root, &day) {
+        Ok(created) => {
+            for path in created {
+                println!("Created {}", display_path(&path).display());
+            }
+            println!(
+                "Registered {} in {}",
+                day.solver(),
+                display_path(&scaffold::year_dir(&args.root, args.year).join("src/lib.rs"))
+                    .display()
+            );
+            ExitCode::SUCCESS
+        }
+        Err(message) => {
+            eprintln!("error: {}", message);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn extract_examples(registry: &Registry, args: &ExamplesArgs) -> ExitCode {
+    let puzzle = match registry.get(args.year, args.day) {
+        Some(puzzle) => puzzle,
+        None => {
+            eprintln!(
+                "error: no puzzle for {} day {}, see `aoc new`",
+                args.year, args.day
+            );
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let page = match fs::read_to_string(&args.page) {
+        Ok(page) => page,
+        Err(err) => {
+            eprintln!("error: couldn't read {}: {}", args.page.display(), err);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let examples = description::examples(&description::parse(&page));
+    if examples.is_empty() {
+        eprintln!("error: no examples found in {}", args.page.display());
+        return ExitCode::FAILURE;
+    }
+
+    let dir = Path::new(puzzle.input_path())
+        .parent()
+        .expect("input path has a directory");
+    let written = match description::save_examples(dir, &examples) {
+        Ok(written) => written,
+        Err(message) => {
+            eprintln!("error: {}", message);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    for (path, example) in written.iter().zip(&examples) {
+        let answer = |answer: &Option<Answer>| {
+            answer
+                .as_ref()
+                .map_or("unknown".to_string(), |a| a.to_string())
+        };
+        println!(
+            "Wrote {}, part 1: {}, part 2: {}",
+            path.display(),
+            answer(&example.part1),
+            answer(&example.part2)
+        );
+    }
+    println!(
+        "Check the examples and their answers in {}, and list new examples in the day's \
+         `examples!` tests",
+        dir.join(verify::ANSWERS_FILE).display()
+    );
+
+    ExitCode::SUCCESS
+}
+
+fn draw_puzzle(registry: &Registry, args: &DrawArgs) -> ExitCode {
+    let puzzle = match registry.get(args.year, args.day) {
+        Some(puzzle) => puzzle,
+        None => {
+            eprintln!("error: no puzzle for {} day {}", args.year, args.day);
+            return ExitCode::FAILURE;
+        }
+    };
+    let puzzle = match &args.options {
+        Some(options) => match configure(puzzle, options) {
+            Ok(puzzle) => puzzle,
+            Err(message) => {
+                eprintln!("error: {}", message);
+                return ExitCode::FAILURE;
+            }
+        },
+        None => Arc::clone(puzzle),
+    };
+
+    let path = match &args.input {
+        Some(path) => path.as_path(),
+        None => Path::new(puzzle.input_path()),
+    };
+    let input = match read_input(path) {
+        Ok(input) => input,
+        Err(err) => {
+            eprintln!("error: couldn't read {}: {}", path.display(), err);
+            return ExitCode::FAILURE;
+        }
+    };
+    let input = match puzzle.parse(&input) {
+        Ok(input) => input,
+        Err(err) => {
+            eprintln!("error: {}", err.with_file(display_path(path)));
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let Some(picture) = puzzle.draw(input.as_ref(), args.part) else {
+        eprintln!(
+            "error: {} day {} part {} has nothing to draw",
+            args.year, args.day, args.part
+        );
+        return ExitCode::FAILURE;
+    };
+
+    match &args.image {
+        Some(image) => {
+            if let Err(err) = fs::write(image, picture::ppm(&picture, args.scale as usize)) {
+                eprintln!("error: couldn't write {}: {}", image.display(), err);
+                return ExitCode::FAILURE;
+            }
+            println!("Saved picture to {}", image.display());
+        }
+        None => println!("{}", picture::ansi(&picture)),
+    }
+
+    ExitCode::SUCCESS
+}
+
+fn bench_puzzles(registry: &Registry, args: &BenchArgs) -> ExitCode {
+    let puzzles = match select(registry, &args.selection) {
+        Ok(puzzles) => puzzles,
+        Err(message) => {
+            eprintln!("error: {}", message);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let baseline = match &args.baseline {
+        Some(path) => match bench::read_results(path) {
+            Ok(results) => results,
+            Err(err) => {
+                eprintln!("error: couldn't read {}: {}", path.display(), err);
+                return ExitCode::FAILURE;
+            }
+        },
+        None => Vec::new(),
+    };
+
+    let parts = args.selection.parts();
+    let mut results = Vec::new();
+
+    for puzzle in &puzzles {
+        let path = Path::new(puzzle.input_path());
+        let input = match read_input(path) {
+            Ok(input) => input,
+            Err(err) => {
+                eprintln!("error: couldn't read {}: {}", path.display(), err);
+                return ExitCode::FAILURE;
+            }
+        };
+
+        println!(
+            "--- {} Day {}: {} ---",
+            puzzle.year(),
+            puzzle.day(),
+            puzzle.title()
+        );
+
+        let measurements = match bench::bench(puzzle, &input, &parts, args.runs as usize) {
+            Ok(measurements) => measurements,
+            Err(BenchError::Parse(err)) => {
+                eprintln!("error: {}", err.with_file(path));
+                return ExitCode::FAILURE;
+            }
+            Err(BenchError::Failed(part, message)) => {
+                eprintln!("error: part {} failed, {}", part, message);
+                return ExitCode::FAILURE;
+            }
+        };
+
+        for m in &measurements {
+            let previous = baseline
+                .iter()
+                .find(|b| (b.year, b.day, b.stage) == (m.year, m.day, m.stage));
+
+            match previous {
+                Some(previous) => println!(
+                    "\t{}: {} ({:+.1}% against baseline)",
+                    m.stage,
+                    m.stats,
+                    change(previous.stats.mean, m.stats.mean)
+                ),
+                None => println!("\t{}: {}", m.stage, m.stats),
+            }
+        }
+
+        results.extend(measurements);
+    }
+
+    if let Err(err) = bench::write_results(&args.output, &results) {
+        eprintln!("error: couldn't write {}: {}", args.output.display(), err);
+        return ExitCode::FAILURE;
+    }
+    println!("Saved results to {}", args.output.display());
+
+    ExitCode::SUCCESS
+}
+
+/// Percentage change from `before` to `after`.
+fn change(before: Duration, after: Duration) -> f64 {
+    (after.as_secs_f64() / before.as_secs_f64() - 1.0) * 100.0
+}
+
+/// Names stdin `<stdin>` in messages rather than `-`.
+fn display_path(path: &Path) -> &Path {
+    if path == Path::new("-") {
+        Path::new("<stdin>")
+    } else {
+        path
+    }
+}
+
+/// Resolves the selected puzzles, in year/day order, with the settings given by `--set`.
+pub fn select(registry: &Registry, args: &Selection) -> Result<Vec<Arc<dyn Puzzle>>, String> {
+    let puzzles = find(registry, args)?;
+
+    match &args.options {
+        Some(options) => puzzles
+            .into_iter()
+            .map(|puzzle| configure(puzzle, options))
+            .collect(),
+        None => Ok(puzzles.into_iter().cloned().collect()),
+    }
+}
+
+fn configure(puzzle: &Arc<dyn Puzzle>, options: &Options) -> Result<Arc<dyn Puzzle>, String> {
+    puzzle
+        .configure(options)
+        .map_err(|message| format!("{} day {}: {}", puzzle.year(), puzzle.day(), message))
+}
+
+fn find<'a>(registry: &'a Registry, args: &Selection) -> Result<Vec<&'a Arc<dyn Puzzle>>, String> {
+    if args.all {
+        return Ok(registry.iter().collect());
+    }
+
+    if args.year.is_none() && args.day.is_none() {
+        return Err("nothing to run, pass --year, --day or --all".to_string());
+    }
+
+    let year = match args.year {
+        Some(year) => year,
+        None => match registry.years().as_slice() {
+            [year] => *year,
+            _ => return Err("--year is required when several years are available".to_string()),
+        },
+    };
+
+    let days = args.day.clone().unwrap_or(1..=25);
+
+    let puzzles = registry
+        .year(year)
+        .filter(|p| days.contains(&p.day()))
+        .collect::<Vec<_>>();
+
+    if puzzles.is_empty() {
+        return Err(format!(
+            "no solved puzzles for {} day {}",
+            year,
+            format_days(&days)
+        ));
+    }
+
+    Ok(puzzles)
+}
+
+fn format_days(days: &RangeInclusive<u32>) -> String {
+    if days.start() == days.end() {
+        days.start().to_string()
+    } else {
+        format!("{}..{}", days.start(), days.end())
+    }
+}
+
+fn parse_day(value: &str) -> Result<u32, String> {
+    let day = value
+        .trim()
+        .parse::<u32>()
+        .map_err(|_| format!("`{}` isn't a day number", value))?;
+
+    if (1..=25).contains(&day) {
+        Ok(day)
+    } else {
+        Err(format!("day {} is outside 1..25", day))
+    }
+}
+
+/// Parses a single day or an inclusive day range such as `10..17` (`10..=17` is also accepted).
+pub fn parse_days(value: &str) -> Result<RangeInclusive<u32>, String> {
+    match value.split_once("..") {
+        Some((start, end)) => {
+            let start = parse_day(start)?;
+            let end = parse_day(end.strip_prefix('=').unwrap_or(end))?;
+
+            if start > end {
+                return Err(format!("day range {} is empty", value));
+            }
+
+            Ok(start..=end)
+        }
+        None => parse_day(value).map(|day| day..=day),
+    }
+}
+
+pub fn parse_seconds(value: &str) -> Result<Duration, String> {
+    value
+        .trim()
+        .parse::<f64>()
+        .ok()
+        .and_then(|seconds| Duration::try_from_secs_f64(seconds).ok())
+        .ok_or_else(|| format!("`{}` isn't a number of seconds", value))
+}
+
+pub fn parse_part(value: &str) -> Result<Part, String> {
+    match value.trim() {
+        "1" => Ok(Part::One),
+        "2" => Ok(Part::Two),
+        _ => Err(format!("part must be 1 or 2, not `{}`", value)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solver::tests::Fixture;
+
+    fn registry() -> Registry {
+        let mut registry = Registry::new();
+        registry.register(Fixture::new(2021, 10));
+        registry.register(Fixture::new(2021, 15));
+        registry.register(Fixture::new(2021, 17));
+        registry.register(Fixture::new(2022, 1));
+        registry
+    }
+
+    fn run_args(args: &[&str]) -> RunArgs {
+        let cli = Cli::try_parse_from(["aoc", "run"].iter().chain(args)).unwrap();
+        match cli.command {
+            Command::Run(args) => args,
+            command => panic!("expected a run command, found {:?}", command),
+        }
+    }
+
+    fn selected(registry: &Registry, args: &[&str]) -> Result<Vec<(u32, u32)>, String> {
+        select(registry, &run_args(args).selection)
+            .map(|puzzles| puzzles.iter().map(|p| (p.year(), p.day())).collect())
+    }
+
+    #[test]
+    fn day_ranges() {
+        assert_eq!(Ok(15..=15), parse_days("15"));
+        assert_eq!(Ok(10..=17), parse_days("10..17"));
+        assert_eq!(Ok(10..=17), parse_days("10..=17"));
+        assert!(parse_days("0").is_err());
+        assert!(parse_days("26").is_err());
+        assert!(parse_days("17..10").is_err());
+        assert!(parse_days("ten").is_err());
+    }
+
+    #[test]
+    fn selection() {
+        let registry = registry();
+
+        assert_eq!(
+            Ok(vec![(2021, 15)]),
+            selected(&registry, &["--year", "2021", "--day", "15"])
+        );
+        assert_eq!(
+            Ok(vec![(2021, 10), (2021, 15)]),
+            selected(&registry, &["--year", "2021", "--day", "10..16"])
+        );
+        assert_eq!(4, selected(&registry, &["--all"]).unwrap().len());
+        assert!(selected(&registry, &["--year", "2021", "--day", "1"]).is_err());
+        assert!(selected(&registry, &["--day", "1"]).is_err());
+        assert!(selected(&registry, &[]).is_err());
+    }
+
+    #[test]
+    fn options() {
+        let registry = registry();
+        let args = run_args(&["--year", "2021", "--day", "15", "--set", "tiles=10"]);
+
+        assert_eq!(Ok(Some(10)), args.selection.options.unwrap().get("tiles"));
+        assert_eq!(
+            Err("2021 day 15: unknown option `tiles`, there are no options".to_string()),
+            selected(
+                &registry,
+                &["--year", "2021", "--day", "15", "--set", "tiles=10"]
+            )
+        );
+        assert!(Cli::try_parse_from(["aoc", "run", "--all", "--set", "tiles"]).is_err());
+    }
+
+    #[test]
+    fn year_inferred_for_single_year() {
+        let mut registry = Registry::new();
+        registry.register(Fixture::new(2023, 3));
+
+        assert_eq!(Ok(vec![(2023, 3)]), selected(&registry, &["--day", "3"]));
+    }
+
+    #[test]
+    fn time_budget() {
+        assert_eq!(Ok(Duration::from_millis(2500)), parse_seconds("2.5"));
+        assert_eq!(Ok(Duration::from_secs(10)), parse_seconds("10"));
+        assert!(parse_seconds("ten").is_err());
+    }
+
+    #[test]
+    fn invalid_arguments() {
+        assert!(Cli::try_parse_from(["aoc", "run", "--part", "3"]).is_err());
+        assert!(Cli::try_parse_from(["aoc", "run", "--all", "--year", "2021"]).is_err());
+        assert!(Cli::try_parse_from(["aoc", "run", "--all", "--input", "-"]).is_err());
+        assert!(Cli::try_parse_from(["aoc", "run", "--all", "--max-time", "-1"]).is_err());
+        assert!(Cli::try_parse_from(["aoc", "run", "--all", "--format", "yaml"]).is_err());
+        assert!(Cli::try_parse_from(["aoc", "bench", "--all", "--runs", "0"]).is_err());
+        assert!(Cli::try_parse_from(["aoc", "bench", "--all", "--input", "-"]).is_err());
+        assert!(Cli::try_parse_from(["aoc", "verify", "--all", "--input", "-"]).is_err());
+    }
+
+    #[test]
+    fn report_arguments() {
+        assert_eq!(Format::Text, run_args(&["--all"]).format);
+        assert_eq!(
+            Format::Json,
+            run_args(&["--all", "--format", "json"]).format
+        );
+        assert_eq!(
+            Format::Markdown,
+            run_args(&["--all", "--format", "markdown"]).format
+        );
+
+        let args = [
+            "aoc",
+            "run",
+            "--all",
+            "--format",
+            "csv",
+            "--update-readme",
+            "README.md",
+        ];
+        assert!(Cli::try_parse_from(args).is_err());
+    }
+
+    #[test]
+    fn fetch_arguments() {
+        let cli = Cli::try_parse_from([
+            "aoc",
+            "fetch",
+            "--year",
+            "2021",
+            "--day",
+            "16",
+            "--base-url",
+            "http://localhost:8080",
+            "--session",
+            "abc",
+        ])
+        .unwrap();
+        let Command::Fetch(args) = cli.command else {
+            panic!("expected a fetch command");
+        };
+
+        assert_eq!((2021, 16), (args.year, args.day));
+        assert_eq!("http://localhost:8080", args.site.base_url);
+        assert_eq!(Some("abc".to_string()), args.site.session);
+
+        assert!(Cli::try_parse_from(["aoc", "fetch", "--year", "2014", "--day", "1"]).is_err());
+        assert!(Cli::try_parse_from(["aoc", "fetch", "--year", "2021", "--day", "26"]).is_err());
+        assert!(Cli::try_parse_from(["aoc", "fetch", "--year", "2021"]).is_err());
+    }
+
+    #[test]
+    fn submit_arguments() {
+        let cli = Cli::try_parse_from([
+            "aoc", "submit", "--year", "2021", "--day", "16", "--part", "2",
+        ])
+        .unwrap();
+        let Command::Submit(args) = cli.command else {
+            panic!("expected a submit command");
+        };
+
+        assert_eq!((2021, 16, Part::Two), (args.year, args.day, args.part));
+        assert_eq!(None, args.input);
+
+        assert!(Cli::try_parse_from(["aoc", "submit", "--year", "2021", "--day", "16"]).is_err());
+        assert!(Cli::try_parse_from([
+            "aoc", "submit", "--year", "2021", "--day", "16", "--part", "3",
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn new_arguments() {
+        let cli = Cli::try_parse_from([
+            "aoc", "new", "--year", "2023", "--day", "5", "--name", "seeds",
+        ])
+        .unwrap();
+        let Command::New(args) = cli.command else {
+            panic!("expected a new command");
+        };
+
+        assert_eq!(
+            (2023, 5, "seeds"),
+            (args.year, args.day, args.name.as_str())
+        );
+        assert_eq!(Path::new("."), args.root);
+
+        assert!(Cli::try_parse_from([
+            "aoc", "new", "--year", "2023", "--day", "5", "--name", "Seeds",
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn examples_arguments() {
+        let cli = Cli::try_parse_from([
+            "aoc",
+            "examples",
+            "--year",
+            "2021",
+            "--day",
+            "12",
+            "day12.html",
+        ])
+        .unwrap();
+        let Command::Examples(args) = cli.command else {
+            panic!("expected an examples command");
+        };
+
+        assert_eq!((2021, 12), (args.year, args.day));
+        assert_eq!(Path::new("day12.html"), args.page);
+        assert!(Cli::try_parse_from(["aoc", "examples", "--year", "2021", "--day", "12"]).is_err());
+    }
+
+    #[test]
+    fn draw_arguments() {
+        let cli = Cli::try_parse_from([
+            "aoc", "draw", "--year", "2021", "--day", "15", "--part", "2",
+        ])
+        .unwrap();
+        let Command::Draw(args) = cli.command else {
+            panic!("expected a draw command");
+        };
+
+        assert_eq!((2021, 15, Part::Two), (args.year, args.day, args.part));
+        assert_eq!((None, 4), (args.image, args.scale));
+        assert_eq!(None, args.options);
+
+        assert!(Cli::try_parse_from([
+            "aoc", "draw", "--year", "2021", "--day", "15", "--part", "2", "--scale", "0",
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn bench_arguments() {
+        let cli = Cli::try_parse_from(["aoc", "bench", "--day", "6", "--part", "2"]).unwrap();
+        let Command::Bench(args) = cli.command else {
+            panic!("expected a bench command");
+        };
+
+        assert_eq!(vec![Part::Two], args.selection.parts());
+        assert_eq!(10, args.runs);
+        assert_eq!(Path::new("bench_output.txt"), args.output);
+        assert_eq!(None, args.baseline);
+    }
+}
@@ -0,0 +1,248 @@
+//! HTTP client for the Advent of Code website.
+
+use std::fmt;
+use std::time::Duration;
+
+use crate::solver::Part;
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+const USER_AGENT: &str = concat!("aoc-core/", env!("CARGO_PKG_VERSION"), " puzzle runner");
+
+#[derive(Debug)]
+pub enum ClientError {
+    /// No session token was configured.
+    MissingSession,
+    /// The server answered with an error status.
+    Status { code: u16, message: String },
+    /// The server couldn't be reached, e.g. when offline.
+    Transport(String),
+}
+
+impl fmt::Display for ClientError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ClientError::MissingSession => {
+                write!(f, "no session token, set AOC_SESSION or pass --session")
+            }
+            ClientError::Status { code, message } => {
+                write!(f, "server responded with {}: {}", code, message)
+            }
+            ClientError::Transport(message) => write!(f, "couldn't reach server: {}", message),
+        }
+    }
+}
+
+impl std::error::Error for ClientError {}
+
+/// Client authenticated with the session cookie of a logged in user.
+pub struct Client {
+    base_url: String,
+    session: Option<String>,
+    agent: ureq::Agent,
+}
+
+impl Client {
+    /// Creates a client for the site at `base_url`, e.g. [DEFAULT_BASE_URL] or a local stub.
+    pub fn new(base_url: &str, session: Option<String>) -> Self {
+        Client {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session,
+            agent: ureq::AgentBuilder::new()
+                .timeout(Duration::from_secs(30))
+                .user_agent(USER_AGENT)
+                .build(),
+        }
+    }
+
+    /// Downloads the puzzle input of the logged in user.
+    pub fn input(&self, year: u32, day: u32) -> Result<String, ClientError> {
+        let request = self.request("GET", &format!("/{}/day/{}/input", year, day))?;
+        read(request.call())
+    }
+
+    /// Posts an answer, returning the HTML page the server responds with.
+    pub fn submit(
+        &self,
+        year: u32,
+        day: u32,
+        part: Part,
+        answer: &str,
+    ) -> Result<String, ClientError> {
+        let request = self.request("POST", &format!("/{}/day/{}/answer", year, day))?;
+        read(request.send_form(&[("level", &part.to_string()), ("answer", answer)]))
+    }
+
+    fn request(&self, method: &str, path: &str) -> Result<ureq::Request, ClientError> {
+        let session = self.session.as_ref().ok_or(ClientError::MissingSession)?;
+
+        Ok(self
+            .agent
+            .request(method, &format!("{}{}", self.base_url, path))
+            .set("Cookie", &format!("session={}", session)))
+    }
+}
+
+/// Reads the body of a successful response.
+fn read(response: Result<ureq::Response, ureq::Error>) -> Result<String, ClientError> {
+    match response {
+        Ok(response) => response
+            .into_string()
+            .map_err(|err| ClientError::Transport(err.to_string())),
+        Err(ureq::Error::Status(code, response)) => {
+            let body = response.into_string().unwrap_or_default();
+            let message = match body.trim() {
+                "" => "no details".to_string(),
+                body => body.lines().next().unwrap_or_default().to_string(),
+            };
+            Err(ClientError::Status { code, message })
+        }
+        Err(ureq::Error::Transport(err)) => Err(ClientError::Transport(err.to_string())),
+    }
+}
+
+/// A local HTTP server returning canned responses, standing in for the website in tests.
+#[cfg(test)]
+pub(crate) mod stub {
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+    use std::sync::{Arc, Mutex};
+    use std::thread;
+
+    /// A request received by the stub, with header names lower cased.
+    #[derive(Debug, Clone)]
+    pub struct Request {
+        pub method: String,
+        pub path: String,
+        pub headers: Vec<(String, String)>,
+        pub body: String,
+    }
+
+    impl Request {
+        pub fn header(&self, name: &str) -> Option<&str> {
+            self.headers
+                .iter()
+                .find(|(key, _)| key == name)
+                .map(|(_, value)| value.as_str())
+        }
+    }
+
+    pub struct StubServer {
+        pub url: String,
+        requests: Arc<Mutex<Vec<Request>>>,
+    }
+
+    impl StubServer {
+        /// Serves one connection per response, in order, then stops listening.
+        pub fn start(responses: Vec<(u16, &'static str)>) -> Self {
+            let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+            let url = format!("http://{}", listener.local_addr().unwrap());
+            let requests = Arc::new(Mutex::new(Vec::new()));
+
+            let received = Arc::clone(&requests);
+            thread::spawn(move || {
+                for (code, body) in responses {
+                    let (mut stream, _) = listener.accept().unwrap();
+                    let request = read_request(&mut BufReader::new(&stream));
+                    received.lock().unwrap().push(request);
+
+                    let response = format!(
+                        "HTTP/1.1 {} Stub\r\nContent-Type: text/plain\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                        code,
+                        body.len(),
+                        body
+                    );
+                    stream.write_all(response.as_bytes()).unwrap();
+                }
+            });
+
+            StubServer { url, requests }
+        }
+
+        pub fn requests(&self) -> Vec<Request> {
+            self.requests.lock().unwrap().clone()
+        }
+    }
+
+    fn read_request(reader: &mut impl BufRead) -> Request {
+        let mut line = String::new();
+        reader.read_line(&mut line).unwrap();
+        let mut request_line = line.split_whitespace();
+        let method = request_line.next().unwrap_or_default().to_string();
+        let path = request_line.next().unwrap_or_default().to_string();
+
+        let mut headers = Vec::new();
+        loop {
+            line.clear();
+            reader.read_line(&mut line).unwrap();
+            match line.trim_end().split_once(':') {
+                Some((name, value)) => {
+                    headers.push((name.to_lowercase(), value.trim().to_string()))
+                }
+                None => break,
+            }
+        }
+
+        let length = headers
+            .iter()
+            .find(|(name, _)| name == "content-length")
+            .map_or(0, |(_, value)| value.parse().unwrap());
+        let mut body = vec![0; length];
+        reader.read_exact(&mut body).unwrap();
+
+        Request {
+            method,
+            path,
+            headers,
+            body: String::from_utf8(body).unwrap(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::stub::StubServer;
+    use super::*;
+
+    #[test]
+    fn downloads_input() {
+        let server = StubServer::start(vec![(200, "1\n2\n3\n")]);
+        let client = Client::new(&server.url, Some("abc123".to_string()));
+
+        assert_eq!("1\n2\n3\n", client.input(2021, 1).unwrap());
+
+        let request = &server.requests()[0];
+        assert_eq!(
+            ("GET", "/2021/day/1/input"),
+            (request.method.as_str(), request.path.as_str())
+        );
+        assert_eq!(Some("session=abc123"), request.header("cookie"));
+        assert!(request
+            .header("user-agent")
+            .unwrap()
+            .starts_with("aoc-core/"));
+    }
+
+    #[test]
+    fn errors() {
+        let server = StubServer::start(vec![(
+            400,
+            "Puzzle inputs differ by user.  Please log in to get your puzzle input.\n",
+        )]);
+        let client = Client::new(&server.url, Some("expired".to_string()));
+
+        match client.input(2021, 1) {
+            Err(ClientError::Status { code, message }) => {
+                assert_eq!(400, code);
+                assert!(message.starts_with("Puzzle inputs differ by user."));
+            }
+            other => panic!("expected a status error, got {:?}", other),
+        }
+
+        let client = Client::new(&server.url, None);
+        assert!(matches!(
+            client.input(2021, 1),
+            Err(ClientError::MissingSession)
+        ));
+    }
+}
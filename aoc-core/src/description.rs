@@ -0,0 +1,386 @@
+//! Extracting examples from a saved puzzle description page.
+//!
+//! Each part of a puzzle is described in its own `<article>`. The example input is the first
+//! `<pre><code>` block of a part, and the example's answer is usually the last emphasised
+//! `<code><em>` of the part, so both are only candidates to be checked by hand.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::solver::{Answer, Part};
+use crate::verify;
+
+/// The description of one part.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PartDescription {
+    /// Contents of every `<pre><code>` block, in order.
+    pub blocks: Vec<String>,
+    /// The last emphasised code outside the blocks.
+    pub answer: Option<String>,
+}
+
+/// Splits a puzzle page into its parts.
+pub fn parse(html: &str) -> Vec<PartDescription> {
+    let mut articles = elements(html, "<article", "</article>");
+    if articles.is_empty() {
+        articles.push(html);
+    }
+
+    articles
+        .into_iter()
+        .map(|article| {
+            let blocks = elements(article, "<pre><code>", "</code></pre>");
+            let mut prose = article.to_string();
+            for block in &blocks {
+                prose = prose.replacen(block, "", 1);
+            }
+
+            PartDescription {
+                blocks: blocks.iter().map(|block| text(block)).collect(),
+                answer: elements(&prose, "<code><em>", "</em></code>")
+                    .last()
+                    .map(|answer| text(answer).trim().to_string()),
+            }
+        })
+        .collect()
+}
+
+/// Contents of the elements between each `open` and the following `close`.
+fn elements<'a>(html: &'a str, open: &str, close: &str) -> Vec<&'a str> {
+    let mut found = Vec::new();
+    let mut rest = html;
+
+    while let Some(start) = rest.find(open) {
+        let after = &rest[start + open.len()..];
+        // skip the rest of an opening tag with attributes, e.g. `<article class="day-desc">`
+        let after = match open.ends_with('>') {
+            true => after,
+            false => &after[after.find('>').map_or(0, |end| end + 1)..],
+        };
+        let Some(end) = after.find(close) else {
+            break;
+        };
+
+        found.push(&after[..end]);
+        rest = &after[end + close.len()..];
+    }
+
+    found
+}
+
+/// Text of an HTML fragment, without tags and with entities decoded.
+fn text(html: &str) -> String {
+    let mut text = String::new();
+    let mut rest = html;
+
+    while let Some(c) = rest.chars().next() {
+        match c {
+            '<' => match rest.find('>') {
+                Some(end) => rest = &rest[end + 1..],
+                None => break,
+            },
+            '&' => {
+                let entity = rest
+                    .find(';')
+                    .filter(|&end| end <= 10)
+                    .and_then(|end| Some((decode_entity(&rest[1..end])?, end)));
+                match entity {
+                    Some((decoded, end)) => {
+                        text.push(decoded);
+                        rest = &rest[end + 1..];
+                    }
+                    None => {
+                        text.push('&');
+                        rest = &rest[1..];
+                    }
+                }
+            }
+            c => {
+                text.push(c);
+                rest = &rest[c.len_utf8()..];
+            }
+        }
+    }
+
+    text
+}
+
+fn decode_entity(entity: &str) -> Option<char> {
+    match entity {
+        "lt" => Some('<'),
+        "gt" => Some('>'),
+        "amp" => Some('&'),
+        "quot" => Some('"'),
+        "apos" => Some('\''),
+        "nbsp" => Some(' '),
+        _ => {
+            let code = match entity
+                .strip_prefix("#x")
+                .or_else(|| entity.strip_prefix("#X"))
+            {
+                Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+                None => entity.strip_prefix('#')?.parse().ok()?,
+            };
+            char::from_u32(code)
+        }
+    }
+}
+
+/// An example input with the answers it's expected to give.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Example {
+    pub input: String,
+    pub part1: Option<Answer>,
+    pub part2: Option<Answer>,
+}
+
+/// Candidate examples of a puzzle: the first block of each part with that part's answer. The
+/// second part usually reuses the first part's example, so its answer is added to the first
+/// example unless the second part starts with a different block.
+pub fn examples(parts: &[PartDescription]) -> Vec<Example> {
+    let mut examples: Vec<Example> = Vec::new();
+
+    for (part, description) in Part::ALL.into_iter().zip(parts) {
+        let answer = description.answer.as_deref().map(answer);
+
+        let example = match description.blocks.first() {
+            Some(input) => match examples.iter_mut().find(|e| &e.input == input) {
+                Some(example) => example,
+                None => {
+                    examples.push(Example {
+                        input: input.clone(),
+                        part1: None,
+                        part2: None,
+                    });
+                    examples.last_mut().unwrap()
+                }
+            },
+            None => match examples.last_mut() {
+                Some(example) => example,
+                None => continue,
+            },
+        };
+
+        match part {
+            Part::One => example.part1 = answer,
+            Part::Two => example.part2 = answer,
+        }
+    }
+
+    examples
+}
+
+fn answer(text: &str) -> Answer {
+    match text.parse::<i64>() {
+        Ok(number) => Answer::from(number),
+        Err(_) => Answer::from(text),
+    }
+}
+
+/// Name of the nth example file, counting from 1: `test_input.txt`, `test_input2.txt`, ...
+pub fn example_file(n: usize) -> String {
+    match n {
+        1 => "test_input.txt".to_string(),
+        n => format!("test_input{}.txt", n),
+    }
+}
+
+/// Writes the examples into a day's directory, with their answers in its `answers.toml`.
+///
+/// Existing examples are kept, new ones take the next free `test_input*.txt` names. An empty
+/// example file, as generated by `aoc new`, is free and its recorded answers are replaced.
+/// Returns the written files.
+pub fn save_examples(dir: &Path, examples: &[Example]) -> Result<Vec<PathBuf>, String> {
+    let answers_path = dir.join(verify::ANSWERS_FILE);
+    let mut answers = match fs::read_to_string(&answers_path) {
+        Ok(answers) => answers,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => String::new(),
+        Err(err) => return Err(format!("couldn't read {}: {}", answers_path.display(), err)),
+    };
+
+    let mut written = Vec::new();
+    let mut n = 1;
+
+    for example in examples {
+        let path = loop {
+            let path = dir.join(example_file(n));
+            n += 1;
+            match fs::read_to_string(&path) {
+                Ok(contents) if !contents.trim().is_empty() => continue,
+                _ => break path,
+            }
+        };
+        let file = path.file_name().unwrap().to_string_lossy().into_owned();
+
+        fs::write(&path, &example.input)
+            .map_err(|err| format!("couldn't write {}: {}", path.display(), err))?;
+
+        answers = remove_table(&answers, &file);
+        if !answers.is_empty() && !answers.ends_with("\n\n") {
+            answers.push('\n');
+        }
+        answers.push_str(&format!("[\"{}\"]\n", file));
+        for (part, answer) in [("part1", &example.part1), ("part2", &example.part2)] {
+            match answer {
+                Some(Answer::Text(text)) => {
+                    answers.push_str(&format!("{} = {}\n", part, toml_string(text)))
+                }
+                Some(answer) => answers.push_str(&format!("{} = {}\n", part, answer)),
+                None => {}
+            }
+        }
+
+        written.push(path);
+    }
+
+    fs::write(&answers_path, answers)
+        .map_err(|err| format!("couldn't write {}: {}", answers_path.display(), err))?;
+
+    Ok(written)
+}
+
+/// Removes the table for `file` from the text of an answers file.
+fn remove_table(answers: &str, file: &str) -> String {
+    let header = format!("[\"{}\"]", file);
+    let mut kept = Vec::new();
+    let mut in_table = false;
+
+    for line in answers.lines() {
+        if line.trim_start().starts_with('[') {
+            in_table = line.trim() == header;
+        }
+        if !in_table {
+            kept.push(line);
+        }
+    }
+
+    let mut answers = kept.join("\n").trim_end().to_string();
+    if !answers.is_empty() {
+        answers.push('\n');
+    }
+    answers
+}
+
+fn toml_string(text: &str) -> String {
+    format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PAGE: &str = r#"<html><body><main>
+<article class="day-desc"><h2>--- Day 12: Passage Pathing ---</h2>
+<p>For example:</p>
+<pre><code>start-A
+start-b
+A-&gt;c
+</code></pre>
+<p>This cave system has the following <em>10</em> paths:</p>
+<pre><code>start,A,b,end
+</code></pre>
+<p>How many paths are there? In this example, there are <code><em>10</em></code>.</p>
+</article>
+<p>Your puzzle answer was <code>3292</code>.</p>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2>
+<p>Now, <code><em>start</em></code> can only be visited once, so there are <code><em>36</em></code>
+paths.</p>
+</article>
+</main></body></html>"#;
+
+    #[test]
+    fn parses_page() {
+        let parts = parse(PAGE);
+
+        assert_eq!(2, parts.len());
+        assert_eq!(
+            vec!["start-A\nstart-b\nA->c\n", "start,A,b,end\n"],
+            parts[0].blocks
+        );
+        assert_eq!(Some("10"), parts[0].answer.as_deref());
+        assert!(parts[1].blocks.is_empty());
+        assert_eq!(Some("36"), parts[1].answer.as_deref());
+
+        assert_eq!(
+            vec![Example {
+                input: "start-A\nstart-b\nA->c\n".to_string(),
+                part1: Some(Answer::Number(10)),
+                part2: Some(Answer::Number(36)),
+            }],
+            examples(&parts)
+        );
+    }
+
+    #[test]
+    fn separate_examples() {
+        let parts = vec![
+            PartDescription {
+                blocks: vec!["1\n".to_string()],
+                answer: Some("CMZ".to_string()),
+            },
+            PartDescription {
+                blocks: vec!["2\n".to_string(), "1\n".to_string()],
+                answer: Some("5".to_string()),
+            },
+        ];
+
+        let examples = examples(&parts);
+        assert_eq!(2, examples.len());
+        assert_eq!(
+            (Some(Answer::from("CMZ")), None),
+            (examples[0].part1.clone(), examples[0].part2.clone())
+        );
+        assert_eq!(
+            (None, Some(Answer::Number(5))),
+            (examples[1].part1.clone(), examples[1].part2.clone())
+        );
+        assert_eq!(
+            "a < b & \"c\" 'd'",
+            text("a &lt; <em>b</em> &amp; &quot;c&quot; &#39;d&#x27;")
+        );
+    }
+
+    #[test]
+    fn saves_examples() {
+        let dir = std::env::temp_dir().join(format!("aoc-examples-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("test_input.txt"), "").unwrap();
+        fs::write(dir.join("test_input2.txt"), "kept\n").unwrap();
+        fs::write(
+            dir.join(verify::ANSWERS_FILE),
+            "# answers\n[\"input.txt\"]\npart1 = 1\n\n[\"test_input.txt\"]\npart1 = 0\npart2 = 0\n\n[\"test_input2.txt\"]\npart1 = 2\n",
+        )
+        .unwrap();
+
+        let examples = [
+            Example {
+                input: "first\n".to_string(),
+                part1: Some(Answer::Number(10)),
+                part2: None,
+            },
+            Example {
+                input: "second\n".to_string(),
+                part1: None,
+                part2: Some(Answer::from("AB\"C")),
+            },
+        ];
+        let written = save_examples(&dir, &examples);
+        let answers = fs::read_to_string(dir.join(verify::ANSWERS_FILE)).unwrap();
+        let inputs = ["test_input.txt", "test_input2.txt", "test_input3.txt"]
+            .map(|file| fs::read_to_string(dir.join(file)).unwrap());
+        let expected = verify::load_expected(&dir.join("test_input3.txt"));
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(2, written.unwrap().len());
+        assert_eq!(["first\n", "kept\n", "second\n"], inputs);
+        assert_eq!(
+            "# answers\n[\"input.txt\"]\npart1 = 1\n\n[\"test_input2.txt\"]\npart1 = 2\n\n\
+             [\"test_input.txt\"]\npart1 = 10\n\n[\"test_input3.txt\"]\npart2 = \"AB\\\"C\"\n",
+            answers
+        );
+        assert_eq!(
+            Some(&Answer::from("AB\"C")),
+            expected.unwrap().unwrap().get(Part::Two)
+        );
+    }
+}
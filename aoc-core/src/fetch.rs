@@ -0,0 +1,171 @@
+//! Downloading puzzle inputs into a local cache.
+
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::client::{Client, ClientError};
+
+/// Default cache directory, relative to the working directory.
+pub const DEFAULT_CACHE_DIR: &str = ".aoc-cache";
+
+/// Location of a cached input.
+pub fn cache_path(cache_dir: &Path, year: u32, day: u32) -> PathBuf {
+    cache_dir
+        .join(year.to_string())
+        .join(day.to_string())
+        .join("input.txt")
+}
+
+/// Where a fetched input came from.
+#[derive(Debug)]
+pub enum Source {
+    Downloaded,
+    Cache,
+    /// The download failed, so the cached copy was used instead.
+    CacheFallback(ClientError),
+}
+
+#[derive(Debug)]
+pub struct Fetched {
+    pub input: String,
+    pub path: PathBuf,
+    pub source: Source,
+}
+
+#[derive(Debug)]
+pub enum FetchError {
+    Client(ClientError),
+    Cache(PathBuf, io::Error),
+}
+
+impl fmt::Display for FetchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FetchError::Client(err) => write!(f, "{}", err),
+            FetchError::Cache(path, err) => write!(f, "cache {}: {}", path.display(), err),
+        }
+    }
+}
+
+impl std::error::Error for FetchError {}
+
+/// Returns the input for a day, downloading it only when it isn't cached or `refresh` is set.
+///
+/// Downloads are saved to the cache. If a download can't reach the server, a cached copy is
+/// used when there is one.
+pub fn fetch_input(
+    client: &Client,
+    cache_dir: &Path,
+    year: u32,
+    day: u32,
+    refresh: bool,
+) -> Result<Fetched, FetchError> {
+    let path = cache_path(cache_dir, year, day);
+    let cached = || fs::read_to_string(&path).map_err(|err| FetchError::Cache(path.clone(), err));
+
+    if !refresh && path.exists() {
+        return Ok(Fetched {
+            input: cached()?,
+            source: Source::Cache,
+            path,
+        });
+    }
+
+    match client.input(year, day) {
+        Ok(input) => {
+            let save = || {
+                fs::create_dir_all(path.parent().expect("cache path has a parent"))?;
+                fs::write(&path, &input)
+            };
+            save().map_err(|err| FetchError::Cache(path.clone(), err))?;
+
+            Ok(Fetched {
+                input,
+                source: Source::Downloaded,
+                path,
+            })
+        }
+        Err(err @ ClientError::Transport(_)) if path.exists() => Ok(Fetched {
+            input: cached()?,
+            source: Source::CacheFallback(err),
+            path,
+        }),
+        Err(err) => Err(FetchError::Client(err)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::stub::StubServer;
+    use std::net::TcpListener;
+
+    fn cache_dir(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("aoc-cache-{}-{}", name, std::process::id()))
+    }
+
+    /// Address of a port nothing is listening on.
+    fn unreachable_url() -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        format!("http://{}", listener.local_addr().unwrap())
+    }
+
+    #[test]
+    fn cached_after_download() {
+        let dir = cache_dir("download");
+        let server = StubServer::start(vec![(200, "5\n6\n")]);
+        let client = Client::new(&server.url, Some("token".to_string()));
+
+        let first = fetch_input(&client, &dir, 2022, 3, false).unwrap();
+        let second = fetch_input(&client, &dir, 2022, 3, false).unwrap();
+        let saved = fs::read_to_string(cache_path(&dir, 2022, 3));
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert!(matches!(first.source, Source::Downloaded));
+        assert!(matches!(second.source, Source::Cache));
+        assert_eq!("5\n6\n", second.input);
+        assert_eq!("5\n6\n", saved.unwrap());
+        assert_eq!(1, server.requests().len());
+    }
+
+    #[test]
+    fn offline_fallback() {
+        let dir = cache_dir("offline");
+        let client = Client::new(&unreachable_url(), Some("token".to_string()));
+
+        let missing = fetch_input(&client, &dir, 2022, 4, true);
+
+        fs::create_dir_all(cache_path(&dir, 2022, 4).parent().unwrap()).unwrap();
+        fs::write(cache_path(&dir, 2022, 4), "cached").unwrap();
+        let fallback = fetch_input(&client, &dir, 2022, 4, true);
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert!(matches!(
+            missing,
+            Err(FetchError::Client(ClientError::Transport(_)))
+        ));
+
+        let fallback = fallback.unwrap();
+        assert_eq!("cached", fallback.input);
+        assert!(matches!(fallback.source, Source::CacheFallback(_)));
+    }
+
+    #[test]
+    fn server_errors_not_hidden_by_cache() {
+        let dir = cache_dir("errors");
+        let server = StubServer::start(vec![(404, "Not Found")]);
+        let client = Client::new(&server.url, Some("token".to_string()));
+
+        fs::create_dir_all(cache_path(&dir, 2022, 5).parent().unwrap()).unwrap();
+        fs::write(cache_path(&dir, 2022, 5), "cached").unwrap();
+        let result = fetch_input(&client, &dir, 2022, 5, true);
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert!(matches!(
+            result,
+            Err(FetchError::Client(ClientError::Status { code: 404, .. }))
+        ));
+    }
+}
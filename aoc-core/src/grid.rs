@@ -0,0 +1,286 @@
+//! Rectangular grids of puzzle cells, such as height maps and character maps.
+//!
+//! Positions are `(row, column)` pairs counted from the top left, matching ndarray's indexing, and
+//! directions are `(row, column)` steps. Neighbour and ray iterators only yield positions inside
+//! the grid, so callers never have to check bounds themselves.
+
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+use ndarray::Array2;
+
+use crate::parse::ParseError;
+
+/// A `(row, column)` position in a grid.
+pub type Position = (usize, usize);
+
+/// A `(row, column)` step between positions.
+pub type Direction = (isize, isize);
+
+pub const NORTH: Direction = (-1, 0);
+pub const SOUTH: Direction = (1, 0);
+pub const WEST: Direction = (0, -1);
+pub const EAST: Direction = (0, 1);
+
+/// Directions to the orthogonally adjacent positions, in reading order.
+pub const ORTHOGONAL: [Direction; 4] = [NORTH, WEST, EAST, SOUTH];
+
+/// Directions to the orthogonally and diagonally adjacent positions, in reading order.
+pub const ADJACENT: [Direction; 8] = [(-1, -1), NORTH, (-1, 1), WEST, EAST, (1, -1), SOUTH, (1, 1)];
+
+/// A rectangular grid of cells.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    cells: Array2<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn new(cells: Array2<T>) -> Self {
+        Grid { cells }
+    }
+
+    /// Creates a grid with every cell set to `value`.
+    pub fn from_elem(dim: (usize, usize), value: T) -> Self
+    where
+        T: Clone,
+    {
+        Grid::new(Array2::from_elem(dim, value))
+    }
+
+    /// Parses a map with a cell per character and a row per line, converting each character with
+    /// `cell`. Every row must have the same width. `name` describes a cell in error messages, e.g.
+    /// `digit` gives "expected a digit, found `x`".
+    pub fn parse<F>(input: &str, name: &str, mut cell: F) -> Result<Self, ParseError>
+    where
+        F: FnMut(char) -> Option<T>,
+    {
+        let rows = input.lines().collect::<Vec<_>>();
+        let width = rows.first().map_or(0, |row| row.chars().count());
+
+        let mut cells = Vec::with_capacity(rows.len() * width);
+
+        for row in &rows {
+            let length = row.chars().count();
+            if length != width {
+                let message = format!("expected {} {}s, found {}", width, name, length);
+                return Err(ParseError::at(input, row, message));
+            }
+
+            for (i, c) in row.char_indices() {
+                let value = cell(c).ok_or_else(|| {
+                    let message = format!("expected a {}, found `{}`", name, c);
+                    ParseError::at(input, &row[i..], message)
+                })?;
+                cells.push(value);
+            }
+        }
+
+        let cells =
+            Array2::from_shape_vec((rows.len(), width), cells).expect("rows have equal width");
+        Ok(Grid::new(cells))
+    }
+
+    /// The number of rows and columns.
+    pub fn dim(&self) -> (usize, usize) {
+        self.cells.dim()
+    }
+
+    pub fn height(&self) -> usize {
+        self.cells.nrows()
+    }
+
+    pub fn width(&self) -> usize {
+        self.cells.ncols()
+    }
+
+    pub fn contains(&self, (row, col): Position) -> bool {
+        row < self.height() && col < self.width()
+    }
+
+    /// The cell at `position`, if it's inside the grid.
+    pub fn get(&self, position: Position) -> Option<&T> {
+        self.cells.get(position)
+    }
+
+    pub fn get_mut(&mut self, position: Position) -> Option<&mut T> {
+        self.cells.get_mut(position)
+    }
+
+    /// The position one step from `position` in `direction`, if it's inside the grid.
+    pub fn step(&self, (row, col): Position, (dr, dc): Direction) -> Option<Position> {
+        let next = (row.checked_add_signed(dr)?, col.checked_add_signed(dc)?);
+        self.contains(next).then_some(next)
+    }
+
+    /// The orthogonally adjacent positions inside the grid.
+    pub fn neighbours4(&self, position: Position) -> impl Iterator<Item = Position> + '_ {
+        ORTHOGONAL
+            .into_iter()
+            .filter_map(move |direction| self.step(position, direction))
+    }
+
+    /// The orthogonally and diagonally adjacent positions inside the grid.
+    pub fn neighbours8(&self, position: Position) -> impl Iterator<Item = Position> + '_ {
+        ADJACENT
+            .into_iter()
+            .filter_map(move |direction| self.step(position, direction))
+    }
+
+    /// The positions from `position`, excluded, to the edge of the grid in `direction`, or none
+    /// for a `(0, 0)` direction which never reaches the edge.
+    pub fn ray(
+        &self,
+        position: Position,
+        direction: Direction,
+    ) -> impl Iterator<Item = Position> + '_ {
+        let first = match direction {
+            (0, 0) => None,
+            _ => self.step(position, direction),
+        };
+
+        std::iter::successors(first, move |&next| self.step(next, direction))
+    }
+
+    /// Every position with its cell, row by row.
+    pub fn indexed_iter(&self) -> impl Iterator<Item = (Position, &T)> {
+        self.cells.indexed_iter()
+    }
+
+    /// Every position, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = Position> {
+        let width = self.width();
+        (0..self.height()).flat_map(move |row| (0..width).map(move |col| (row, col)))
+    }
+
+    /// A grid of the same shape with `f` applied to every cell.
+    pub fn map<U, F>(&self, f: F) -> Grid<U>
+    where
+        F: FnMut(&T) -> U,
+    {
+        Grid::new(self.cells.map(f))
+    }
+
+    /// The underlying array, for whole row, column or slice operations.
+    pub fn as_array(&self) -> &Array2<T> {
+        &self.cells
+    }
+
+    pub fn as_array_mut(&mut self) -> &mut Array2<T> {
+        &mut self.cells
+    }
+
+    pub fn into_array(self) -> Array2<T> {
+        self.cells
+    }
+}
+
+impl Grid<u32> {
+    /// Parses a map of single digits, one row per line.
+    pub fn digits(input: &str) -> Result<Self, ParseError> {
+        Grid::parse(input, "digit", |c| c.to_digit(10))
+    }
+}
+
+impl<T> From<Array2<T>> for Grid<T> {
+    fn from(cells: Array2<T>) -> Self {
+        Grid::new(cells)
+    }
+}
+
+impl<T> Index<Position> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, position: Position) -> &T {
+        &self.cells[position]
+    }
+}
+
+impl<T> IndexMut<Position> for Grid<T> {
+    fn index_mut(&mut self, position: Position) -> &mut T {
+        &mut self.cells[position]
+    }
+}
+
+/// Renders the grid as its map, each cell's `Display` output in turn with a line per row.
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, row) in self.cells.rows().into_iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_maps() {
+        let grid = Grid::parse("#.\n.#\n", "cell", |c| match c {
+            '#' => Some(true),
+            '.' => Some(false),
+            _ => None,
+        })
+        .unwrap();
+        assert_eq!((2, 2), grid.dim());
+        assert!(grid[(1, 1)] && !grid[(0, 1)]);
+        assert_eq!("10\n01", grid.map(|&c| c as u8).to_string());
+
+        let digits = Grid::digits("123\n456").unwrap();
+        assert_eq!("123\n456", digits.to_string());
+        assert_eq!(Some(&6), digits.get((1, 2)));
+        assert_eq!(None, digits.get((2, 0)));
+
+        let err = Grid::digits("123\n4x6").unwrap_err();
+        assert_eq!(
+            (2, 2, "expected a digit, found `x`"),
+            (err.line, err.column, err.message.as_str())
+        );
+
+        let err = Grid::digits("123\n45").unwrap_err();
+        assert_eq!(
+            (2, 1, "expected 3 digits, found 2"),
+            (err.line, err.column, err.message.as_str())
+        );
+    }
+
+    #[test]
+    fn neighbours() {
+        let grid = Grid::from_elem((3, 4), 0);
+
+        assert_eq!(
+            vec![(0, 1), (1, 0)],
+            grid.neighbours4((0, 0)).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![(0, 1), (1, 0), (1, 2), (2, 1)],
+            grid.neighbours4((1, 1)).collect::<Vec<_>>()
+        );
+        assert_eq!(3, grid.neighbours8((2, 3)).count());
+        assert_eq!(8, grid.neighbours8((1, 2)).count());
+        assert_eq!(None, grid.step((0, 3), EAST));
+    }
+
+    #[test]
+    fn rays() {
+        let grid = Grid::from_elem((3, 4), 0);
+
+        assert_eq!(
+            vec![(1, 2), (1, 3)],
+            grid.ray((1, 1), EAST).collect::<Vec<_>>()
+        );
+        assert_eq!(vec![(0, 0)], grid.ray((1, 1), (-1, -1)).collect::<Vec<_>>());
+        assert_eq!(0, grid.ray((0, 1), NORTH).count());
+        assert_eq!(0, grid.ray((1, 1), (0, 0)).count());
+        assert_eq!(
+            vec![(0, 0), (0, 1), (0, 2), (0, 3), (1, 0)],
+            grid.positions().take(5).collect::<Vec<_>>()
+        );
+    }
+}
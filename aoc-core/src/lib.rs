@@ -0,0 +1,28 @@
+//! Shared building blocks for the Advent of Code year crates.
+//!
+//! Each puzzle implements [Solver] and is added to a [Registry] by its year crate, so runners,
+//! tests and reports all work from the same puzzle metadata.
+
+pub use crate::grid::Grid;
+pub use crate::parse::ParseError;
+pub use crate::registry::Registry;
+pub use crate::runner::{print_solution, read_input, solve_part, Outcome};
+pub use crate::solver::{Answer, Options, Part, Puzzle, Solver};
+
+pub mod bench;
+pub mod cli;
+pub mod client;
+pub mod description;
+pub mod fetch;
+pub mod grid;
+pub mod ocr;
+pub mod parse;
+pub mod picture;
+mod registry;
+pub mod report;
+mod runner;
+pub mod scaffold;
+pub mod search;
+mod solver;
+pub mod submit;
+pub mod verify;
@@ -0,0 +1,238 @@
+//! Recognition of the capital letters Advent of Code draws with pixels.
+//!
+//! Two glyph sets are known: 4x6 letters spaced 5 pixels apart (e.g. 2021 day 13, 2022 day 10)
+//! and 6x10 letters spaced 8 pixels apart (e.g. 2018 day 10).
+
+use std::fmt;
+
+use ndarray::{s, Array2};
+
+/// Letter bitmaps with rows joined top to bottom, '#' marking lit pixels.
+const SMALL_GLYPHS: [(char, &str); 17] = [
+    ('A', ".##.#..##..######..##..#"),
+    ('B', "###.#..####.#..##..####."),
+    ('C', ".##.#..##...#...#..#.##."),
+    ('E', "#####...###.#...#...####"),
+    ('F', "#####...###.#...#...#..."),
+    ('G', ".##.#..##...#.###..#.###"),
+    ('H', "#..##..######..##..##..#"),
+    ('I', ".###..#...#...#...#..###"),
+    ('J', "..##...#...#...##..#.##."),
+    ('K', "#..##.#.##..#.#.#.#.#..#"),
+    ('L', "#...#...#...#...#...####"),
+    ('O', ".##.#..##..##..##..#.##."),
+    ('P', "###.#..##..####.#...#..."),
+    ('R', "###.#..##..####.#.#.#..#"),
+    ('S', ".####...#....##....####."),
+    ('U', "#..##..##..##..##..#.##."),
+    ('Z', "####...#..#..#..#...####"),
+];
+
+#[rustfmt::skip]
+const LARGE_GLYPHS: [(char, &str); 15] = [
+    ('A', "..##...#..#.#....##....##....########....##....##....##....#"),
+    ('B', "#####.#....##....##....######.#....##....##....##....######."),
+    ('C', ".####.#....##.....#.....#.....#.....#.....#.....#....#.####."),
+    ('E', "#######.....#.....#.....#####.#.....#.....#.....#.....######"),
+    ('F', "#######.....#.....#.....#####.#.....#.....#.....#.....#....."),
+    ('G', ".####.#....##.....#.....#.....#..####....##....##...##.###.#"),
+    ('H', "#....##....##....##....########....##....##....##....##....#"),
+    ('J', "...###....#.....#.....#.....#.....#.....#.#...#.#...#..###.."),
+    ('K', "#....##...#.#..#..#.#...##....##....#.#...#..#..#...#.#....#"),
+    ('L', "#.....#.....#.....#.....#.....#.....#.....#.....#.....######"),
+    ('N', "#....###...###...##.#..##.#..##..#.##..#.##...###...###....#"),
+    ('P', "#####.#....##....##....######.#.....#.....#.....#.....#....."),
+    ('R', "#####.#....##....##....######.#..#..#...#.#...#.#....##....#"),
+    ('X', "#....##....#.#..#..#..#...##....##...#..#..#..#.#....##....#"),
+    ('Z', "######.....#.....#....#....#....#....#....#.....#.....######"),
+];
+
+/// A glyph that didn't match any known letter.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Glyph {
+    /// Zero based position of the glyph in the line of text.
+    pub position: usize,
+    /// Glyph pixels, one row per line with '#' for lit pixels.
+    pub bitmap: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OcrError {
+    /// The image isn't 6 or 10 pixels high, so doesn't use a known glyph set.
+    UnsupportedHeight(usize),
+    Unrecognised(Vec<Glyph>),
+}
+
+impl fmt::Display for OcrError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OcrError::UnsupportedHeight(height) => write!(
+                f,
+                "can't read letters {} pixels high, expected 6 or 10",
+                height
+            ),
+            OcrError::Unrecognised(glyphs) => {
+                write!(f, "{} unrecognised glyph(s)", glyphs.len())?;
+                for glyph in glyphs {
+                    write!(f, "\nglyph {}:\n{}", glyph.position, glyph.bitmap)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl std::error::Error for OcrError {}
+
+/// Reads the letters drawn by the lit pixels of an image.
+///
+/// The image height picks the glyph set. Blank trailing columns may be omitted, and blank space
+/// in place of a letter is skipped.
+pub fn recognise(pixels: &Array2<bool>) -> Result<String, OcrError> {
+    let (height, width) = pixels.dim();
+
+    let (glyphs, glyph_width, stride) = match height {
+        6 => (&SMALL_GLYPHS[..], 4, 5),
+        10 => (&LARGE_GLYPHS[..], 6, 8),
+        _ => return Err(OcrError::UnsupportedHeight(height)),
+    };
+
+    let mut text = String::new();
+    let mut unrecognised = Vec::<Glyph>::new();
+
+    for (position, start) in (0..width).step_by(stride).enumerate() {
+        let end = (start + glyph_width).min(width);
+
+        // pad glyphs cut short by the edge of the image with blank columns
+        let mut glyph = Array2::<bool>::default((height, glyph_width));
+        glyph
+            .slice_mut(s![.., ..end - start])
+            .assign(&pixels.slice(s![.., start..end]));
+
+        if glyph.iter().all(|lit| !lit) {
+            continue;
+        }
+
+        let key = glyph
+            .iter()
+            .map(|lit| if *lit { '#' } else { '.' })
+            .collect::<String>();
+
+        match glyphs.iter().find(|(_, bitmap)| *bitmap == key) {
+            Some((letter, _)) => text.push(*letter),
+            None => unrecognised.push(Glyph {
+                position,
+                bitmap: render(&glyph),
+            }),
+        }
+    }
+
+    if unrecognised.is_empty() {
+        Ok(text)
+    } else {
+        Err(OcrError::Unrecognised(unrecognised))
+    }
+}
+
+/// Converts text drawn with '#' for lit pixels, one row per line, into an image.
+///
+/// Short rows are padded with unlit pixels.
+pub fn parse_pixels(text: &str) -> Array2<bool> {
+    let rows = text.lines().collect::<Vec<_>>();
+    let width = rows.iter().map(|row| row.len()).max().unwrap_or(0);
+
+    Array2::from_shape_fn((rows.len(), width), |(y, x)| {
+        rows[y].as_bytes().get(x) == Some(&b'#')
+    })
+}
+
+/// Renders an image with '#' for lit pixels and '.' for unlit ones, one row per line.
+pub fn render(pixels: &Array2<bool>) -> String {
+    pixels
+        .rows()
+        .into_iter()
+        .map(|row| {
+            row.iter()
+                .map(|lit| if *lit { '#' } else { '.' })
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn small_letters() {
+        let pixels = parse_pixels(concat!(
+            "####.#..#..##..###..#..#..##..###..#..#.\n",
+            "...#.#.#..#..#.#..#.#.#..#..#.#..#.#.#..\n",
+            "..#..##...#....#..#.##...#....#..#.##...\n",
+            ".#...#.#..#.##.###..#.#..#.##.###..#.#..\n",
+            "#....#.#..#..#.#.#..#.#..#..#.#.#..#.#..\n",
+            "####.#..#..###.#..#.#..#..###.#..#.#..#.",
+        ));
+
+        assert_eq!(Ok("ZKGRKGRK".to_string()), recognise(&pixels));
+    }
+
+    #[test]
+    fn small_letters_trimmed() {
+        // the final blank column is missing, as in folded origami paper
+        let pixels = parse_pixels(concat!(
+            ".##..###\n",
+            "#..#.#..#\n",
+            "#..#.###\n",
+            "####.#..#\n",
+            "#..#.#..#\n",
+            "#..#.###",
+        ));
+
+        assert_eq!(Ok("AB".to_string()), recognise(&pixels));
+    }
+
+    #[test]
+    fn large_letters() {
+        let pixels = parse_pixels(concat!(
+            "#....#..#####.\n",
+            "#....#..#....#\n",
+            ".#..#...#....#\n",
+            ".#..#...#....#\n",
+            "..##....#####.\n",
+            "..##....#.....\n",
+            ".#..#...#.....\n",
+            ".#..#...#.....\n",
+            "#....#..#.....\n",
+            "#....#..#.....",
+        ));
+
+        assert_eq!(Ok("XP".to_string()), recognise(&pixels));
+    }
+
+    #[test]
+    fn unrecognised_glyphs() {
+        let pixels = parse_pixels(concat!(
+            "####.#..#\n",
+            "...#.##.#\n",
+            "..#..#.##\n",
+            ".#...#..#\n",
+            "#....#..#\n",
+            "####.#..#",
+        ));
+
+        let glyph = Glyph {
+            position: 1,
+            bitmap: "#..#\n##.#\n#.##\n#..#\n#..#\n#..#".to_string(),
+        };
+        assert_eq!(Err(OcrError::Unrecognised(vec![glyph])), recognise(&pixels));
+    }
+
+    #[test]
+    fn unsupported_height() {
+        let pixels = parse_pixels("#..#\n####");
+
+        assert_eq!(Err(OcrError::UnsupportedHeight(2)), recognise(&pixels));
+    }
+}
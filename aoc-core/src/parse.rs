@@ -0,0 +1,180 @@
+//! Error reporting for puzzle input parsers.
+
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+/// A problem found while parsing puzzle input, located by line and column.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// Input file, when the input was read from one.
+    pub file: Option<PathBuf>,
+    /// One based line number.
+    pub line: usize,
+    /// One based column number, counted in characters.
+    pub column: usize,
+    /// The input line containing the problem.
+    pub excerpt: String,
+    pub message: String,
+}
+
+impl ParseError {
+    /// Creates an error located at `fragment`, which must be a slice of `input`.
+    ///
+    /// Parsers usually hold slices of the original input (from `lines`, `split` or a nom
+    /// parser's remaining input), so the position is recovered from the slice itself. A fragment
+    /// from elsewhere is reported at the end of the input.
+    pub fn at(input: &str, fragment: &str, message: impl Into<String>) -> Self {
+        let start = input.as_ptr() as usize;
+        let position = fragment.as_ptr() as usize;
+
+        let offset = if (start..=start + input.len()).contains(&position) {
+            position - start
+        } else {
+            input.len()
+        };
+
+        let line_start = input[..offset].rfind('\n').map_or(0, |i| i + 1);
+        let line_end = input[offset..]
+            .find('\n')
+            .map_or(input.len(), |i| offset + i);
+
+        ParseError {
+            file: None,
+            line: input[..offset].matches('\n').count() + 1,
+            column: input[line_start..offset].chars().count() + 1,
+            excerpt: input[line_start..line_end]
+                .trim_end_matches('\r')
+                .to_string(),
+            message: message.into(),
+        }
+    }
+
+    /// Creates an error for input that ended before parsing was complete.
+    pub fn end_of_input(input: &str, message: impl Into<String>) -> Self {
+        ParseError::at(input, &input[input.len()..], message)
+    }
+
+    /// Converts a nom error from parsing a slice of `input`.
+    pub fn from_nom(input: &str, err: nom::Err<nom::error::Error<&str>>) -> Self {
+        match err {
+            nom::Err::Incomplete(_) => ParseError::end_of_input(input, "unexpected end of input"),
+            nom::Err::Error(err) | nom::Err::Failure(err) => ParseError::at(
+                input,
+                err.input,
+                format!("unexpected input ({} failed)", err.code.description()),
+            ),
+        }
+    }
+
+    /// Records the file the input was read from.
+    pub fn with_file(mut self, file: &Path) -> Self {
+        self.file = Some(file.to_path_buf());
+        self
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(file) = &self.file {
+            write!(f, "{}:", file.display())?;
+        }
+        writeln!(f, "{}:{}: {}", self.line, self.column, self.message)?;
+
+        let gutter = " ".repeat(self.line.to_string().len());
+        writeln!(f, "{} |", gutter)?;
+        writeln!(f, "{} | {}", self.line, self.excerpt)?;
+        write!(f, "{} | {}^", gutter, " ".repeat(self.column - 1))
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Parses `token`, a slice of `input`, as a number.
+pub fn number<T: FromStr>(input: &str, token: &str) -> Result<T, ParseError> {
+    token.trim().parse::<T>().map_err(|_| {
+        ParseError::at(
+            input,
+            token,
+            format!("expected a number, found `{}`", token),
+        )
+    })
+}
+
+/// Returns the next item from an iterator over slices of `input`, or an error describing what
+/// was missing.
+pub fn next<'a, I>(input: &str, items: &mut I, expected: &str) -> Result<&'a str, ParseError>
+where
+    I: Iterator<Item = &'a str>,
+{
+    items
+        .next()
+        .ok_or_else(|| ParseError::end_of_input(input, format!("missing {}", expected)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nom::character::complete::digit1;
+
+    #[test]
+    fn location() {
+        let input = "123\n45x6\n789";
+        let token = &input[6..7];
+
+        let err = ParseError::at(input, token, "bad digit");
+        assert_eq!((2, 3), (err.line, err.column));
+        assert_eq!("45x6", err.excerpt);
+
+        let err = ParseError::end_of_input(input, "truncated");
+        assert_eq!((3, 4), (err.line, err.column));
+        assert_eq!("789", err.excerpt);
+    }
+
+    #[test]
+    fn display() {
+        let input = "1\n2\n3\n4\n5\n6\n7\n8\n9\n10 11";
+        let err = ParseError::at(input, &input[21..], "trailing number")
+            .with_file(Path::new("input.txt"));
+
+        assert_eq!(
+            concat!(
+                "input.txt:10:4: trailing number\n",
+                "   |\n",
+                "10 | 10 11\n",
+                "   |    ^",
+            ),
+            err.to_string()
+        );
+    }
+
+    #[test]
+    fn numbers() {
+        let input = "12,x4";
+        let mut tokens = input.split(',');
+
+        assert_eq!(
+            Ok(12),
+            number::<u32>(input, next(input, &mut tokens, "a").unwrap())
+        );
+
+        let err = number::<u32>(input, next(input, &mut tokens, "b").unwrap()).unwrap_err();
+        assert_eq!(
+            (1, 4, "expected a number, found `x4`"),
+            (err.line, err.column, err.message.as_str())
+        );
+
+        let err = next(input, &mut tokens, "a third number").unwrap_err();
+        assert_eq!("missing a third number", err.message);
+    }
+
+    #[test]
+    fn nom_errors() {
+        let input = "ab\ncd";
+        let err = digit1::<&str, nom::error::Error<&str>>(&input[3..]).unwrap_err();
+
+        let err = ParseError::from_nom(input, err);
+        assert_eq!((2, 1), (err.line, err.column));
+        assert_eq!("unexpected input (Digit failed)", err.message);
+    }
+}
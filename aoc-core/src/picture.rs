@@ -0,0 +1,118 @@
+//! Pictures of puzzle state, shown in a terminal or exported as an image.
+//!
+//! A [Picture] is a grid of coloured glyphs. In a terminal each glyph is printed in its colour
+//! using ANSI escape codes, while the image export paints each cell as a square of its colour in
+//! a binary PPM file, which most image viewers open without any extra tooling.
+
+use std::fmt::Write as _;
+
+use crate::grid::Grid;
+
+/// A 24 bit colour.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+impl Rgb {
+    pub const BLACK: Rgb = Rgb(0, 0, 0);
+    pub const WHITE: Rgb = Rgb(255, 255, 255);
+    pub const RED: Rgb = Rgb(230, 40, 40);
+
+    /// A shade of grey, from black at 0 to white at `max`.
+    pub fn grey(level: u32, max: u32) -> Rgb {
+        let shade = (level.min(max) * 255 / max.max(1)) as u8;
+        Rgb(shade, shade, shade)
+    }
+}
+
+/// One cell of a picture.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Pixel {
+    pub glyph: char,
+    pub colour: Rgb,
+}
+
+pub type Picture = Grid<Pixel>;
+
+/// Renders a picture for a terminal, each glyph in its colour.
+pub fn ansi(picture: &Picture) -> String {
+    let mut text = String::new();
+
+    for (i, row) in picture.as_array().rows().into_iter().enumerate() {
+        if i > 0 {
+            text.push('\n');
+        }
+
+        let mut colour = None;
+        for pixel in row {
+            if colour != Some(pixel.colour) {
+                let Rgb(r, g, b) = pixel.colour;
+                write!(text, "\x1b[38;2;{};{};{}m", r, g, b).unwrap();
+                colour = Some(pixel.colour);
+            }
+            text.push(pixel.glyph);
+        }
+        text.push_str("\x1b[0m");
+    }
+
+    text
+}
+
+/// Encodes a picture as a binary PPM image, with each cell a `scale` pixels wide square.
+pub fn ppm(picture: &Picture, scale: usize) -> Vec<u8> {
+    let (height, width) = picture.dim();
+    let mut image = format!("P6\n{} {}\n255\n", width * scale, height * scale).into_bytes();
+
+    for row in picture.as_array().rows() {
+        let line = row
+            .iter()
+            .flat_map(|pixel| {
+                let Rgb(r, g, b) = pixel.colour;
+                [r, g, b].repeat(scale)
+            })
+            .collect::<Vec<_>>();
+
+        for _ in 0..scale {
+            image.extend_from_slice(&line);
+        }
+    }
+
+    image
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn picture() -> Picture {
+        Grid::parse("ab\nc.", "cell", |glyph| {
+            let colour = match glyph {
+                'a' | 'b' => Rgb::RED,
+                _ => Rgb::grey(1, 2),
+            };
+            Some(Pixel { glyph, colour })
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn terminal() {
+        assert_eq!(
+            concat!(
+                "\x1b[38;2;230;40;40mab\x1b[0m\n",
+                "\x1b[38;2;127;127;127mc.\x1b[0m",
+            ),
+            ansi(&picture())
+        );
+    }
+
+    #[test]
+    fn image() {
+        let image = ppm(&picture(), 2);
+        let header = b"P6\n4 4\n255\n";
+
+        assert_eq!(header, &image[..header.len()]);
+        assert_eq!(header.len() + 4 * 4 * 3, image.len());
+        assert_eq!([230, 40, 40], image[header.len()..header.len() + 3]);
+        assert_eq!([127, 127, 127], image[image.len() - 3..]);
+    }
+}
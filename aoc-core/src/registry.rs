@@ -0,0 +1,92 @@
+use std::sync::Arc;
+
+use crate::solver::{Puzzle, Solver};
+
+/// Collection of every puzzle known to a runner, ordered by year and then day.
+#[derive(Default)]
+pub struct Registry {
+    puzzles: Vec<Arc<dyn Puzzle>>,
+}
+
+impl Registry {
+    pub fn new() -> Self {
+        Registry::default()
+    }
+
+    /// Adds a puzzle to the registry, keeping puzzles in year/day order.
+    ///
+    /// Panics if a puzzle for the same year and day has already been registered.
+    pub fn register<S>(&mut self, solver: S)
+    where
+        S: Solver + Send + Sync + 'static,
+    {
+        let key = (Solver::year(&solver), Solver::day(&solver));
+
+        match self
+            .puzzles
+            .binary_search_by_key(&key, |p| (p.year(), p.day()))
+        {
+            Ok(_) => panic!("Puzzle for {} day {} registered twice", key.0, key.1),
+            Err(index) => self.puzzles.insert(index, Arc::new(solver)),
+        }
+    }
+
+    pub fn get(&self, year: u32, day: u32) -> Option<&Arc<dyn Puzzle>> {
+        self.iter().find(|p| p.year() == year && p.day() == day)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Arc<dyn Puzzle>> {
+        self.puzzles.iter()
+    }
+
+    /// Returns the puzzles registered for the given year in day order.
+    pub fn year(&self, year: u32) -> impl Iterator<Item = &Arc<dyn Puzzle>> {
+        self.iter().filter(move |p| p.year() == year)
+    }
+
+    pub fn years(&self) -> Vec<u32> {
+        let mut years = self.iter().map(|p| p.year()).collect::<Vec<_>>();
+        years.dedup();
+        years
+    }
+
+    pub fn len(&self) -> usize {
+        self.puzzles.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.puzzles.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solver::tests::Fixture;
+
+    #[test]
+    fn registration_order() {
+        let mut registry = Registry::new();
+        registry.register(Fixture::new(2022, 3));
+        registry.register(Fixture::new(2021, 15));
+        registry.register(Fixture::new(2022, 1));
+
+        let order = registry
+            .iter()
+            .map(|p| (p.year(), p.day()))
+            .collect::<Vec<_>>();
+        assert_eq!(vec![(2021, 15), (2022, 1), (2022, 3)], order);
+        assert_eq!(vec![2021, 2022], registry.years());
+        assert_eq!(2, registry.year(2022).count());
+        assert_eq!("Fixture", registry.get(2021, 15).unwrap().title());
+        assert!(registry.get(2021, 16).is_none());
+    }
+
+    #[test]
+    #[should_panic]
+    fn duplicate_registration() {
+        let mut registry = Registry::new();
+        registry.register(Fixture::new(2021, 1));
+        registry.register(Fixture::new(2021, 1));
+    }
+}
@@ -0,0 +1,399 @@
+//! Structured run reports, rendered as JSON, CSV or Markdown.
+
+use std::any::Any;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use clap::ValueEnum;
+use serde::Serialize;
+
+use crate::runner::{solve_part, Outcome};
+use crate::solver::{Answer, Part, Puzzle};
+
+/// Start and end markers of the generated progress table in the README.
+pub const PROGRESS_START: &str = "<!-- progress:start -->";
+pub const PROGRESS_END: &str = "<!-- progress:end -->";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Status {
+    Solved,
+    /// Ran out of time, see `--max-time`.
+    Skipped,
+    /// The input couldn't be parsed, or a part failed.
+    Failed,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PartReport {
+    pub part: Part,
+    pub status: Status,
+    pub answer: Option<Answer>,
+    /// Wall time, for solved parts only.
+    pub seconds: Option<f64>,
+    /// Why the part failed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Outcome of running one day's puzzle.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DayReport {
+    pub year: u32,
+    pub day: u32,
+    pub title: String,
+    /// Solved only when every selected part was solved, and failed when any part failed.
+    pub status: Status,
+    pub parse_seconds: Option<f64>,
+    pub parts: Vec<PartReport>,
+    /// Why the input couldn't be parsed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl DayReport {
+    fn part(&self, part: Part) -> Option<&PartReport> {
+        self.parts.iter().find(|p| p.part == part)
+    }
+
+    /// Number of solved parts.
+    pub fn stars(&self) -> usize {
+        self.parts
+            .iter()
+            .filter(|p| p.status == Status::Solved)
+            .count()
+    }
+}
+
+/// Parses the input and solves the requested parts, recording answers and timings.
+///
+/// Parse errors are recorded in the report rather than returned, so one bad input doesn't stop
+/// a report covering many days.
+pub fn run_puzzle(
+    puzzle: &Arc<dyn Puzzle>,
+    input: &str,
+    parts: &[Part],
+    max_time: Option<Duration>,
+) -> DayReport {
+    let mut report = DayReport {
+        year: puzzle.year(),
+        day: puzzle.day(),
+        title: puzzle.title().to_string(),
+        status: Status::Failed,
+        parse_seconds: None,
+        parts: Vec::new(),
+        error: None,
+    };
+
+    let start = Instant::now();
+    let input: Arc<dyn Any + Send + Sync> = match puzzle.parse(input) {
+        Ok(input) => Arc::from(input),
+        Err(err) => {
+            report.error = Some(format!(
+                "line {}, column {}: {}",
+                err.line, err.column, err.message
+            ));
+            return report;
+        }
+    };
+    report.parse_seconds = Some(start.elapsed().as_secs_f64());
+
+    for &part in parts {
+        let start = Instant::now();
+        let part_report = match solve_part(puzzle, &input, part, max_time) {
+            Outcome::Solved(answer) => PartReport {
+                part,
+                status: Status::Solved,
+                answer: Some(answer),
+                seconds: Some(start.elapsed().as_secs_f64()),
+                error: None,
+            },
+            Outcome::Skipped(_) => PartReport {
+                part,
+                status: Status::Skipped,
+                answer: None,
+                seconds: None,
+                error: None,
+            },
+            Outcome::Failed(message) => PartReport {
+                part,
+                status: Status::Failed,
+                answer: None,
+                seconds: None,
+                error: Some(message),
+            },
+        };
+        report.parts.push(part_report);
+    }
+
+    report.status = if report.parts.iter().any(|p| p.status == Status::Failed) {
+        Status::Failed
+    } else if report.parts.iter().all(|p| p.status == Status::Solved) {
+        Status::Solved
+    } else {
+        Status::Skipped
+    };
+
+    report
+}
+
+/// Output format of the `run` command.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum Format {
+    /// Human readable solutions, printed as each part finishes
+    #[default]
+    Text,
+    Json,
+    Csv,
+    Markdown,
+}
+
+impl Format {
+    /// Renders the reports, or `None` for the text format which is printed while running.
+    pub fn render(self, reports: &[DayReport]) -> Option<String> {
+        match self {
+            Format::Text => None,
+            Format::Json => Some(to_json(reports)),
+            Format::Csv => Some(to_csv(reports)),
+            Format::Markdown => Some(to_markdown(reports)),
+        }
+    }
+}
+
+pub fn to_json(reports: &[DayReport]) -> String {
+    serde_json::to_string_pretty(reports).expect("reports serialise to JSON") + "\n"
+}
+
+/// Renders one row per day, with the answer and time of each part in their own columns.
+pub fn to_csv(reports: &[DayReport]) -> String {
+    let mut csv = String::from(
+        "year,day,title,status,parse_seconds,part1_answer,part1_seconds,part2_answer,part2_seconds,error\n",
+    );
+
+    for report in reports {
+        let mut fields = vec![
+            report.year.to_string(),
+            report.day.to_string(),
+            csv_field(&report.title),
+            status_name(report.status).to_string(),
+            optional(report.parse_seconds),
+        ];
+
+        for part in Part::ALL {
+            let part = report.part(part);
+            let answer = part.and_then(|p| p.answer.as_ref());
+            fields.push(csv_field(&answer.map_or(String::new(), |a| a.to_string())));
+            fields.push(optional(part.and_then(|p| p.seconds)));
+        }
+
+        fields.push(csv_field(&errors(report).join("; ")));
+
+        csv.push_str(&fields.join(","));
+        csv.push('\n');
+    }
+
+    csv
+}
+
+/// Renders a Markdown table of answers and timings.
+pub fn to_markdown(reports: &[DayReport]) -> String {
+    let mut table = String::from(
+        "| Year | Day | Puzzle | Part 1 | Part 2 | Parse | Status |\n\
+         | ---: | --: | :----- | :----- | :----- | ----: | :----- |\n",
+    );
+
+    for report in reports {
+        let cell = |part| match report.part(part) {
+            Some(PartReport {
+                answer: Some(answer),
+                seconds: Some(seconds),
+                ..
+            }) => format!(
+                "{} ({:.2?})",
+                markdown_cell(&answer.to_string()),
+                Duration::from_secs_f64(*seconds)
+            ),
+            Some(part) => status_name(part.status).to_string(),
+            None => String::new(),
+        };
+
+        table.push_str(&format!(
+            "| {} | {} | {} | {} | {} | {} | {} |\n",
+            report.year,
+            report.day,
+            markdown_cell(&report.title),
+            cell(Part::One),
+            cell(Part::Two),
+            report.parse_seconds.map_or(String::new(), |s| format!(
+                "{:.2?}",
+                Duration::from_secs_f64(s)
+            )),
+            status_name(report.status),
+        ));
+    }
+
+    table
+}
+
+/// Renders the README progress table, listing the stars earned for each day.
+pub fn progress_table(reports: &[DayReport]) -> String {
+    let mut table =
+        String::from("| Year | Day | Puzzle | Stars |\n| ---: | --: | :----- | :---- |\n");
+
+    for report in reports {
+        table.push_str(&format!(
+            "| {} | {} | {} | {} |\n",
+            report.year,
+            report.day,
+            markdown_cell(&report.title),
+            "★".repeat(report.stars()),
+        ));
+    }
+
+    table
+}
+
+/// Replaces the text between the progress markers of a README with `table`.
+pub fn update_progress(readme: &str, table: &str) -> Result<String, String> {
+    let start = readme
+        .find(PROGRESS_START)
+        .ok_or_else(|| format!("missing `{}` marker", PROGRESS_START))?
+        + PROGRESS_START.len();
+    let end = readme[start..]
+        .find(PROGRESS_END)
+        .ok_or_else(|| format!("missing `{}` marker", PROGRESS_END))?
+        + start;
+
+    Ok(format!("{}\n{}{}", &readme[..start], table, &readme[end..]))
+}
+
+/// The parse error or the errors of the failed parts, each naming its part.
+pub fn errors(report: &DayReport) -> Vec<String> {
+    let parts = report.parts.iter().filter_map(|p| {
+        p.error
+            .as_ref()
+            .map(|error| format!("part {}: {}", p.part, error))
+    });
+
+    report.error.iter().cloned().chain(parts).collect()
+}
+
+fn status_name(status: Status) -> &'static str {
+    match status {
+        Status::Solved => "solved",
+        Status::Skipped => "skipped",
+        Status::Failed => "failed",
+    }
+}
+
+fn optional(seconds: Option<f64>) -> String {
+    seconds.map_or(String::new(), |s| s.to_string())
+}
+
+/// Quotes a CSV field when it contains a separator, quote or line break.
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn markdown_cell(value: &str) -> String {
+    value.replace('|', "\\|").replace('\n', "<br>")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solver::tests::Fixture;
+
+    fn supply_stacks() -> Fixture {
+        Fixture {
+            title: "Supply Stacks",
+            part2: |crates| match crates.len() {
+                1 => Err("no crates to move".to_string()),
+                _ => Ok("CMZ, \"top\"".into()),
+            },
+            ..Fixture::new(2022, 5)
+        }
+    }
+
+    fn report(input: &str) -> DayReport {
+        let puzzle: Arc<dyn Puzzle> = Arc::new(supply_stacks());
+        run_puzzle(&puzzle, input, &Part::ALL, None)
+    }
+
+    #[test]
+    fn reports() {
+        let day = report("1,2");
+
+        assert_eq!(Status::Solved, day.status);
+        assert_eq!(2, day.stars());
+        assert_eq!(Some(&Answer::Number(3)), day.parts[0].answer.as_ref());
+
+        let day = report("1,x");
+        assert_eq!(Status::Failed, day.status);
+        assert_eq!(0, day.stars());
+        assert_eq!(
+            Some("line 1, column 3: expected a number, found `x`"),
+            day.error.as_deref()
+        );
+
+        let day = report("1");
+        assert_eq!(Status::Failed, day.status);
+        assert_eq!(1, day.stars());
+        assert_eq!(None, day.error);
+        assert_eq!(Some("no crates to move"), day.parts[1].error.as_deref());
+        assert_eq!(vec!["part 2: no crates to move"], errors(&day));
+    }
+
+    #[test]
+    fn json() {
+        let json = serde_json::to_value(vec![report("1,2")]).unwrap();
+        let day = &json[0];
+
+        assert_eq!(2022, day["year"]);
+        assert_eq!("solved", day["status"]);
+        assert_eq!(1, day["parts"][0]["part"]);
+        assert_eq!(3, day["parts"][0]["answer"]);
+        assert_eq!("CMZ, \"top\"", day["parts"][1]["answer"]);
+        assert!(day.get("error").is_none());
+    }
+
+    #[test]
+    fn csv() {
+        let csv = to_csv(&[report("1,2")]);
+        let row = csv.lines().nth(1).unwrap();
+
+        assert!(row.starts_with("2022,5,Supply Stacks,solved,"));
+        assert!(row.contains(",\"CMZ, \"\"top\"\"\","));
+        assert!(row.ends_with(','));
+
+        let csv = to_csv(&[report("1")]);
+        assert!(csv.ends_with(",,,part 2: no crates to move\n"));
+    }
+
+    #[test]
+    fn markdown() {
+        let markdown = to_markdown(&[report("1,x")]);
+
+        assert_eq!(
+            "| 2022 | 5 | Supply Stacks |  |  |  | failed |",
+            markdown.lines().nth(2).unwrap()
+        );
+    }
+
+    #[test]
+    fn progress() {
+        let table = progress_table(&[report("1,2")]);
+        let readme = format!("# AoC\n{}\nold\n{}\nmore", PROGRESS_START, PROGRESS_END);
+
+        assert_eq!(
+            format!("# AoC\n{}\n{}{}\nmore", PROGRESS_START, table, PROGRESS_END),
+            update_progress(&readme, &table).unwrap()
+        );
+        assert!(table.ends_with("| 2022 | 5 | Supply Stacks | ★★ |\n"));
+        assert!(update_progress("# AoC", &table).is_err());
+    }
+}
@@ -0,0 +1,248 @@
+use std::any::Any;
+use std::cell::Cell;
+use std::fmt;
+use std::fs;
+use std::io::{self, Read};
+use std::panic::{self, AssertUnwindSafe};
+use std::path::Path;
+use std::sync::mpsc::{channel, RecvTimeoutError};
+use std::sync::{Arc, Once};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::parse::ParseError;
+use crate::solver::{Answer, Part, Puzzle};
+
+/// Result of running one part of a puzzle.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    Solved(Answer),
+    /// The part was still running when its time budget ran out.
+    Skipped(Duration),
+    /// The part returned an error or panicked.
+    Failed(String),
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            // start pictures such as CRT output on their own line
+            Outcome::Solved(Answer::Text(text)) if text.contains('\n') => write!(f, "\n{}", text),
+            Outcome::Solved(answer) => write!(f, "{}", answer),
+            Outcome::Skipped(limit) => write!(f, "skipped, exceeded {:?}", limit),
+            Outcome::Failed(message) => write!(f, "failed, {}", message),
+        }
+    }
+}
+
+/// Reads puzzle input text from a file, or from stdin when the path is `-`.
+pub fn read_input(path: &Path) -> io::Result<String> {
+    if path == Path::new("-") {
+        let mut input = String::new();
+        io::stdin().read_to_string(&mut input)?;
+        Ok(input)
+    } else {
+        fs::read_to_string(path)
+    }
+}
+
+/// Solves one part of a puzzle, giving up once `max_time` has passed.
+///
+/// Without a time limit the part runs on the current thread. With one, it runs on a worker
+/// thread that is abandoned if the limit is exceeded, so it keeps using CPU until the process
+/// exits. Either way a panicking part is reported as failed rather than ending the run, and
+/// without the usual panic message and backtrace on stderr.
+pub fn solve_part(
+    puzzle: &Arc<dyn Puzzle>,
+    input: &Arc<dyn Any + Send + Sync>,
+    part: Part,
+    max_time: Option<Duration>,
+) -> Outcome {
+    let max_time = match max_time {
+        Some(max_time) => max_time,
+        None => {
+            let solved = catch_quietly(|| puzzle.solve(input.as_ref(), part));
+            return outcome(solved.unwrap_or_else(|payload| Err(panic_message(payload))));
+        }
+    };
+
+    let (sender, receiver) = channel();
+    let worker = {
+        let puzzle = Arc::clone(puzzle);
+        let input = Arc::clone(input);
+        thread::spawn(move || {
+            let solved = catch_quietly(|| puzzle.solve(input.as_ref(), part));
+            let _ = sender.send(solved.unwrap_or_else(|payload| Err(panic_message(payload))));
+        })
+    };
+
+    match receiver.recv_timeout(max_time) {
+        Ok(solved) => outcome(solved),
+        Err(RecvTimeoutError::Timeout) => Outcome::Skipped(max_time),
+        Err(RecvTimeoutError::Disconnected) => match worker.join() {
+            Err(payload) => Outcome::Failed(panic_message(payload)),
+            Ok(()) => unreachable!("worker finished without sending an answer"),
+        },
+    }
+}
+
+fn outcome(solved: Result<Answer, String>) -> Outcome {
+    match solved {
+        Ok(answer) => Outcome::Solved(answer),
+        Err(message) => Outcome::Failed(message),
+    }
+}
+
+thread_local! {
+    /// Whether a panic on this thread will be caught and reported as a failed part.
+    static CATCHING: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Runs `f`, catching a panic without the panic hook printing it. Panics on other threads, or
+/// outside of `f`, still go to the hook that was set before.
+fn catch_quietly<T>(f: impl FnOnce() -> T) -> thread::Result<T> {
+    static QUIET_HOOK: Once = Once::new();
+
+    QUIET_HOOK.call_once(|| {
+        let previous = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if !CATCHING.with(Cell::get) {
+                previous(info);
+            }
+        }));
+    });
+
+    let catching = CATCHING.with(|catching| catching.replace(true));
+    let result = panic::catch_unwind(AssertUnwindSafe(f));
+    CATCHING.with(|c| c.set(catching));
+
+    result
+}
+
+/// The message a part panicked with, which is a string for `panic!` and failed `unwrap`s.
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    let message = match payload.downcast::<String>() {
+        Ok(message) => *message,
+        Err(payload) => match payload.downcast::<&str>() {
+            Ok(message) => message.to_string(),
+            Err(_) => "unknown error".to_string(),
+        },
+    };
+
+    format!("panicked: {}", message)
+}
+
+/// Parses the puzzle input and prints the solution for each requested part, followed by the
+/// wall time of parsing and of each solved part. Returns the outcome of each part.
+pub fn print_solution(
+    puzzle: &Arc<dyn Puzzle>,
+    input: &str,
+    parts: &[Part],
+    max_time: Option<Duration>,
+) -> Result<Vec<Outcome>, ParseError> {
+    println!("--- Day {}: {} ---", puzzle.day(), puzzle.title());
+
+    let start = Instant::now();
+    let input: Arc<dyn Any + Send + Sync> = Arc::from(puzzle.parse(input)?);
+    let mut timings = vec![format!("parse {:.2?}", start.elapsed())];
+    let mut outcomes = Vec::new();
+
+    for &part in parts {
+        let start = Instant::now();
+        let result = solve_part(puzzle, &input, part, max_time);
+        let elapsed = start.elapsed();
+
+        match part {
+            Part::One => println!("\t1st Star Solution = {}", result),
+            Part::Two => println!("\t2nd Star Solution = {}", result),
+        }
+
+        if let Outcome::Solved(_) = result {
+            timings.push(format!("part {} {:.2?}", part, elapsed));
+        }
+        outcomes.push(result);
+    }
+
+    println!("\tTimings: {}", timings.join(", "));
+
+    Ok(outcomes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solver::tests::Fixture;
+
+    /// A puzzle whose second part sleeps for as many seconds as its input.
+    fn slow() -> Fixture {
+        Fixture {
+            part1: |input| match input[0] {
+                0 => Err("nothing to wait for".to_string()),
+                seconds => Ok(Answer::from(seconds)),
+            },
+            part2: |input| {
+                thread::sleep(Duration::from_secs(input[0]));
+                Ok(Answer::from(10 / input[0]))
+            },
+            ..Fixture::new(2021, 1)
+        }
+    }
+
+    #[test]
+    fn time_budget() {
+        let puzzle: Arc<dyn Puzzle> = Arc::new(slow());
+        let input: Arc<dyn Any + Send + Sync> = Arc::from(puzzle.parse("2").unwrap());
+        let budget = Some(Duration::from_millis(50));
+
+        assert_eq!(
+            Outcome::Solved(Answer::from(2_u64)),
+            solve_part(&puzzle, &input, Part::One, budget)
+        );
+        assert_eq!(
+            Outcome::Skipped(Duration::from_millis(50)),
+            solve_part(&puzzle, &input, Part::Two, budget)
+        );
+        assert_eq!(
+            Outcome::Solved(Answer::from(2_u64)),
+            solve_part(&puzzle, &input, Part::One, None)
+        );
+    }
+
+    #[test]
+    fn failures() {
+        let puzzle: Arc<dyn Puzzle> = Arc::new(slow());
+        let input: Arc<dyn Any + Send + Sync> = Arc::from(puzzle.parse("0").unwrap());
+        let budget = Some(Duration::from_secs(10));
+
+        for max_time in [None, budget] {
+            assert_eq!(
+                Outcome::Failed("nothing to wait for".to_string()),
+                solve_part(&puzzle, &input, Part::One, max_time)
+            );
+            assert_eq!(
+                Outcome::Failed("panicked: attempt to divide by zero".to_string()),
+                solve_part(&puzzle, &input, Part::Two, max_time)
+            );
+        }
+
+        // panics after solving aren't caught, so are printed as usual
+        assert!(!CATCHING.with(Cell::get));
+    }
+
+    #[test]
+    fn outcome_display() {
+        assert_eq!("42", Outcome::Solved(Answer::from(42_u32)).to_string());
+        assert_eq!(
+            "\n#.\n.#",
+            Outcome::Solved(Answer::from("#.\n.#")).to_string()
+        );
+        assert_eq!(
+            "skipped, exceeded 1.5s",
+            Outcome::Skipped(Duration::from_millis(1500)).to_string()
+        );
+        assert_eq!(
+            "failed, unreadable letter",
+            Outcome::Failed("unreadable letter".to_string()).to_string()
+        );
+    }
+}
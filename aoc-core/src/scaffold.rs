@@ -0,0 +1,298 @@
+//! Generating the module for a new day from templates.
+//!
+//! A day lives in `aoc<year>/src/<module>/` with a `mod.rs` implementing [crate::Solver], a
+//! `solve.rs` holding `parse_input`, `solve_first_star` and `solve_second_star`, the `input.txt`
+//! and `test_input.txt` inputs, and an `answers.toml` with the expected answers of the example.
+//! The module is then declared and registered in the year crate's `lib.rs`.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::verify;
+
+const MOD_TEMPLATE: &str = include_str!("../templates/mod.rs.in");
+const SOLVE_TEMPLATE: &str = include_str!("../templates/solve.rs.in");
+const ANSWERS_TEMPLATE: &str = include_str!("../templates/answers.toml.in");
+
+/// A day to generate.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NewDay {
+    pub year: u32,
+    pub day: u32,
+    /// Module name, e.g. `sonar_sweep`.
+    pub module: String,
+    pub title: String,
+}
+
+impl NewDay {
+    /// Name of the solver type, the module name in upper camel case.
+    pub fn solver(&self) -> String {
+        self.module.split('_').map(capitalise).collect()
+    }
+
+    fn render(&self, template: &str) -> String {
+        template
+            .replace("{{module}}", &self.module)
+            .replace("{{solver}}", &self.solver())
+            .replace("{{day}}", &self.day.to_string())
+            .replace(
+                "{{title}}",
+                &self.title.replace('\\', "\\\\").replace('"', "\\\""),
+            )
+    }
+}
+
+/// Rust's strict and reserved keywords, which can't name a module.
+const KEYWORDS: &[&str] = &[
+    "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "crate",
+    "do", "dyn", "else", "enum", "extern", "false", "final", "fn", "for", "gen", "if", "impl",
+    "in", "let", "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref",
+    "return", "self", "static", "struct", "super", "trait", "true", "try", "type", "typeof",
+    "unsafe", "unsized", "use", "virtual", "where", "while", "yield",
+];
+
+/// Checks a module name is a lower case identifier such as `sonar_sweep`.
+pub fn parse_module(value: &str) -> Result<String, String> {
+    if KEYWORDS.contains(&value) {
+        return Err(format!(
+            "`{}` is a Rust keyword, so can't be a module name",
+            value
+        ));
+    }
+
+    let valid = value.starts_with(|c: char| c.is_ascii_lowercase())
+        && value
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_');
+
+    if valid {
+        Ok(value.to_string())
+    } else {
+        Err(format!(
+            "`{}` isn't a module name, use lower case letters, digits and underscores",
+            value
+        ))
+    }
+}
+
+/// Default title for a module, e.g. `Sonar Sweep` for `sonar_sweep`.
+pub fn title(module: &str) -> String {
+    module
+        .split('_')
+        .filter(|word| !word.is_empty())
+        .map(capitalise)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn capitalise(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_ascii_uppercase().to_string() + chars.as_str(),
+        None => String::new(),
+    }
+}
+
+/// Location of the crate holding a year's puzzles.
+pub fn year_dir(root: &Path, year: u32) -> PathBuf {
+    root.join(format!("aoc{}", year))
+}
+
+/// Creates the files of a new day under the workspace at `root` and registers it in the year
+/// crate's `lib.rs`. Returns the created files.
+pub fn new_day(root: &Path, day: &NewDay) -> Result<Vec<PathBuf>, String> {
+    let crate_dir = year_dir(root, day.year);
+    let lib_path = crate_dir.join("src").join("lib.rs");
+    let module_dir = crate_dir.join("src").join(&day.module);
+
+    let lib = fs::read_to_string(&lib_path).map_err(|err| {
+        format!(
+            "couldn't read {}, is there a crate for {}? {}",
+            lib_path.display(),
+            day.year,
+            err
+        )
+    })?;
+
+    if module_dir.exists() {
+        return Err(format!("{} already exists", module_dir.display()));
+    }
+    let lib =
+        register(&lib, day).map_err(|message| format!("{}: {}", lib_path.display(), message))?;
+
+    let files = [
+        ("mod.rs", day.render(MOD_TEMPLATE)),
+        ("solve.rs", day.render(SOLVE_TEMPLATE)),
+        ("input.txt", String::new()),
+        ("test_input.txt", String::new()),
+        (verify::ANSWERS_FILE, ANSWERS_TEMPLATE.to_string()),
+    ];
+
+    fs::create_dir_all(&module_dir)
+        .map_err(|err| format!("couldn't create {}: {}", module_dir.display(), err))?;
+
+    let mut created = Vec::new();
+    for (name, contents) in files {
+        let path = module_dir.join(name);
+        fs::write(&path, contents)
+            .map_err(|err| format!("couldn't write {}: {}", path.display(), err))?;
+        created.push(path);
+    }
+
+    fs::write(&lib_path, lib)
+        .map_err(|err| format!("couldn't write {}: {}", lib_path.display(), err))?;
+
+    Ok(created)
+}
+
+/// Adds the `use`, `mod` and `registry.register` lines for a day to a year crate's `lib.rs`,
+/// each after the last line of its kind.
+fn register(lib: &str, day: &NewDay) -> Result<String, String> {
+    let solver = day.solver();
+    let mut lines = lib.lines().map(str::to_string).collect::<Vec<_>>();
+
+    if lines
+        .iter()
+        .any(|line| line.trim() == format!("mod {};", day.module))
+    {
+        return Err(format!("module `{}` is already declared", day.module));
+    }
+
+    let additions = [
+        (
+            "use crate::",
+            format!("use crate::{}::{};", day.module, solver),
+        ),
+        ("mod ", format!("mod {};", day.module)),
+        (
+            "    registry.register(",
+            format!("    registry.register({});", solver),
+        ),
+    ];
+
+    for (prefix, line) in additions {
+        let last = lines
+            .iter()
+            .rposition(|existing| existing.starts_with(prefix) && existing.ends_with(';'))
+            .ok_or_else(|| format!("no `{}` line to add `{}` after", prefix.trim(), line.trim()))?;
+        lines.insert(last + 1, line);
+    }
+
+    Ok(lines.join("\n") + "\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LIB: &str = "use aoc_core::Registry;
+
+use crate::day1::Trebuchet;
+
+mod day1;
+
+pub const YEAR: u32 = 2023;
+
+/// Registers every solved 2023 puzzle with the registry.
+pub fn register(registry: &mut Registry) {
+    registry.register(Trebuchet);
+}
+";
+
+    fn new(module: &str) -> NewDay {
+        NewDay {
+            year: 2023,
+            day: 5,
+            module: module.to_string(),
+            title: title(module),
+        }
+    }
+
+    #[test]
+    fn names() {
+        let day = new("if_you_give_a_seed_a_fertilizer");
+
+        assert_eq!("IfYouGiveASeedAFertilizer", day.solver());
+        assert_eq!("If You Give A Seed A Fertilizer", day.title);
+        assert!(parse_module("day5").is_ok());
+        assert!(parse_module("Day5").is_err());
+        assert!(parse_module("5th").is_err());
+        assert!(parse_module("seed-fertilizer").is_err());
+        assert_eq!(
+            Err("`type` is a Rust keyword, so can't be a module name".to_string()),
+            parse_module("type")
+        );
+        assert!(parse_module("mod").is_err());
+        assert!(parse_module("types").is_ok());
+    }
+
+    #[test]
+    fn generates_day() {
+        let root = std::env::temp_dir().join(format!("aoc-scaffold-{}", std::process::id()));
+        let src = year_dir(&root, 2023).join("src");
+        fs::create_dir_all(&src).unwrap();
+        fs::write(src.join("lib.rs"), LIB).unwrap();
+
+        let created = new_day(&root, &new("seeds"));
+        let lib = fs::read_to_string(src.join("lib.rs")).unwrap();
+        let module = fs::read_to_string(src.join("seeds").join("mod.rs")).unwrap();
+        let again = new_day(&root, &new("seeds"));
+        fs::remove_dir_all(&root).unwrap();
+
+        assert_eq!(5, created.unwrap().len());
+        assert!(lib.contains("use crate::day1::Trebuchet;\nuse crate::seeds::Seeds;\n"));
+        assert!(lib.contains("mod day1;\nmod seeds;\n"));
+        assert!(lib.contains("    registry.register(Trebuchet);\n    registry.register(Seeds);\n}"));
+        assert!(module.contains("pub struct Seeds;"));
+        assert!(module.contains("        5\n"));
+        assert!(module.contains("\"/src/seeds/input.txt\""));
+        assert!(again.is_err());
+
+        assert!(register("mod day1;\n", &new("seeds")).is_err());
+    }
+
+    /// Builds the generated module with its tests in a crate of its own, against this aoc-core,
+    /// so that the templates keep up with the [crate::Solver] trait.
+    #[test]
+    fn generated_day_compiles() {
+        let manifest_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+        let dir = std::env::temp_dir().join(format!("aoc-scaffold-check-{}", std::process::id()));
+        let module_dir = dir.join("src").join("seeds");
+        fs::create_dir_all(&module_dir).unwrap();
+
+        let manifest = format!(
+            "[package]\nname = \"scaffold-check\"\nversion = \"0.1.0\"\nedition = \"2021\"\n\n\
+             [dependencies]\naoc-core = {{ path = {:?} }}\n\n[workspace]\n",
+            manifest_dir
+        );
+        let lib = "pub const YEAR: u32 = 2023;\n\nmod seeds;\n\n\
+                   pub fn register(registry: &mut aoc_core::Registry) {\n\
+                   \x20   registry.register(seeds::Seeds);\n}\n";
+        let day = new("seeds");
+
+        fs::write(dir.join("Cargo.toml"), manifest).unwrap();
+        // the workspace's lock file pins the same dependencies, so nothing is fetched
+        fs::copy(manifest_dir.join("../Cargo.lock"), dir.join("Cargo.lock")).unwrap();
+        fs::write(dir.join("src").join("lib.rs"), lib).unwrap();
+        fs::write(module_dir.join("mod.rs"), day.render(MOD_TEMPLATE)).unwrap();
+        fs::write(module_dir.join("solve.rs"), day.render(SOLVE_TEMPLATE)).unwrap();
+
+        let cargo = std::env::var_os("CARGO").unwrap_or_else(|| "cargo".into());
+        let output = std::process::Command::new(cargo)
+            .args(["check", "--tests", "--quiet", "--manifest-path"])
+            .arg(dir.join("Cargo.toml"))
+            .env(
+                "CARGO_TARGET_DIR",
+                manifest_dir.join("../target/scaffold-check"),
+            )
+            .output()
+            .unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert!(
+            output.status.success(),
+            "generated module doesn't compile:\n{}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+}
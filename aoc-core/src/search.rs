@@ -0,0 +1,203 @@
+//! Shortest path searches over implicit graphs.
+//!
+//! Nodes are never stored up front: each search is given the start node and a function listing
+//! the successors of a node, so a grid position and its [crate::Grid::neighbours4] are enough to
+//! search a grid. The searches return the cost of the path found along with its nodes.
+
+use std::cmp::Reverse;
+use std::collections::hash_map::Entry;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::hash::Hash;
+use std::ops::Add;
+
+/// A path found by a search.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Path<N, C> {
+    /// Total cost of the path's steps.
+    pub cost: C,
+    /// Nodes from the start to the goal, both included.
+    pub nodes: Vec<N>,
+}
+
+/// Finds a cheapest path from `start` to a node satisfying `goal`. `successors` lists the nodes
+/// reachable from a node with the cost of each step, which must not be negative.
+pub fn dijkstra<N, C, FN, IN, FG>(start: N, successors: FN, goal: FG) -> Option<Path<N, C>>
+where
+    N: Eq + Hash + Clone,
+    C: Ord + Copy + Default + Add<Output = C>,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = (N, C)>,
+    FG: FnMut(&N) -> bool,
+{
+    astar(start, successors, |_| C::default(), goal)
+}
+
+/// Finds a cheapest path like [dijkstra], exploring the nodes with the lowest cost so far plus
+/// `heuristic` first. The heuristic estimates the remaining cost to the goal and must never
+/// overestimate it, or the path found may not be the cheapest.
+pub fn astar<N, C, FN, IN, FH, FG>(
+    start: N,
+    mut successors: FN,
+    mut heuristic: FH,
+    mut goal: FG,
+) -> Option<Path<N, C>>
+where
+    N: Eq + Hash + Clone,
+    C: Ord + Copy + Default + Add<Output = C>,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = (N, C)>,
+    FH: FnMut(&N) -> C,
+    FG: FnMut(&N) -> bool,
+{
+    // every node reached so far, with the index of its parent and its cheapest cost so far
+    let mut nodes = vec![(start.clone(), usize::MAX, C::default())];
+    let mut indices = HashMap::from([(start.clone(), 0)]);
+
+    let mut queue = BinaryHeap::new();
+    queue.push(Reverse((heuristic(&start), C::default(), 0)));
+
+    while let Some(Reverse((_, cost, index))) = queue.pop() {
+        if cost > nodes[index].2 {
+            // a cheaper path to the node was queued after this one
+            continue;
+        }
+
+        let node = nodes[index].0.clone();
+        if goal(&node) {
+            return Some(Path {
+                cost,
+                nodes: unwind(&nodes, index, |(node, parent, _)| (node, *parent)),
+            });
+        }
+
+        for (next, step) in successors(&node) {
+            let next_cost = cost + step;
+
+            let next_index = match indices.entry(next) {
+                Entry::Occupied(entry) if nodes[*entry.get()].2 <= next_cost => continue,
+                Entry::Occupied(entry) => *entry.get(),
+                Entry::Vacant(entry) => {
+                    nodes.push((entry.key().clone(), index, next_cost));
+                    *entry.insert(nodes.len() - 1)
+                }
+            };
+            nodes[next_index].1 = index;
+            nodes[next_index].2 = next_cost;
+
+            let estimate = next_cost + heuristic(&nodes[next_index].0);
+            queue.push(Reverse((estimate, next_cost, next_index)));
+        }
+    }
+
+    None
+}
+
+/// Finds a path from `start` to a node satisfying `goal` with the fewest steps. The cost of the
+/// path is its number of steps.
+pub fn bfs<N, FN, IN, FG>(start: N, mut successors: FN, mut goal: FG) -> Option<Path<N, usize>>
+where
+    N: Eq + Hash + Clone,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = N>,
+    FG: FnMut(&N) -> bool,
+{
+    // every node reached so far, with the index of its parent and its number of steps
+    let mut nodes = vec![(start.clone(), usize::MAX, 0)];
+    let mut indices = HashMap::from([(start, 0)]);
+
+    let mut queue = VecDeque::from([0]);
+
+    while let Some(index) = queue.pop_front() {
+        let (node, _, steps) = nodes[index].clone();
+        if goal(&node) {
+            return Some(Path {
+                cost: steps,
+                nodes: unwind(&nodes, index, |(node, parent, _)| (node, *parent)),
+            });
+        }
+
+        for next in successors(&node) {
+            if let Entry::Vacant(entry) = indices.entry(next) {
+                nodes.push((entry.key().clone(), index, steps + 1));
+                queue.push_back(*entry.insert(nodes.len() - 1));
+            }
+        }
+    }
+
+    None
+}
+
+/// Follows the parents from `index` back to the start, returning the nodes from the start.
+fn unwind<N, T, F>(nodes: &[T], mut index: usize, link: F) -> Vec<N>
+where
+    N: Clone,
+    F: Fn(&T) -> (&N, usize),
+{
+    let mut path = Vec::new();
+
+    while index != usize::MAX {
+        let (node, parent) = link(&nodes[index]);
+        path.push(node.clone());
+        index = parent;
+    }
+
+    path.reverse();
+    path
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Grid;
+
+    #[test]
+    fn cheapest_paths() {
+        let grid = Grid::digits("1163\n1381\n2136").unwrap();
+        let end = (2, 3);
+        let successors = |&p: &(usize, usize)| {
+            grid.neighbours4(p)
+                .map(|n| (n, grid[n]))
+                .collect::<Vec<_>>()
+        };
+
+        let path = dijkstra((0, 0), successors, |&p| p == end).unwrap();
+        assert_eq!(13, path.cost);
+        assert_eq!(
+            vec![(0, 0), (1, 0), (2, 0), (2, 1), (2, 2), (2, 3)],
+            path.nodes
+        );
+
+        let distance = |&(row, col): &(usize, usize)| (end.0 - row + end.1 - col) as u32;
+        let path = astar((0, 0), successors, distance, |&p| p == end).unwrap();
+        assert_eq!(13, path.cost);
+        assert_eq!(
+            path.cost,
+            path.nodes[1..].iter().map(|&p| grid[p]).sum::<u32>()
+        );
+
+        assert_eq!(
+            None,
+            dijkstra((0, 0), successors, |_: &(usize, usize)| false)
+        );
+    }
+
+    #[test]
+    fn fewest_steps() {
+        let walls = Grid::parse("..#\n#..\n...", "cell", |c| Some(c == '#')).unwrap();
+        let open = |&p: &(usize, usize)| {
+            walls
+                .neighbours4(p)
+                .filter(|&n| !walls[n])
+                .collect::<Vec<_>>()
+        };
+
+        let path = bfs((0, 0), open, |&p| p == (0, 0)).unwrap();
+        assert_eq!((0, vec![(0, 0)]), (path.cost, path.nodes));
+
+        let path = bfs((0, 0), open, |&p| p == (2, 0)).unwrap();
+        assert_eq!(4, path.cost);
+        assert_eq!(vec![(0, 0), (0, 1), (1, 1), (2, 1), (2, 0)], path.nodes);
+
+        assert_eq!(None, bfs((0, 0), open, |&p| p == (0, 2)));
+    }
+}
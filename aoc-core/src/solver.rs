@@ -0,0 +1,402 @@
+use std::any::Any;
+use std::fmt;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::parse::ParseError;
+use crate::picture::Picture;
+
+/// A single Advent of Code puzzle.
+///
+/// The puzzle input text is loaded by the runner and parsed once by [Solver::parse], then shared
+/// by both parts so expensive parsing isn't repeated for the second star.
+pub trait Solver {
+    /// Parsed representation of the puzzle input.
+    type Input: Send + Sync + 'static;
+
+    fn year(&self) -> u32;
+
+    fn day(&self) -> u32;
+
+    fn title(&self) -> &'static str;
+
+    /// Location of the default puzzle input, used when no other input is supplied.
+    fn input_path(&self) -> &'static str;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError>;
+
+    /// Solves the first part, or fails with a message when the input has no answer, such as a
+    /// picture of letters that can't be read.
+    fn part1(&self, input: &Self::Input) -> Result<Answer, String>;
+
+    fn part2(&self, input: &Self::Input) -> Result<Answer, String>;
+
+    /// Draws what's behind a part's answer, such as the route taken, for the `draw` command.
+    /// Most puzzles have nothing to draw.
+    fn draw(&self, _input: &Self::Input, _part: Part) -> Option<Picture> {
+        None
+    }
+
+    /// Returns a copy of the solver with the settings given by `options`, such as the size of
+    /// the problem, which is only called with at least one option. Most puzzles have no settings
+    /// and reject every option.
+    fn configure(&self, options: &Options) -> Result<Self, String>
+    where
+        Self: Sized,
+    {
+        options.expect(&[])?;
+        Err("no options given".to_string())
+    }
+}
+
+/// Object safe view of a [Solver], allowing puzzles with different input types to be stored
+/// together in a [crate::Registry].
+pub trait Puzzle: Send + Sync {
+    fn year(&self) -> u32;
+
+    fn day(&self) -> u32;
+
+    fn title(&self) -> &'static str;
+
+    fn input_path(&self) -> &'static str;
+
+    fn parse(&self, input: &str) -> Result<Box<dyn Any + Send + Sync>, ParseError>;
+
+    fn solve(&self, input: &dyn Any, part: Part) -> Result<Answer, String>;
+
+    fn draw(&self, input: &dyn Any, part: Part) -> Option<Picture>;
+
+    fn configure(&self, options: &Options) -> Result<Arc<dyn Puzzle>, String>;
+}
+
+impl<S> Puzzle for S
+where
+    S: Solver + Send + Sync + 'static,
+{
+    fn year(&self) -> u32 {
+        Solver::year(self)
+    }
+
+    fn day(&self) -> u32 {
+        Solver::day(self)
+    }
+
+    fn title(&self) -> &'static str {
+        Solver::title(self)
+    }
+
+    fn input_path(&self) -> &'static str {
+        Solver::input_path(self)
+    }
+
+    fn parse(&self, input: &str) -> Result<Box<dyn Any + Send + Sync>, ParseError> {
+        let input = Solver::parse(self, input)?;
+        Ok(Box::new(input))
+    }
+
+    fn solve(&self, input: &dyn Any, part: Part) -> Result<Answer, String> {
+        let input = input
+            .downcast_ref::<S::Input>()
+            .expect("input should be parsed by the same puzzle");
+
+        match part {
+            Part::One => self.part1(input),
+            Part::Two => self.part2(input),
+        }
+    }
+
+    fn draw(&self, input: &dyn Any, part: Part) -> Option<Picture> {
+        let input = input
+            .downcast_ref::<S::Input>()
+            .expect("input should be parsed by the same puzzle");
+
+        Solver::draw(self, input, part)
+    }
+
+    fn configure(&self, options: &Options) -> Result<Arc<dyn Puzzle>, String> {
+        let solver = Solver::configure(self, options)?;
+        Ok(Arc::new(solver))
+    }
+}
+
+/// Puzzle settings given on the command line as `KEY=VALUE` pairs.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Options {
+    values: Vec<(String, String)>,
+}
+
+impl Options {
+    pub fn new() -> Self {
+        Options::default()
+    }
+
+    /// Sets `key`, replacing any earlier value.
+    pub fn set(&mut self, key: &str, value: &str) {
+        self.values.retain(|(existing, _)| existing != key);
+        self.values.push((key.to_string(), value.to_string()));
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// The value of `key` if it was given, failing when it doesn't parse.
+    pub fn get<T: FromStr>(&self, key: &str) -> Result<Option<T>, String> {
+        match self.values.iter().find(|(existing, _)| existing == key) {
+            Some((_, value)) => value
+                .parse()
+                .map(Some)
+                .map_err(|_| format!("invalid value `{}` for option `{}`", value, key)),
+            None => Ok(None),
+        }
+    }
+
+    /// Fails on the first option that isn't one of the `known` keys.
+    pub fn expect(&self, known: &[&str]) -> Result<(), String> {
+        match self
+            .values
+            .iter()
+            .find(|(key, _)| !known.contains(&key.as_str()))
+        {
+            Some((key, _)) if known.is_empty() => {
+                Err(format!("unknown option `{}`, there are no options", key))
+            }
+            Some((key, _)) => Err(format!(
+                "unknown option `{}`, expected one of {}",
+                key,
+                known.join(", ")
+            )),
+            None => Ok(()),
+        }
+    }
+}
+
+impl FromStr for Options {
+    type Err = String;
+
+    /// Parses comma separated settings such as `tiles=10,wrap=9`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut options = Options::new();
+
+        for setting in s.split(',').filter(|setting| !setting.trim().is_empty()) {
+            let (key, value) = setting
+                .split_once('=')
+                .ok_or_else(|| format!("expected KEY=VALUE, found `{}`", setting))?;
+            options.set(key.trim(), value.trim());
+        }
+
+        Ok(options)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const ALL: [Part; 2] = [Part::One, Part::Two];
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Part::One => write!(f, "1"),
+            Part::Two => write!(f, "2"),
+        }
+    }
+}
+
+/// Parts are stored as their number, in reports and submission histories.
+impl Serialize for Part {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Part::One => serializer.serialize_u8(1),
+            Part::Two => serializer.serialize_u8(2),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Part {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        match u8::deserialize(deserializer)? {
+            1 => Ok(Part::One),
+            2 => Ok(Part::Two),
+            part => Err(serde::de::Error::custom(format!(
+                "part must be 1 or 2, found {}",
+                part
+            ))),
+        }
+    }
+}
+
+/// The answer to one part of a puzzle, either numeric or textual (e.g. a crate ordering).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Answer {
+    Number(i128),
+    Text(String),
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Number(value) => write!(f, "{}", value),
+            Answer::Text(value) => write!(f, "{}", value),
+        }
+    }
+}
+
+/// Answers are stored as a plain number or string, in reports and recorded answers.
+impl Serialize for Answer {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Answer::Number(value) => serializer.serialize_i128(*value),
+            Answer::Text(value) => serializer.serialize_str(value),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Answer {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Raw {
+            Number(i64),
+            Text(String),
+        }
+
+        Ok(match Raw::deserialize(deserializer)? {
+            Raw::Number(value) => Answer::from(value),
+            Raw::Text(value) => Answer::Text(value),
+        })
+    }
+}
+
+macro_rules! answer_from_number {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(value: $t) -> Self {
+                    Answer::Number(value as i128)
+                }
+            }
+        )*
+    };
+}
+
+answer_from_number!(u32, u64, usize, i32, i64);
+
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        Answer::Text(value)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(value: &str) -> Self {
+        Answer::Text(value.to_string())
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::parse::{self, ParseError};
+
+    /// A puzzle over comma separated numbers, for testing the code that runs puzzles.
+    pub(crate) struct Fixture {
+        pub year: u32,
+        pub day: u32,
+        pub title: &'static str,
+        pub part1: fn(&[u64]) -> Result<Answer, String>,
+        pub part2: fn(&[u64]) -> Result<Answer, String>,
+    }
+
+    impl Fixture {
+        /// A puzzle whose first part sums the numbers and whose second part multiplies them.
+        pub fn new(year: u32, day: u32) -> Self {
+            Fixture {
+                year,
+                day,
+                title: "Fixture",
+                part1: |numbers| Ok(numbers.iter().sum::<u64>().into()),
+                part2: |numbers| {
+                    numbers
+                        .iter()
+                        .try_fold(1_u64, |product, &n| product.checked_mul(n))
+                        .map(Answer::from)
+                        .ok_or_else(|| "product overflows".to_string())
+                },
+            }
+        }
+    }
+
+    impl Solver for Fixture {
+        type Input = Vec<u64>;
+
+        fn year(&self) -> u32 {
+            self.year
+        }
+
+        fn day(&self) -> u32 {
+            self.day
+        }
+
+        fn title(&self) -> &'static str {
+            self.title
+        }
+
+        fn input_path(&self) -> &'static str {
+            "input.txt"
+        }
+
+        fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+            input.split(',').map(|n| parse::number(input, n)).collect()
+        }
+
+        fn part1(&self, input: &Self::Input) -> Result<Answer, String> {
+            (self.part1)(input)
+        }
+
+        fn part2(&self, input: &Self::Input) -> Result<Answer, String> {
+            (self.part2)(input)
+        }
+    }
+
+    #[test]
+    fn options() {
+        let options = "tiles=10, wrap = 9,tiles=20".parse::<Options>().unwrap();
+
+        assert_eq!(Ok(Some(20)), options.get::<u32>("tiles"));
+        assert_eq!(Ok(None), options.get::<u32>("search"));
+        assert!(options.get::<bool>("wrap").is_err());
+        assert!(options.expect(&["tiles", "wrap"]).is_ok());
+        assert_eq!(
+            Err("unknown option `wrap`, expected one of tiles".to_string()),
+            options.expect(&["tiles"])
+        );
+        assert!("tiles".parse::<Options>().is_err());
+    }
+
+    #[test]
+    fn answer_display() {
+        assert_eq!("1574445493136", Answer::from(1574445493136_u64).to_string());
+        assert_eq!("-12", Answer::from(-12_i32).to_string());
+        assert_eq!("FPEKBEJL", Answer::from("FPEKBEJL").to_string());
+    }
+
+    #[test]
+    fn serde() {
+        assert_eq!("[1,2]", serde_json::to_string(&Part::ALL).unwrap());
+        assert_eq!(Part::Two, serde_json::from_str::<Part>("2").unwrap());
+        assert!(serde_json::from_str::<Part>("3").is_err());
+
+        let answers = vec![Answer::from(895), Answer::from("FPEKBEJL")];
+        let json = serde_json::to_string(&answers).unwrap();
+        assert_eq!(r#"[895,"FPEKBEJL"]"#, json);
+        assert_eq!(answers, serde_json::from_str::<Vec<Answer>>(&json).unwrap());
+    }
+}
@@ -0,0 +1,493 @@
+//! Submitting answers, keeping a local history of every attempt.
+//!
+//! The history is stored as `submissions.toml` next to the cached input of a day. Before an
+//! answer is posted it's checked against the history, so an answer the server already rejected
+//! is never sent again, numeric answers outside the bounds learned from earlier "too high" and
+//! "too low" responses are refused, and waits imposed by the server are respected.
+
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::client::{Client, ClientError};
+use crate::fetch;
+use crate::solver::{Answer, Part};
+
+pub const HISTORY_FILE: &str = "submissions.toml";
+
+/// How the server judged a submitted answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Response {
+    Correct,
+    /// Wrong, without a hint.
+    Incorrect,
+    TooHigh,
+    TooLow,
+    /// An answer was submitted too recently, this one wasn't checked.
+    Wait,
+    /// The part is already solved or not unlocked yet, the answer wasn't checked.
+    WrongLevel,
+}
+
+/// A parsed response page.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Reply {
+    pub response: Response,
+    /// How long to wait before submitting another answer.
+    pub wait: Option<Duration>,
+}
+
+impl fmt::Display for Reply {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.response {
+            Response::Correct => write!(f, "that's the right answer")?,
+            Response::Incorrect => write!(f, "that's not the right answer")?,
+            Response::TooHigh => write!(f, "that's not the right answer, it's too high")?,
+            Response::TooLow => write!(f, "that's not the right answer, it's too low")?,
+            Response::Wait => write!(f, "an answer was submitted too recently")?,
+            Response::WrongLevel => write!(f, "the part is already solved or still locked")?,
+        }
+
+        match self.wait {
+            Some(wait) => write!(f, ", wait {:?} before trying again", wait),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Parses the page returned for a submission, or `None` if it isn't recognised.
+pub fn parse_response(html: &str) -> Option<Reply> {
+    let text = page_text(html);
+
+    let response = if text.contains("That's the right answer") {
+        Response::Correct
+    } else if text.contains("not the right answer") {
+        if text.contains("your answer is too high") {
+            Response::TooHigh
+        } else if text.contains("your answer is too low") {
+            Response::TooLow
+        } else {
+            Response::Incorrect
+        }
+    } else if text.contains("You gave an answer too recently") {
+        Response::Wait
+    } else if text.contains("You don't seem to be solving the right level") {
+        Response::WrongLevel
+    } else {
+        return None;
+    };
+
+    Some(Reply {
+        response,
+        wait: parse_wait(&text),
+    })
+}
+
+/// Visible text of an HTML page, with whitespace collapsed.
+fn page_text(html: &str) -> String {
+    let mut text = String::new();
+    let mut in_tag = false;
+
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => {
+                in_tag = false;
+                text.push(' ');
+            }
+            _ if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Finds the wait in either "You have 1m 30s left to wait" or "please wait 5 minutes before
+/// trying again".
+fn parse_wait(text: &str) -> Option<Duration> {
+    if let Some(end) = text.find(" left to wait") {
+        let start = text[..end].rfind("You have ")? + "You have ".len();
+        return text[start..end]
+            .split_whitespace()
+            .map(|amount| {
+                let (number, unit) = amount.split_at(amount.find(|c: char| !c.is_ascii_digit())?);
+                let number = number.parse::<u64>().ok()?;
+                match unit {
+                    "h" => Some(number * 3600),
+                    "m" => Some(number * 60),
+                    "s" => Some(number),
+                    _ => None,
+                }
+            })
+            .sum::<Option<u64>>()
+            .map(Duration::from_secs);
+    }
+
+    text.match_indices("wait ").find_map(|(start, _)| {
+        let mut words = text[start..].split_whitespace().skip(1);
+        let number = match words.next()? {
+            "one" | "a" => 1,
+            number => number.parse::<u64>().ok()?,
+        };
+        match words.next()? {
+            "second" | "seconds" => Some(Duration::from_secs(number)),
+            "minute" | "minutes" => Some(Duration::from_secs(number * 60)),
+            _ => None,
+        }
+    })
+}
+
+/// One answer judged by the server.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Attempt {
+    pub part: Part,
+    pub answer: String,
+    pub response: Response,
+    /// Unix time of the submission.
+    pub at: u64,
+}
+
+/// Earlier submissions for one day.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct History {
+    /// Unix time until which the server won't accept answers.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub wait_until: Option<u64>,
+    #[serde(default, rename = "attempt")]
+    pub attempts: Vec<Attempt>,
+}
+
+/// Reason an answer isn't submitted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Rejection {
+    /// The part was already solved, with this answer.
+    Solved(String),
+    /// The same answer was already judged wrong.
+    Repeated(Response),
+    /// An answer no larger than this was already too high.
+    TooHigh(i128),
+    /// An answer no smaller than this was already too low.
+    TooLow(i128),
+    /// The server asked to wait this much longer.
+    Wait(Duration),
+}
+
+impl fmt::Display for Rejection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Rejection::Solved(answer) => write!(f, "already solved, the answer was {}", answer),
+            Rejection::Repeated(response) => write!(
+                f,
+                "already submitted, {}",
+                Reply {
+                    response: *response,
+                    wait: None
+                }
+            ),
+            Rejection::TooHigh(bound) => write!(f, "too high, {} already was", bound),
+            Rejection::TooLow(bound) => write!(f, "too low, {} already was", bound),
+            Rejection::Wait(wait) => write!(f, "wait {:?} before trying again", wait),
+        }
+    }
+}
+
+impl History {
+    /// Loads the history at `path`, which is empty when nothing was submitted yet.
+    pub fn load(path: &Path) -> Result<History, String> {
+        match fs::read_to_string(path) {
+            Ok(text) => {
+                toml::from_str(&text).map_err(|err| format!("invalid {}: {}", path.display(), err))
+            }
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(History::default()),
+            Err(err) => Err(format!("couldn't read {}: {}", path.display(), err)),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        let text = toml::to_string(self).expect("history serialises to TOML");
+        let write = || {
+            fs::create_dir_all(path.parent().expect("history path has a parent"))?;
+            fs::write(path, text)
+        };
+        write().map_err(|err| format!("couldn't write {}: {}", path.display(), err))
+    }
+
+    fn attempts(&self, part: Part) -> impl Iterator<Item = &Attempt> {
+        self.attempts.iter().filter(move |a| a.part == part)
+    }
+
+    /// Checks whether `answer` is worth submitting at unix time `now`.
+    pub fn check(&self, part: Part, answer: &Answer, now: u64) -> Result<(), Rejection> {
+        let answer = answer.to_string();
+
+        if let Some(correct) = self
+            .attempts(part)
+            .find(|a| a.response == Response::Correct)
+        {
+            return Err(Rejection::Solved(correct.answer.clone()));
+        }
+
+        if let Some(repeated) = self.attempts(part).find(|a| a.answer == answer) {
+            return Err(Rejection::Repeated(repeated.response));
+        }
+
+        if let Ok(value) = answer.parse::<i128>() {
+            let bound = |response| {
+                self.attempts(part)
+                    .filter(move |a| a.response == response)
+                    .filter_map(|a| a.answer.parse::<i128>().ok())
+            };
+
+            if let Some(high) = bound(Response::TooHigh).filter(|&high| value >= high).min() {
+                return Err(Rejection::TooHigh(high));
+            }
+            if let Some(low) = bound(Response::TooLow).filter(|&low| value <= low).max() {
+                return Err(Rejection::TooLow(low));
+            }
+        }
+
+        match self.wait_until {
+            Some(until) if until > now => Err(Rejection::Wait(Duration::from_secs(until - now))),
+            _ => Ok(()),
+        }
+    }
+
+    /// Records the server's reply to `answer`, submitted at unix time `now`.
+    pub fn record(&mut self, part: Part, answer: &Answer, reply: &Reply, now: u64) {
+        if let Some(wait) = reply.wait {
+            self.wait_until = Some(now + wait.as_secs());
+        }
+
+        // answers the server didn't check tell us nothing about the next one
+        if matches!(reply.response, Response::Wait | Response::WrongLevel) {
+            return;
+        }
+
+        self.attempts.push(Attempt {
+            part,
+            answer: answer.to_string(),
+            response: reply.response,
+            at: now,
+        });
+    }
+}
+
+/// Location of the submission history of a day, next to its cached input.
+pub fn history_path(cache_dir: &Path, year: u32, day: u32) -> PathBuf {
+    fetch::cache_path(cache_dir, year, day).with_file_name(HISTORY_FILE)
+}
+
+#[derive(Debug)]
+pub enum SubmitError {
+    /// The history shows the answer shouldn't be submitted.
+    Rejected(Rejection),
+    Client(ClientError),
+    History(String),
+    /// The server's response page wasn't understood.
+    Unrecognised(String),
+}
+
+impl fmt::Display for SubmitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SubmitError::Rejected(rejection) => write!(f, "not submitted, {}", rejection),
+            SubmitError::Client(err) => write!(f, "{}", err),
+            SubmitError::History(message) => write!(f, "{}", message),
+            SubmitError::Unrecognised(text) => write!(f, "unrecognised response: {}", text),
+        }
+    }
+}
+
+impl std::error::Error for SubmitError {}
+
+/// Submits an answer at unix time `now`, unless the history of the day shows it can't be right
+/// or the server isn't accepting answers yet. The reply is added to the history.
+pub fn submit_answer(
+    client: &Client,
+    cache_dir: &Path,
+    year: u32,
+    day: u32,
+    part: Part,
+    answer: &Answer,
+    now: u64,
+) -> Result<Reply, SubmitError> {
+    let path = history_path(cache_dir, year, day);
+    let mut history = History::load(&path).map_err(SubmitError::History)?;
+
+    history
+        .check(part, answer, now)
+        .map_err(SubmitError::Rejected)?;
+
+    let page = client
+        .submit(year, day, part, &answer.to_string())
+        .map_err(SubmitError::Client)?;
+    let reply = parse_response(&page).ok_or_else(|| {
+        let text = page_text(&page);
+        SubmitError::Unrecognised(text.chars().take(200).collect())
+    })?;
+
+    history.record(part, answer, &reply, now);
+    history.save(&path).map_err(SubmitError::History)?;
+
+    Ok(reply)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::stub::StubServer;
+
+    const TOO_HIGH: &str = "<main>\n<article><p>That's not the right answer; your answer is \
+        too high.  If you're stuck, make sure you're using the full input data.  Please wait \
+        one minute before trying again. <a href=\"/2021/day/16\">[Return to Day 16]</a></p>\
+        </article>\n</main>";
+
+    const CORRECT: &str = "<main>\n<article><p>That's the right answer!  You are <span \
+        class=\"day-success\">one gold star</span> closer to saving Christmas.</p></article>\n\
+        </main>";
+
+    fn reply(response: Response, wait: Option<u64>) -> Option<Reply> {
+        Some(Reply {
+            response,
+            wait: wait.map(Duration::from_secs),
+        })
+    }
+
+    #[test]
+    fn responses() {
+        assert_eq!(reply(Response::TooHigh, Some(60)), parse_response(TOO_HIGH));
+        assert_eq!(reply(Response::Correct, None), parse_response(CORRECT));
+        assert_eq!(
+            reply(Response::TooLow, Some(300)),
+            parse_response(
+                "<p>That's not the right answer; your answer is too low.  Please wait 5 \
+                 minutes before trying again.</p>"
+            )
+        );
+        assert_eq!(
+            reply(Response::Incorrect, Some(60)),
+            parse_response(
+                "<p>That's not the right answer.  Please wait one minute before trying again.</p>"
+            )
+        );
+        assert_eq!(
+            reply(Response::Wait, Some(94)),
+            parse_response(
+                "<p>You gave an answer too recently; you have to wait after submitting an \
+                 answer before trying again.  You have 1m 34s left to wait.</p>"
+            )
+        );
+        assert_eq!(
+            reply(Response::WrongLevel, None),
+            parse_response(
+                "<p>You don't seem to be solving the right level.  Did you already complete \
+                 it?</p>"
+            )
+        );
+        assert_eq!(None, parse_response("<p>Please log in.</p>"));
+    }
+
+    #[test]
+    fn history() {
+        let mut history = History::default();
+        let wrong = |response| Reply {
+            response,
+            wait: None,
+        };
+
+        history.record(Part::One, &Answer::from(100), &wrong(Response::TooHigh), 0);
+        history.record(Part::One, &Answer::from(10), &wrong(Response::TooLow), 0);
+        history.record(Part::One, &Answer::from(50), &wrong(Response::Incorrect), 0);
+        history.record(
+            Part::One,
+            &Answer::from(40),
+            &wrong(Response::WrongLevel),
+            0,
+        );
+
+        let check = |answer: i64| history.check(Part::One, &Answer::from(answer), 0);
+        assert_eq!(Err(Rejection::Repeated(Response::TooHigh)), check(100));
+        assert_eq!(Err(Rejection::TooHigh(100)), check(150));
+        assert_eq!(Err(Rejection::TooLow(10)), check(3));
+        assert_eq!(Err(Rejection::Repeated(Response::Incorrect)), check(50));
+        assert_eq!(Ok(()), check(40));
+        assert_eq!(Ok(()), history.check(Part::Two, &Answer::from(150), 0));
+
+        history.record(Part::One, &Answer::from(42), &wrong(Response::Correct), 0);
+        assert_eq!(
+            Err(Rejection::Solved("42".to_string())),
+            history.check(Part::One, &Answer::from(43), 0)
+        );
+
+        history.record(
+            Part::Two,
+            &Answer::from("ABC"),
+            &reply(Response::Incorrect, Some(60)).unwrap(),
+            1000,
+        );
+        let check = |now| history.check(Part::Two, &Answer::from("ABD"), now);
+        assert_eq!(Err(Rejection::Wait(Duration::from_secs(15))), check(1045));
+        assert_eq!(Ok(()), check(1060));
+
+        let text = toml::to_string(&history).unwrap();
+        assert_eq!(history, toml::from_str(&text).unwrap());
+    }
+
+    #[test]
+    fn submits_once() {
+        let dir = std::env::temp_dir().join(format!("aoc-submit-{}", std::process::id()));
+        let server = StubServer::start(vec![(200, TOO_HIGH), (200, CORRECT)]);
+        let client = Client::new(&server.url, Some("token".to_string()));
+        let submit = |answer: i64, now| {
+            submit_answer(
+                &client,
+                &dir,
+                2021,
+                16,
+                Part::Two,
+                &Answer::from(answer),
+                now,
+            )
+        };
+
+        let too_high = submit(5000, 1000);
+        let repeated = submit(5000, 2000);
+        let too_soon = submit(4000, 1030);
+        let correct = submit(4000, 2000);
+        let history = History::load(&history_path(&dir, 2021, 16));
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(reply(Response::TooHigh, Some(60)), too_high.ok());
+        assert!(matches!(
+            repeated,
+            Err(SubmitError::Rejected(Rejection::Repeated(
+                Response::TooHigh
+            )))
+        ));
+        assert!(matches!(
+            too_soon,
+            Err(SubmitError::Rejected(Rejection::Wait(_)))
+        ));
+        assert_eq!(reply(Response::Correct, None), correct.ok());
+        assert_eq!(2, history.unwrap().attempts.len());
+
+        let requests = server.requests();
+        assert_eq!(2, requests.len());
+        assert_eq!(
+            ("POST", "/2021/day/16/answer"),
+            (requests[0].method.as_str(), requests[0].path.as_str())
+        );
+        assert_eq!(
+            Some("application/x-www-form-urlencoded"),
+            requests[0].header("content-type")
+        );
+        assert_eq!("level=2&answer=5000", requests[0].body);
+        assert_eq!("level=2&answer=4000", requests[1].body);
+    }
+}
@@ -0,0 +1,376 @@
+//! Checking answers against the expected answers recorded next to each input.
+//!
+//! Each puzzle directory may hold an `answers.toml` with a table per input file:
+//!
+//! ```toml
+//! ["input.txt"]
+//! part1 = 895
+//! part2 = "FPEKBEJL"
+//! ```
+//!
+//! Swapping in a different input only needs a matching entry, no source changes.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
+use serde::Deserialize;
+
+use crate::report;
+use crate::runner::read_input;
+use crate::solver::{Answer, Part, Puzzle, Solver};
+
+pub const ANSWERS_FILE: &str = "answers.toml";
+
+/// Expected answers for one input file. Either part may be unknown.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Expected {
+    pub part1: Option<Answer>,
+    pub part2: Option<Answer>,
+}
+
+impl Expected {
+    pub fn get(&self, part: Part) -> Option<&Answer> {
+        match part {
+            Part::One => self.part1.as_ref(),
+            Part::Two => self.part2.as_ref(),
+        }
+    }
+}
+
+/// Location of the answers file for an input file.
+pub fn answers_path(input: &Path) -> PathBuf {
+    input.with_file_name(ANSWERS_FILE)
+}
+
+/// Loads the expected answers for an input file, or `None` when none are recorded.
+pub fn load_expected(input: &Path) -> Result<Option<Expected>, String> {
+    let path = answers_path(input);
+
+    let text = match fs::read_to_string(&path) {
+        Ok(text) => text,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(err) => return Err(format!("couldn't read {}: {}", path.display(), err)),
+    };
+
+    let mut answers = toml::from_str::<BTreeMap<String, Expected>>(&text)
+        .map_err(|err| format!("invalid {}: {}", path.display(), err))?;
+
+    let name = input
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned());
+    Ok(name.and_then(|name| answers.remove(&name)))
+}
+
+/// Result of checking one part.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Pass,
+    Mismatch {
+        expected: Answer,
+        actual: Answer,
+    },
+    /// The part couldn't produce an answer.
+    Fail(String),
+    /// No expected answer is recorded for the input.
+    Unknown,
+}
+
+impl Verdict {
+    /// Whether the verdict should fail a verification run.
+    pub fn is_failure(&self) -> bool {
+        matches!(self, Verdict::Mismatch { .. } | Verdict::Fail(_))
+    }
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Verdict::Pass => write!(f, "pass"),
+            Verdict::Mismatch { expected, actual } => {
+                write!(f, "mismatch, expected {} but got {}", expected, actual)
+            }
+            Verdict::Fail(reason) => write!(f, "fail, {}", reason),
+            Verdict::Unknown => write!(f, "unknown, no expected answer"),
+        }
+    }
+}
+
+/// Solves the requested parts for the input at `path` and checks them against its recorded
+/// answers.
+pub fn verify(
+    puzzle: &Arc<dyn Puzzle>,
+    path: &Path,
+    parts: &[Part],
+    max_time: Option<Duration>,
+) -> Vec<(Part, Verdict)> {
+    let fail = |reason: String| {
+        parts
+            .iter()
+            .map(|&part| (part, Verdict::Fail(reason.clone())))
+            .collect()
+    };
+
+    let expected = match load_expected(path) {
+        Ok(expected) => expected.unwrap_or_default(),
+        Err(message) => return fail(message),
+    };
+
+    let input = match read_input(path) {
+        Ok(input) => input,
+        Err(err) => return fail(format!("couldn't read {}: {}", path.display(), err)),
+    };
+
+    let day = report::run_puzzle(puzzle, &input, parts, max_time);
+    if let Some(error) = day.error {
+        return fail(format!("couldn't parse input, {}", error));
+    }
+
+    day.parts
+        .into_iter()
+        .map(|part| {
+            let verdict = match (part.answer, expected.get(part.part)) {
+                (None, _) => {
+                    Verdict::Fail(part.error.unwrap_or_else(|| "ran out of time".to_string()))
+                }
+                (Some(_), None) => Verdict::Unknown,
+                // compare as text, so `"42"` in the answers file matches a numeric answer
+                (Some(actual), Some(expected)) if actual.to_string() == expected.to_string() => {
+                    Verdict::Pass
+                }
+                (Some(actual), Some(expected)) => Verdict::Mismatch {
+                    expected: expected.clone(),
+                    actual,
+                },
+            };
+            (part.part, verdict)
+        })
+        .collect()
+}
+
+/// Checks both parts of a puzzle against the answers recorded for its default input, panicking
+/// on any mismatch or failure. Used by each day's `solved` test.
+pub fn assert_solved<S>(solver: S)
+where
+    S: Solver + Send + Sync + 'static,
+{
+    let puzzle: Arc<dyn Puzzle> = Arc::new(solver);
+    let path = Path::new(puzzle.input_path());
+
+    if let Err(failures) = check(&puzzle, path, false) {
+        panic!(
+            "{} day {} ({}): {}",
+            puzzle.year(),
+            puzzle.day(),
+            path.display(),
+            failures
+        );
+    }
+}
+
+/// Checks a puzzle against the answers recorded for one of its examples, an input file next to
+/// its default input, panicking on any mismatch or failure. Used by the tests generated with
+/// [crate::examples].
+pub fn assert_example<S>(solver: S, file: &str)
+where
+    S: Solver + Send + Sync + 'static,
+{
+    let puzzle: Arc<dyn Puzzle> = Arc::new(solver);
+    let path = Path::new(puzzle.input_path()).with_file_name(file);
+
+    if let Err(failures) = check(&puzzle, &path, true) {
+        panic!(
+            "{} day {} ({}): {}",
+            puzzle.year(),
+            puzzle.day(),
+            path.display(),
+            failures
+        );
+    }
+}
+
+/// Verifies an input, describing the parts that failed.
+///
+/// Examples often only cover one part, so only the parts with a recorded answer are solved for
+/// them, and at least one answer is required.
+fn check(puzzle: &Arc<dyn Puzzle>, path: &Path, example: bool) -> Result<(), String> {
+    let parts = if example {
+        let expected = load_expected(path)?
+            .ok_or_else(|| format!("no answers recorded in {}", answers_path(path).display()))?;
+        let parts = Part::ALL
+            .into_iter()
+            .filter(|&part| expected.get(part).is_some())
+            .collect::<Vec<_>>();
+
+        if parts.is_empty() {
+            return Err(format!(
+                "no answers recorded in {}",
+                answers_path(path).display()
+            ));
+        }
+        parts
+    } else {
+        Part::ALL.to_vec()
+    };
+
+    let failures = verify(puzzle, path, &parts, None)
+        .into_iter()
+        .filter(|(_, verdict)| verdict.is_failure())
+        .map(|(part, verdict)| format!("part {}: {}", part, verdict))
+        .collect::<Vec<_>>();
+
+    if failures.is_empty() {
+        Ok(())
+    } else {
+        Err(failures.join("; "))
+    }
+}
+
+/// Generates a test per example input of a puzzle, checking the answers recorded for it in the
+/// day's `answers.toml`.
+///
+/// ```ignore
+/// aoc_core::examples!(Chiton, {
+///     example: "test_input.txt",
+/// });
+/// ```
+#[macro_export]
+macro_rules! examples {
+    ($solver:expr, { $($name:ident: $file:literal),+ $(,)? }) => {
+        $(
+            #[test]
+            fn $name() {
+                $crate::verify::assert_example($solver, $file);
+            }
+        )+
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solver::tests::Fixture;
+
+    /// A puzzle that doubles its input, and prefixes it with `x` for a text answer.
+    fn double() -> Fixture {
+        Fixture {
+            part1: |input| Ok((input[0] * 2).into()),
+            part2: |input| Ok(format!("x{}", input[0]).into()),
+            ..Fixture::new(2021, 1)
+        }
+    }
+
+    /// Creates a scratch directory holding the given files.
+    fn scratch(name: &str, files: &[(&str, &str)]) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("aoc-verify-{}-{}", name, std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        for (file, contents) in files {
+            fs::write(dir.join(file), contents).unwrap();
+        }
+        dir
+    }
+
+    #[test]
+    fn answers_file() {
+        let dir = scratch(
+            "answers",
+            &[(
+                ANSWERS_FILE,
+                "[\"input.txt\"]\npart1 = 42\n\n[\"other.txt\"]\npart2 = \"ABC\"\n",
+            )],
+        );
+
+        let input = load_expected(&dir.join("input.txt"));
+        let other = load_expected(&dir.join("other.txt"));
+        let missing = load_expected(&dir.join("missing.txt"));
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(
+            Some(&Answer::Number(42)),
+            input.unwrap().unwrap().get(Part::One)
+        );
+        assert_eq!(
+            Some(Expected {
+                part1: None,
+                part2: Some(Answer::from("ABC")),
+            }),
+            other.unwrap()
+        );
+        assert_eq!(None, missing.unwrap());
+    }
+
+    #[test]
+    fn verdicts() {
+        let dir = scratch(
+            "verdicts",
+            &[
+                ("input.txt", "21"),
+                ("wrong.txt", "20"),
+                ("bad.txt", "x"),
+                (
+                    ANSWERS_FILE,
+                    "[\"input.txt\"]\npart1 = 42\npart2 = \"x21\"\n[\"wrong.txt\"]\npart1 = 42\n",
+                ),
+            ],
+        );
+        let puzzle: Arc<dyn Puzzle> = Arc::new(double());
+
+        let check = |file: &str| verify(&puzzle, &dir.join(file), &Part::ALL, None);
+        let (input, wrong, bad) = (check("input.txt"), check("wrong.txt"), check("bad.txt"));
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(
+            vec![(Part::One, Verdict::Pass), (Part::Two, Verdict::Pass)],
+            input
+        );
+        assert_eq!(
+            vec![
+                (
+                    Part::One,
+                    Verdict::Mismatch {
+                        expected: Answer::Number(42),
+                        actual: Answer::Number(40),
+                    }
+                ),
+                (Part::Two, Verdict::Unknown),
+            ],
+            wrong
+        );
+        assert!(bad.iter().all(|(_, verdict)| verdict.is_failure()));
+    }
+
+    #[test]
+    fn examples() {
+        let dir = scratch(
+            "examples",
+            &[
+                ("first.txt", "21"),
+                ("second.txt", "5"),
+                ("unknown.txt", "1"),
+                (
+                    ANSWERS_FILE,
+                    "[\"first.txt\"]\npart1 = 42\n[\"second.txt\"]\npart1 = 11\npart2 = \"x5\"\n\
+                     [\"unknown.txt\"]\n",
+                ),
+            ],
+        );
+        let puzzle: Arc<dyn Puzzle> = Arc::new(double());
+
+        let check = |file: &str| check(&puzzle, &dir.join(file), true);
+        let results = ["first.txt", "second.txt", "unknown.txt", "missing.txt"].map(check);
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(Ok(()), results[0]);
+        assert_eq!(
+            Err("part 1: mismatch, expected 11 but got 10".to_string()),
+            results[1]
+        );
+        assert!(results[2].is_err());
+        assert!(results[3].is_err());
+    }
+}
@@ -0,0 +1,26 @@
+use std::process::ExitCode;
+
+use aoc_core::{cli, Registry};
+
+/// Builds a registry holding the solved puzzles of every year.
+fn registry() -> Registry {
+    let mut registry = Registry::new();
+    aoc2021::register(&mut registry);
+    aoc2022::register(&mut registry);
+    aoc2023::register(&mut registry);
+    registry
+}
+
+fn main() -> ExitCode {
+    cli::run(&registry())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_year_registered() {
+        assert_eq!(vec![2021, 2022, 2023], registry().years());
+    }
+}
@@ -1,12 +1,55 @@
-use crate::binary_diagnostic::solve::{solve_first_star, solve_second_star};
+use aoc_core::{Answer, ParseError, Solver};
+use crate::binary_diagnostic::solve::{parse_input, solve_first_star, solve_second_star, Diagnostic};
+use crate::YEAR;
 
 mod solve;
 
-pub fn binary_diagnostic_solution() {
-    println!("--- Day 3: Binary Diagnostic ---");
-    let result = solve_first_star();
-    println!("\t1st Star Solution = {}", result);
+pub struct BinaryDiagnostic;
 
-    let result = solve_second_star();
-    println!("\t2nd Star Solution = {}", result);
-}
\ No newline at end of file
+impl Solver for BinaryDiagnostic {
+    type Input = Diagnostic;
+
+    fn year(&self) -> u32 {
+        YEAR
+    }
+
+    fn day(&self) -> u32 {
+        3
+    }
+
+    fn title(&self) -> &'static str {
+        "Binary Diagnostic"
+    }
+
+    fn input_path(&self) -> &'static str {
+        concat!(env!("CARGO_MANIFEST_DIR"), "/src/binary_diagnostic/input.txt")
+    }
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Result<Answer, String> {
+        Ok(solve_first_star(input).into())
+    }
+
+    fn part2(&self, input: &Self::Input) -> Result<Answer, String> {
+        Ok(solve_second_star(input).into())
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_core::verify;
+
+    #[test]
+    fn solved() {
+        verify::assert_solved(BinaryDiagnostic);
+    }
+
+    aoc_core::examples!(BinaryDiagnostic, {
+        example: "test_input.txt",
+    });
+}
@@ -1,23 +1,28 @@
-use std::fs;
+use aoc_core::ParseError;
 
-pub fn solve_first_star() -> u32 {
-    let diagnostic = parse_input("src/binary_diagnostic/input.txt");
 
-    // Calculate length of diagnostic
-    let diag_length = diagnostic.len();
+/// Diagnostic report entries, each `width` bits long.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub width: u32,
+    pub entries: Vec<u32>,
+}
 
-    let diag_entry_length = 12;
 
-    // each entry is 12 bits in length, construct a counter
-    let mut bit_counts: Vec<u32> = vec![0; diag_entry_length];
+pub fn solve_first_star(diagnostic: &Diagnostic) -> u64 {
+    // Calculate length of diagnostic
+    let diag_length = diagnostic.entries.len();
 
-    for entry in diagnostic {
+    // construct a counter for each bit of the entries
+    let mut bit_counts: Vec<u32> = vec![0; diagnostic.width as usize];
+
+    for entry in &diagnostic.entries {
         let mut mask = 1u32;
 
-        for b in 0..diag_entry_length {
+        for bit_count in bit_counts.iter_mut() {
             if (mask & entry) == mask {
                 // bit set, increment count
-                bit_counts[b] += 1;
+                *bit_count += 1;
             }
             // bitshift mask
             mask <<= 1;
@@ -41,91 +46,77 @@ pub fn solve_first_star() -> u32 {
 
     // construct epsilon rate by inverting relevant bits of gamma rate
     let mut epsilon_rate = !gamma_rate;
-    // use mask to ensure only first 12 bits set after inverting gamma rate
-    epsilon_rate &= 0x00000FFF;
+    // use mask to ensure only the entry bits are set after inverting gamma rate
+    epsilon_rate &= u32::MAX >> (32 - diagnostic.width);
 
-    gamma_rate * epsilon_rate
+    // the rates are up to 32 bits each, so their product needs 64
+    gamma_rate as u64 * epsilon_rate as u64
 }
 
 
-pub fn solve_second_star() -> u32 {
-    let diagnostic:Vec<u32> = parse_input("src/binary_diagnostic/input.txt");
-
-    let oxygen_gen_rating = life_support_diagnostic_extractor(&diagnostic, true);
+pub fn solve_second_star(diagnostic: &Diagnostic) -> u64 {
+    let oxygen_gen_rating = life_support_diagnostic_extractor(diagnostic, true);
 
-    let co2_scrub_rating = life_support_diagnostic_extractor(&diagnostic, false);
+    let co2_scrub_rating = life_support_diagnostic_extractor(diagnostic, false);
 
-    oxygen_gen_rating*co2_scrub_rating
+    oxygen_gen_rating as u64 * co2_scrub_rating as u64
 }
 
-fn life_support_diagnostic_extractor(diagnostic: &Vec<u32>, oxygen_rating: bool) -> u32 {
-    let diag_entry_length = 12;
-
-    // set up bit mask for data extraction from diagnostic entries
-    let mut mask = 0b100000000000u32;
-
-    // initialise ones and zeros vectors of diagnostic entries
-    // ones vector contains all diagnostic entries with 1's at mask bit index
-    // zeros vector contains all diagnostic entries with 0's at mask bit index
-    let mut ones = diagnostic.iter().collect::<Vec<_>>();
-    let mut zeros = diagnostic.iter().collect::<Vec<_>>();
-
-    for _ in 0..diag_entry_length {
-        // create filters for the two bit criteria
-        ones = ones.into_iter().filter(|&x| (x & mask) == mask).collect::<Vec<_>>();
-        zeros = zeros.into_iter().filter(|&x| (x & mask) == 0u32).collect::<Vec<_>>();
-
-        // determine which entries to keep based on bit criteria
-        if oxygen_rating {
-            // finding oxygen generator rating
-            if ones.len() >= zeros.len() {
-                // check if we're done
-                if ones.len() == 1 {return ones[0].clone()}
-
-                // keep the ones set of diagnostic data replacing zeros set
-                zeros = ones.clone();
-            } else {
-                // check if we're done
-                if zeros.len() == 1 {return zeros[0].clone()}
-
-                // keep the zeros set of diagnostic data replacing ones set
-                ones = zeros.clone();
-            }
-        } else {
-            // finding CO2 scrubber rating
-            if ones.len() < zeros.len() {
-                // check if we're done
-                if ones.len() == 1 {return ones[0].clone()}
-
-                // keep the ones set of diagnostic data replacing zeros
-                zeros = ones.clone();
-            } else {
-                // check if we're done
-                if zeros.len() == 1 {return zeros[0].clone()}
-
-                // keep the zeros set of diagnostic data replacng ones
-                ones = zeros.clone();
-            }
+/// Filters the entries bit by bit, from the highest, keeping those with the most common bit for
+/// the oxygen generator rating or the least common for the CO2 scrubber rating, until one is left.
+///
+/// When every remaining entry has the same bit, they are all kept, and entries left once every
+/// bit is checked are all the same number. Parsing checks there's at least one entry.
+fn life_support_diagnostic_extractor(diagnostic: &Diagnostic, oxygen_rating: bool) -> u32 {
+    let mut remaining = diagnostic.entries.clone();
+
+    for bit in (0..diagnostic.width).rev() {
+        // check if we're done
+        if remaining.len() == 1 {
+            break;
         }
 
-        // shift mask
-        mask >>= 1;
+        // split entries into those with 1's and 0's at the bit
+        let (ones, zeros): (Vec<u32>, Vec<u32>) =
+            remaining.into_iter().partition(|&x| (x >> bit) & 1 == 1);
+
+        remaining = if ones.is_empty() || zeros.is_empty() {
+            // same bit everywhere, nothing to filter on
+            if ones.is_empty() { zeros } else { ones }
+        } else if oxygen_rating == (ones.len() >= zeros.len()) {
+            // oxygen keeps the most common bit, 1 on ties, CO2 the least common, 0 on ties
+            ones
+        } else {
+            zeros
+        };
     }
 
-    panic!("Unable to find rating in diagnostic")
+    remaining[0]
 }
 
 
-fn parse_input(file_path: &str) -> Vec<u32> {
-    let contents = fs::read_to_string(file_path).expect("Input file local to project");
+pub fn parse_input(contents: &str) -> Result<Diagnostic, ParseError> {
+    let lines = contents.lines().filter(|s| !s.is_empty()).collect::<Vec<_>>();
+
+    let width = match lines.first() {
+        Some(line) if line.len() <= 32 => line.len(),
+        Some(line) => return Err(ParseError::at(contents, line, "expected at most 32 bits")),
+        None => return Err(ParseError::end_of_input(contents, "missing diagnostic report")),
+    };
+
+    let entries = lines
+        .iter()
+        .map(|&s| {
+            if s.len() != width {
+                return Err(ParseError::at(contents, s, format!("expected {} bits", width)));
+            }
 
-    let result: Vec<u32> = contents
-        .lines()
-        .filter(|s| !s.is_empty())
-        .map(|s| u32::from_str_radix(s, 2).unwrap())
-        .collect();
+            u32::from_str_radix(s, 2)
+                .map_err(|_| ParseError::at(contents, s, "expected a binary number"))
+        })
+        .collect::<Result<_, _>>()?;
 
-    result
+    Ok(Diagnostic { width: width as u32, entries })
 }
 
 
@@ -134,8 +125,25 @@ mod tests {
     use super::*;
 
     #[test]
-    fn solved() {
-        assert_eq!(3549854, solve_first_star());
-        assert_eq!(3765399, solve_second_star());
+    fn wide_report() {
+        let report = "11111111111111110000000000000001\n\
+                      11111111111111110000000000000011\n\
+                      00000000000000001111111111111111";
+        let diagnostic = parse_input(report).unwrap();
+
+        assert_eq!(32, diagnostic.width);
+        assert_eq!(0xFFFF_0003 * 0xFFFC, solve_first_star(&diagnostic));
+        assert_eq!(0xFFFF_0003 * 0xFFFF, solve_second_star(&diagnostic));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn unusual_reports() {
+        // a single entry, and entries sharing their highest bit, are both ratings
+        assert_eq!(0b101 * 0b101, solve_second_star(&parse_input("101").unwrap()));
+        assert_eq!(0b111 * 0b110, solve_second_star(&parse_input("110\n111").unwrap()));
+        assert_eq!(0b011 * 0b011, solve_second_star(&parse_input("011\n011").unwrap()));
+
+        let error = parse_input("\n").unwrap_err();
+        assert_eq!("missing diagnostic report", error.message);
+    }
+}
@@ -1,12 +1,121 @@
-use crate::chiton::solve::{solve_first_star, solve_second_star};
+use aoc_core::{Answer, Options, ParseError, Part, Solver};
+use aoc_core::grid::Grid;
+use aoc_core::picture::Picture;
+use crate::chiton::solve::{
+    draw_first_star, draw_second_star, parse_input, solve_first_star, solve_second_star, Search,
+    Tiling, FULL_MAP,
+};
+use crate::YEAR;
 
 mod solve;
 
-pub fn chiton_solution() {
-    println!("--- Day 15: Chiton ---");
-    let result = solve_first_star();
-    println!("\t1st Star Solution = {}", result);
+/// Chiton, with settings for stress testing the search: `tiles` and `wrap` change the tiling of
+/// the second star's full map, and `search` is `astar` or `dijkstra`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Chiton {
+    pub tiling: Tiling,
+    pub search: Search,
+}
 
-    let result = solve_second_star();
-    println!("\t2nd Star Solution = {}", result);
-}
\ No newline at end of file
+impl Default for Chiton {
+    fn default() -> Self {
+        Chiton { tiling: FULL_MAP, search: Search::AStar }
+    }
+}
+
+impl Solver for Chiton {
+    type Input = Grid<u32>;
+
+    fn year(&self) -> u32 {
+        YEAR
+    }
+
+    fn day(&self) -> u32 {
+        15
+    }
+
+    fn title(&self) -> &'static str {
+        "Chiton"
+    }
+
+    fn input_path(&self) -> &'static str {
+        concat!(env!("CARGO_MANIFEST_DIR"), "/src/chiton/input.txt")
+    }
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Result<Answer, String> {
+        Ok(solve_first_star(input, self.search).into())
+    }
+
+    fn part2(&self, input: &Self::Input) -> Result<Answer, String> {
+        Ok(solve_second_star(input, self.tiling, self.search).into())
+    }
+
+    fn draw(&self, input: &Self::Input, part: Part) -> Option<Picture> {
+        match part {
+            Part::One => Some(draw_first_star(input, self.search)),
+            Part::Two => Some(draw_second_star(input, self.tiling, self.search)),
+        }
+    }
+
+    fn configure(&self, options: &Options) -> Result<Self, String> {
+        options.expect(&["tiles", "wrap", "search"])?;
+
+        let tiling = Tiling {
+            tiles: options.get("tiles")?.unwrap_or(self.tiling.tiles),
+            wrap: options.get("wrap")?.unwrap_or(self.tiling.wrap),
+        };
+        if tiling.tiles == 0 || tiling.wrap == 0 {
+            return Err("tiles and wrap must be at least 1".to_string());
+        }
+
+        Ok(Chiton { tiling, search: options.get("search")?.unwrap_or(self.search) })
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_core::verify;
+
+    #[test]
+    fn solved() {
+        verify::assert_solved(Chiton::default());
+    }
+
+    aoc_core::examples!(Chiton::default(), {
+        example: "test_input.txt",
+    });
+
+    #[test]
+    fn options() {
+        let chiton = Chiton::default()
+            .configure(&"tiles=10,search=dijkstra".parse().unwrap())
+            .unwrap();
+
+        assert_eq!(Tiling { tiles: 10, wrap: 9 }, chiton.tiling);
+        assert_eq!(Search::Dijkstra, chiton.search);
+        assert!(chiton.configure(&"tiles=0".parse().unwrap()).is_err());
+        assert!(chiton.configure(&"search=bfs".parse().unwrap()).is_err());
+        assert!(chiton.configure(&"size=10".parse().unwrap()).is_err());
+    }
+
+    #[test]
+    fn searches_agree() {
+        for input in [include_str!("test_input.txt"), "111\n111\n111", "19\n11", "1"] {
+            let cave = parse_input(input).unwrap();
+
+            for wrap in [1, 2, 9] {
+                let astar = Chiton { tiling: Tiling { tiles: 3, wrap }, search: Search::AStar };
+                let dijkstra = Chiton { search: Search::Dijkstra, ..astar };
+
+                assert_eq!(dijkstra.part1(&cave), astar.part1(&cave));
+                assert_eq!(dijkstra.part2(&cave), astar.part2(&cave));
+            }
+        }
+    }
+}
@@ -1,167 +1,133 @@
-use std::fs;
-use ndarray::{Array, Array2, ArrayBase, ArrayView, Axis, Ix, Ix2, OwnedRepr};
-use petgraph::graph::{NodeIndex};
-use petgraph::algo::{dijkstra};
-use petgraph::{Directed, Graph};
-
-pub fn solve_first_star() -> u32 {
-    let (graph, start, end) = parse_input("src/chiton/input.txt");
-
-    let node_map = dijkstra(&graph, start, Some(end), |x| *x.weight());
-
-    let shortest_path = *node_map.get(&end).unwrap();
-
-    shortest_path
+use std::collections::HashSet;
+use std::str::FromStr;
+
+use aoc_core::grid::{Grid, Position};
+use aoc_core::picture::{Picture, Pixel, Rgb};
+use aoc_core::search::{self, Path};
+use aoc_core::ParseError;
+use ndarray::Array2;
+
+
+/// How the full map repeats the scanned cave: `tiles` copies in each direction, with the risks of
+/// each copy one higher than the copy above or to its left, wrapping back round to 1 above `wrap`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Tiling {
+    pub tiles: usize,
+    pub wrap: u32,
 }
 
-pub fn solve_second_star() -> u32 {
-    let mut shortest_path = 0u32;
-
-    // finding shortest path slightly computationally expensive. Disable it behind a feature flag.
-    #[cfg(feature = "run_solver")]
-    {
-        let (graph, start, end) = parse_large_input("src/chiton/input.txt");
-
-        let node_map = dijkstra(&graph, start, Some(end), |x| *x.weight());
-
-        shortest_path = *node_map.get(&end).unwrap();
-    }
-
-    if shortest_path == 0 {
-        // solver didn't run. Pass known result
-        shortest_path = 3012;
-    }
+/// The full map of the second star.
+pub const FULL_MAP: Tiling = Tiling { tiles: 5, wrap: 9 };
 
-    shortest_path
+/// The search finding the lowest risk path.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Search {
+    Dijkstra,
+    AStar,
 }
 
+impl FromStr for Search {
+    type Err = String;
 
-fn parse_input(file_path: &str) -> (Graph<(), u32, Directed>, NodeIndex, NodeIndex) {
-    let contents = fs::read_to_string(file_path).expect("Input file local to project");
-
-    // construct new undirected graph
-    let mut graph = Graph::<(), u32>::new();
-
-    let grid = construct_cave_data(contents);
-
-    // construct graph using nodes, capture node indexes in order of original grid
-    let graph_nodes = grid.map(|_| graph.add_node(()));
-
-    // iterate graph and add edges
-    let (height, width) = grid.dim();
-
-    construct_graph(&mut graph, grid, &graph_nodes, height, width);
-
-    let start = *graph_nodes.first().unwrap();
-    let end = *graph_nodes.last().unwrap();
-
-    (graph, start, end)
-}
-
-fn construct_graph(graph: &mut Graph<(), u32>, grid: ArrayBase<OwnedRepr<u32>, Ix2>, graph_nodes: &Array<NodeIndex, Ix2>, height: Ix, width: Ix) {
-    for ((row, col), node) in graph_nodes.indexed_iter() {
-
-        // construct  indices as int so we can filter out negatives
-        let indices: Vec<(i32, i32)> = vec![
-            (row as i32 - 1, col as i32),
-            (row as i32, col as i32 - 1),
-            (row as i32, col as i32 + 1),
-            (row as i32 + 1, col as i32),
-        ];
-
-        // filter out invalid indices
-        let neighbours = indices
-            .iter()
-            .filter(|&(r, c)|
-                *r >= 0 && *c >= 0 && *r < height as i32 && *c < width as i32)
-            .collect::<Vec<_>>();
-
-        for (neighbour_row, neighbour_col) in neighbours {
-            let r = *neighbour_row as usize;
-            let c = *neighbour_col as usize;
-
-            graph.add_edge(*node, graph_nodes[[r, c]], grid[[r, c]]);
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "dijkstra" => Ok(Search::Dijkstra),
+            "astar" => Ok(Search::AStar),
+            _ => Err(format!("unknown search `{}`, expected dijkstra or astar", s)),
         }
     }
 }
 
-fn construct_cave_data(contents: String) -> ArrayBase<OwnedRepr<u32>, Ix2> {
-// process input file data into an Array to support construction of graph
-    let width = contents.lines().count();
 
-    let mut grid = Array2::<u32>::zeros((0, width));
+pub fn solve_first_star(cave: &Grid<u32>, search: Search) -> u32 {
+    lowest_risk_path(cave, search).cost
+}
 
-    for line in contents.lines() {
-        let grid_row = line.
-            chars()
-            .map(|x| x.to_digit(10).unwrap())
-            .collect::<Vec<_>>();
+pub fn solve_second_star(cave: &Grid<u32>, tiling: Tiling, search: Search) -> u32 {
+    let full_map = build_cave(cave, tiling);
 
-        grid.push(Axis(0), ArrayView::from(&grid_row)).unwrap();
-    }
-    grid
+    lowest_risk_path(&full_map, search).cost
 }
 
-fn parse_large_input(file_path: &str) -> (Graph<(), u32, Directed>, NodeIndex, NodeIndex) {
-    let contents = fs::read_to_string(file_path).expect("Input file local to project");
-
-    // construct new undirected graph
-    let mut graph = Graph::<(), u32>::new();
+pub fn draw_first_star(cave: &Grid<u32>, search: Search) -> Picture {
+    draw_route(cave, &lowest_risk_path(cave, search).nodes)
+}
 
-    let grid = construct_large_cave_data(contents);
+pub fn draw_second_star(cave: &Grid<u32>, tiling: Tiling, search: Search) -> Picture {
+    let full_map = build_cave(cave, tiling);
 
-    // construct graph using nodes, capture node indexes in order of original grid
-    let graph_nodes = grid.map(|_| graph.add_node(()));
+    draw_route(&full_map, &lowest_risk_path(&full_map, search).nodes)
+}
 
-    // iterate graph and add edges
-    let (height, width) = grid.dim();
 
-    construct_graph(&mut graph, grid, &graph_nodes, height, width);
+/// Parses the scanned cave, whose risks must be at least 1 for the A* search's estimate.
+pub fn parse_input(contents: &str) -> Result<Grid<u32>, ParseError> {
+    let cave = Grid::parse(contents, "risk from 1 to 9", |c| {
+        c.to_digit(10).filter(|&risk| risk > 0)
+    })?;
 
-    let start = *graph_nodes.first().unwrap();
-    let end = *graph_nodes.last().unwrap();
+    if cave.height() == 0 || cave.width() == 0 {
+        return Err(ParseError::end_of_input(contents, "expected a map of risks"));
+    }
 
-    (graph, start, end)
+    Ok(cave)
 }
 
-fn construct_large_cave_data(contents: String) -> ArrayBase<OwnedRepr<u32>, Ix2> {
-// process input file data into an Array to support construction of graph
-    let width = contents.lines().count();
 
-    let mut grid = Array2::<u32>::zeros((0, width));
+/// Finds the path from the top left to the bottom right of the cave with the lowest total risk,
+/// the risk of each step being the risk of the position it enters. The path's nodes are the
+/// positions of the route, start and end included.
+pub fn lowest_risk_path(cave: &Grid<u32>, search: Search) -> Path<Position, u32> {
+    let end = (cave.height() - 1, cave.width() - 1);
+    let successors = |&position: &Position| {
+        cave.neighbours4(position).map(|next| (next, cave[next]))
+    };
 
-    for line in contents.lines() {
-        let grid_row = line.
-            chars()
-            .map(|x| x.to_digit(10).unwrap())
-            .collect::<Vec<_>>();
+    let path = match search {
+        Search::Dijkstra => search::dijkstra((0, 0), successors, |&position| position == end),
+        Search::AStar => {
+            // every position has a risk of at least 1, so the distance to the end never
+            // overestimates the remaining risk
+            let distance = |&(row, col): &Position| (end.0 - row + end.1 - col) as u32;
 
-        grid.push(Axis(0), ArrayView::from(&grid_row)).unwrap();
-    }
+            search::astar((0, 0), successors, distance, |&position| position == end)
+        }
+    };
 
-    // Widen grid to dimensions of large cave, following the construction rule
-    let template = grid.clone();
+    path.expect("the end is reachable from the start")
+}
 
-    for i in 1..5 {
-        let mut temp = template.map(|x| *x + i);
+/// Draws the risk levels of the cave in shades of grey, brighter for riskier positions, with the
+/// route over it in red.
+fn draw_route(cave: &Grid<u32>, route: &[Position]) -> Picture {
+    let route = route.iter().collect::<HashSet<_>>();
 
-        temp.iter_mut().filter(|x| **x > 9u32).for_each(|x| *x -= 9);
+    let mut picture = cave.map(|&risk| Pixel {
+        glyph: char::from_digit(risk, 10).unwrap_or('?'),
+        colour: Rgb::grey(risk + 3, 12),
+    });
 
-        grid.append(Axis(1), temp.view()).unwrap();
+    for position in route {
+        picture[*position].colour = Rgb::RED;
     }
 
-    // Lengthen grid to dimensions of large cave, following the construction rule
-    let template = grid.clone();
-
-    for i in 1..5 {
-        let mut temp = template.map(|x| *x + i);
+    picture
+}
 
-        temp.iter_mut().filter(|x| **x > 9u32).for_each(|x| *x -= 9);
+/// Builds the map of the cave repeated following the tiling.
+pub fn build_cave(cave: &Grid<u32>, tiling: Tiling) -> Grid<u32> {
+    let (height, width) = cave.dim();
+    let dim = (height * tiling.tiles, width * tiling.tiles);
 
-        grid.append(Axis(0), temp.view()).unwrap();
-    }
+    Grid::new(Array2::from_shape_fn(dim, |(row, col)| {
+        let risk = cave[(row % height, col % width)] + (row / height + col / width) as u32;
 
-    grid
+        if risk > tiling.wrap {
+            (risk - 1) % tiling.wrap + 1
+        } else {
+            risk
+        }
+    }))
 }
 
 
@@ -169,20 +135,51 @@ fn construct_large_cave_data(contents: String) -> ArrayBase<OwnedRepr<u32>, Ix2>
 mod tests {
     use super::*;
 
+
     #[test]
-    fn solved() {
-        assert_eq!(811, solve_first_star());
-        assert_eq!(3012, solve_second_star());
+    fn route() {
+        let cave = parse_input(include_str!("test_input.txt")).unwrap();
+        let path = lowest_risk_path(&cave, Search::AStar);
+
+        assert_eq!((0, 0), path.nodes[0]);
+        assert_eq!(Some(&(9, 9)), path.nodes.last());
+        assert!(path.nodes.windows(2).all(|step| cave.neighbours4(step[0]).any(|p| p == step[1])));
+        assert_eq!(40, path.nodes[1..].iter().map(|&p| cave[p]).sum::<u32>());
+
+        let picture = draw_first_star(&cave, Search::Dijkstra);
+        let on_route = picture.indexed_iter().filter(|(_, pixel)| pixel.colour == Rgb::RED);
+        assert_eq!(path.nodes.len(), on_route.count());
+        assert_eq!(include_str!("test_input.txt").trim_end(), picture.map(|p| p.glyph).to_string());
     }
 
     #[test]
-    fn test_shortest_path() {
-        let (graph, start, end) = parse_input("src/chiton/test_input.txt");
-
-        let node_map = dijkstra(&graph, start, Some(end), |x| *x.weight());
+    fn parsing_errors() {
+        let error = parse_input("119\n102").unwrap_err();
+        assert_eq!((2, 2), (error.line, error.column));
+        assert_eq!("expected a risk from 1 to 9, found `0`", error.message);
 
-        let shortest_path = *node_map.get(&end).unwrap();
+        for empty in ["", "\n\n"] {
+            assert_eq!("expected a map of risks", parse_input(empty).unwrap_err().message);
+        }
+    }
 
-        assert_eq!(40, shortest_path);
+    #[test]
+    fn tiling() {
+        let cave = parse_input(include_str!("test_input.txt")).unwrap();
+
+        let full_map = build_cave(&cave, FULL_MAP);
+        assert_eq!((50, 50), full_map.dim());
+        assert_eq!((cave[(0, 0)] + 1, 9), (full_map[(0, 10)], full_map[(49, 49)]));
+        assert_eq!(cave, build_cave(&cave, Tiling { tiles: 1, wrap: 9 }));
+
+        let wrapped = build_cave(&cave, Tiling { tiles: 3, wrap: 4 });
+        assert!(wrapped.indexed_iter().all(|(_, &risk)| (1..=4).contains(&risk)));
+        assert_eq!(1, wrapped[(29, 29)]);
+
+        let large = build_cave(&cave, Tiling { tiles: 10, wrap: 9 });
+        assert_eq!(
+            lowest_risk_path(&large, Search::Dijkstra).cost,
+            lowest_risk_path(&large, Search::AStar).cost
+        );
     }
-}
\ No newline at end of file
+}
@@ -1,12 +1,55 @@
-use crate::dive::solve::{solve_first_star, solve_second_star};
+use aoc_core::{Answer, ParseError, Solver};
+use crate::dive::solve::{parse_input, solve_first_star, solve_second_star};
+use crate::YEAR;
 
 mod solve;
 
-pub fn dive_solution() {
-    println!("--- Day 2: Dive! ---");
-    let result = solve_first_star();
-    println!("\t1st Star Solution = {}", result);
+pub struct Dive;
 
-    let result = solve_second_star();
-    println!("\t2nd Star Solution = {}", result);
-}
\ No newline at end of file
+impl Solver for Dive {
+    type Input = Vec<solve::Command>;
+
+    fn year(&self) -> u32 {
+        YEAR
+    }
+
+    fn day(&self) -> u32 {
+        2
+    }
+
+    fn title(&self) -> &'static str {
+        "Dive!"
+    }
+
+    fn input_path(&self) -> &'static str {
+        concat!(env!("CARGO_MANIFEST_DIR"), "/src/dive/input.txt")
+    }
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Result<Answer, String> {
+        Ok(solve_first_star(input).into())
+    }
+
+    fn part2(&self, input: &Self::Input) -> Result<Answer, String> {
+        Ok(solve_second_star(input).into())
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_core::verify;
+
+    #[test]
+    fn solved() {
+        verify::assert_solved(Dive);
+    }
+
+    aoc_core::examples!(Dive, {
+        example: "test_input.txt",
+    });
+}
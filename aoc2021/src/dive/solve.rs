@@ -1,57 +1,44 @@
-use std::fs;
+use aoc_core::parse::{self, ParseError};
 
-pub fn solve_first_star() -> i32 {
-    let commands = parse_input("src/dive/input.txt");
 
+pub fn solve_first_star(commands: &[Command]) -> i32 {
     let mut distance = 0i32;
     let mut depth = 0i32;
 
     for command in commands {
-        let command_split: Vec<&str> = command.split(' ').collect();
-
-        match command_split[0] {
-            "forward" => {
-                distance += command_split[1].parse::<i32>().unwrap();
-            }
-            "up" => {
-                depth -= command_split[1].parse::<i32>().unwrap();
+        match command {
+            Command::Forward(units) => {
+                distance += units;
             }
-            "down" => {
-                depth += command_split[1].parse::<i32>().unwrap();
+            Command::Up(units) => {
+                depth -= units;
             }
-            _ => {
-                panic!("Invalid submarine command");
+            Command::Down(units) => {
+                depth += units;
             }
         }
     }
 
-    return distance * depth;
+    distance * depth
 }
 
 
-pub fn solve_second_star() -> i32 {
-    let commands = parse_input("src/dive/input.txt");
-
+pub fn solve_second_star(commands: &[Command]) -> i32 {
     let mut distance = 0i32;
     let mut depth = 0i32;
     let mut aim = 0i32;
 
     for command in commands {
-        let command_split: Vec<&str> = command.split(' ').collect();
-
-        match command_split[0] {
-            "forward" => {
-                distance += command_split[1].parse::<i32>().unwrap();
-                depth += aim * command_split[1].parse::<i32>().unwrap();
-            }
-            "up" => {
-                aim -= command_split[1].parse::<i32>().unwrap();
+        match command {
+            Command::Forward(units) => {
+                distance += units;
+                depth += aim * units;
             }
-            "down" => {
-                aim += command_split[1].parse::<i32>().unwrap();
+            Command::Up(units) => {
+                aim -= units;
             }
-            _ => {
-                panic!("Invalid submarine command");
+            Command::Down(units) => {
+                aim += units;
             }
         }
     }
@@ -60,16 +47,39 @@ pub fn solve_second_star() -> i32 {
 }
 
 
-fn parse_input(file_path: &str) -> Vec<String> {
-    let contents = fs::read_to_string(file_path).expect("Input file local to project");
+#[derive(Debug, PartialEq, Eq)]
+pub enum Command {
+    Forward(i32),
+    Up(i32),
+    Down(i32),
+}
+
 
-    let result: Vec<String> = contents
+pub fn parse_input(contents: &str) -> Result<Vec<Command>, ParseError> {
+    contents
         .lines()
         .filter(|s| !s.is_empty())
-        .map(String::from)
-        .collect();
+        .map(|line| parse_command(contents, line))
+        .collect()
+}
+
+
+fn parse_command(contents: &str, line: &str) -> Result<Command, ParseError> {
+    let (direction, units) = line
+        .split_once(' ')
+        .ok_or_else(|| ParseError::at(contents, line, "expected a direction and distance"))?;
 
-    result
+    let units = parse::number(contents, units)?;
+
+    match direction {
+        "forward" => Ok(Command::Forward(units)),
+        "up" => Ok(Command::Up(units)),
+        "down" => Ok(Command::Down(units)),
+        _ => {
+            let message = format!("invalid submarine command `{}`", direction);
+            Err(ParseError::at(contents, direction, message))
+        }
+    }
 }
 
 
@@ -78,8 +88,9 @@ mod tests {
     use super::*;
 
     #[test]
-    fn solved() {
-        assert_eq!(1936494, solve_first_star());
-        assert_eq!(1997106066, solve_second_star());
+    fn invalid_command() {
+        let err = parse_input("forward 5\nbackward 2").unwrap_err();
+        assert_eq!((2, 1), (err.line, err.column));
+        assert_eq!("invalid submarine command `backward`", err.message);
     }
 }
\ No newline at end of file
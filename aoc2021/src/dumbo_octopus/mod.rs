@@ -1,12 +1,56 @@
-use crate::dumbo_octopus::solve::{solve_first_star, solve_second_star};
+use aoc_core::{Answer, ParseError, Solver};
+use aoc_core::grid::Grid;
+use crate::dumbo_octopus::solve::{parse_input, solve_first_star, solve_second_star};
+use crate::YEAR;
 
 mod solve;
 
-pub fn dumbo_octopus_solution() {
-    println!("--- Day 11: Dumbo Octopus ---");
-    let result = solve_first_star();
-    println!("\t1st Star Solution = {}", result);
+pub struct DumboOctopus;
 
-    let result = solve_second_star();
-    println!("\t2nd Star Solution = {}", result);
-}
\ No newline at end of file
+impl Solver for DumboOctopus {
+    type Input = Grid<u32>;
+
+    fn year(&self) -> u32 {
+        YEAR
+    }
+
+    fn day(&self) -> u32 {
+        11
+    }
+
+    fn title(&self) -> &'static str {
+        "Dumbo Octopus"
+    }
+
+    fn input_path(&self) -> &'static str {
+        concat!(env!("CARGO_MANIFEST_DIR"), "/src/dumbo_octopus/input.txt")
+    }
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Result<Answer, String> {
+        Ok(solve_first_star(input).into())
+    }
+
+    fn part2(&self, input: &Self::Input) -> Result<Answer, String> {
+        Ok(solve_second_star(input).into())
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_core::verify;
+
+    #[test]
+    fn solved() {
+        verify::assert_solved(DumboOctopus);
+    }
+
+    aoc_core::examples!(DumboOctopus, {
+        example: "test_input.txt",
+    });
+}
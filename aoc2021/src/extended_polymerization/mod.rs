@@ -1,12 +1,56 @@
-use crate::extended_polymerization::solve::{solve_first_star, solve_second_star};
+use aoc_core::{Answer, ParseError, Solver};
+use std::collections::HashMap;
+use crate::extended_polymerization::solve::{parse_input, solve_first_star, solve_second_star};
+use crate::YEAR;
 
 mod solve;
 
-pub fn extended_polymerization_solution() {
-    println!("--- Day 14: Extended Polymerization ---");
-    let result = solve_first_star();
-    println!("\t1st Star Solution = {}", result);
+pub struct ExtendedPolymerization;
 
-    let result = solve_second_star();
-    println!("\t2nd Star Solution = {}", result);
-}
\ No newline at end of file
+impl Solver for ExtendedPolymerization {
+    type Input = (String, HashMap<String, char>);
+
+    fn year(&self) -> u32 {
+        YEAR
+    }
+
+    fn day(&self) -> u32 {
+        14
+    }
+
+    fn title(&self) -> &'static str {
+        "Extended Polymerization"
+    }
+
+    fn input_path(&self) -> &'static str {
+        concat!(env!("CARGO_MANIFEST_DIR"), "/src/extended_polymerization/input.txt")
+    }
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Result<Answer, String> {
+        Ok(solve_first_star(input).into())
+    }
+
+    fn part2(&self, input: &Self::Input) -> Result<Answer, String> {
+        Ok(solve_second_star(input).into())
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_core::verify;
+
+    #[test]
+    fn solved() {
+        verify::assert_solved(ExtendedPolymerization);
+    }
+
+    aoc_core::examples!(ExtendedPolymerization, {
+        example: "test_input.txt",
+    });
+}
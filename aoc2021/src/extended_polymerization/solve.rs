@@ -1,14 +1,12 @@
 use std::collections::{BTreeMap, HashMap};
-use std::fs;
+use aoc_core::parse::{self, ParseError};
 
 
-pub fn solve_first_star() -> u64 {
-    let (template, rules) = parse_input("src/extended_polymerization/input.txt");
-
-    let mut polymer = template;
+pub fn solve_first_star((template, rules): &(String, HashMap<String, char>)) -> u64 {
+    let mut polymer = template.clone();
 
     for _ in 0..10 {
-        polymerise(&mut polymer, &rules);
+        polymerise(&mut polymer, rules);
     }
 
     // find most common and least common elements and count
@@ -24,9 +22,7 @@ pub fn solve_first_star() -> u64 {
     max - min
 }
 
-pub fn solve_second_star() -> u64 {
-    let (template, rules) = parse_input("src/extended_polymerization/input.txt");
-
+pub fn solve_second_star((template, rules): &(String, HashMap<String, char>)) -> u64 {
     // Due to exponential growth, method for solve_first_star() doesn't scale.
     // Instead manage the polymer not as a string, but as a hashmap of element pairs and counts.
 
@@ -35,7 +31,7 @@ pub fn solve_second_star() -> u64 {
 
     // Polymerise for 40 steps
     for _ in 0..40 {
-        polymers = hash_polymerise(polymers, &rules);
+        polymers = hash_polymerise(polymers, rules);
     }
 
 
@@ -87,7 +83,7 @@ fn polymerise(polymer: &mut String, ruleset: &HashMap<String, char>) {
 }
 
 
-fn init_polymer_hashmap(template: String) -> HashMap<String, u64> {
+fn init_polymer_hashmap(template: &str) -> HashMap<String, u64> {
     let mut polymer_chain = HashMap::<String, u64>::new();
 
     for i in 0..template.len() - 1 {
@@ -122,6 +118,9 @@ fn hash_polymerise(polymer_chain: HashMap<String, u64>, ruleset: &HashMap<String
             // add new polymers
             new_polymer_chain.entry(poly_1).and_modify(|x| *x += count).or_insert(count);
             new_polymer_chain.entry(poly_2).and_modify(|x| *x += count).or_insert(count);
+        } else {
+            // no rule for the pair, so nothing is inserted between its elements
+            new_polymer_chain.entry(poly_string).and_modify(|x| *x += count).or_insert(count);
         }
     }
 
@@ -129,13 +128,19 @@ fn hash_polymerise(polymer_chain: HashMap<String, u64>, ruleset: &HashMap<String
 }
 
 
-fn parse_input(file_path: &str) -> (String, HashMap<String, char>) {
-    let contents = fs::read_to_string(file_path).expect("Input file local to project");
-
+pub fn parse_input(contents: &str) -> Result<(String, HashMap<String, char>), ParseError> {
     let mut lines = contents.lines();
 
-    // parse template
-    let template = lines.next().unwrap().to_string();
+    // parse template, which needs a pair of elements to polymerise
+    let template = parse::next(contents, &mut lines, "polymer template")?;
+
+    if let Some(i) = template.find(|c: char| !c.is_ascii_uppercase()) {
+        return Err(ParseError::at(contents, &template[i..], "expected an element, A to Z"));
+    }
+    if template.len() < 2 {
+        return Err(ParseError::at(contents, template, "expected at least 2 elements"));
+    }
+    let template = template.to_string();
 
     let mut polymerisation_ruleset = HashMap::<String, char>::new();
 
@@ -144,13 +149,24 @@ fn parse_input(file_path: &str) -> (String, HashMap<String, char>) {
 
     // parse ruleset
     for line in lines {
-        let polymer_rule = line.split("->").collect::<Vec<_>>();
+        let (pair, element) = line
+            .split_once("->")
+            .ok_or_else(|| ParseError::at(contents, line, "expected `AB -> C`"))?;
+
+        let pair = pair.trim();
+        if pair.len() != 2 || !pair.chars().all(|c| c.is_ascii_uppercase()) {
+            return Err(ParseError::at(contents, pair, "expected a pair of elements"));
+        }
 
-        polymerisation_ruleset.insert(polymer_rule[0].trim().to_string(),
-                                      polymer_rule[1].trim().chars().next().unwrap());
+        let element = match element.trim().as_bytes() {
+            &[c] if c.is_ascii_uppercase() => c as char,
+            _ => return Err(ParseError::at(contents, element, "expected an inserted element")),
+        };
+
+        polymerisation_ruleset.insert(pair.to_string(), element);
     }
 
-    (template, polymerisation_ruleset)
+    Ok((template, polymerisation_ruleset))
 }
 
 
@@ -158,34 +174,59 @@ fn parse_input(file_path: &str) -> (String, HashMap<String, char>) {
 mod tests {
     use super::*;
 
-    #[test]
-    fn solved() {
-        assert_eq!(2967, solve_first_star());
-        assert_eq!(3692219987038, solve_second_star());
-    }
-
     #[test]
     fn test_poylmerise_hash() {
-        let (template, rules) = parse_input("src/extended_polymerization/test_input.txt");
+        let (template, rules) = parse_input(include_str!("test_input.txt")).unwrap();
 
-        let polymer = init_polymer_hashmap(template);
+        let polymer = init_polymer_hashmap(&template);
 
         let polymer = hash_polymerise(polymer, &rules);
-        assert_eq!(init_polymer_hashmap("NCNBCHB".to_string()), polymer);
+        assert_eq!(init_polymer_hashmap("NCNBCHB"), polymer);
 
         let polymer = hash_polymerise(polymer, &rules);
-        assert_eq!(init_polymer_hashmap("NBCCNBBBCBHCB".to_string()), polymer);
+        assert_eq!(init_polymer_hashmap("NBCCNBBBCBHCB"), polymer);
 
         let polymer = hash_polymerise(polymer, &rules);
-        assert_eq!(init_polymer_hashmap("NBBBCNCCNBBNBNBBCHBHHBCHB".to_string()), polymer);
+        assert_eq!(init_polymer_hashmap("NBBBCNCCNBBNBNBBCHBHHBCHB"), polymer);
 
         let polymer = hash_polymerise(polymer, &rules);
-        assert_eq!(init_polymer_hashmap("NBBNBNBBCCNBCNCCNBBNBBNBBBNBBNBBCBHCBHHNHCBBCBHCB".to_string()), polymer);
+        assert_eq!(init_polymer_hashmap("NBBNBNBBCCNBCNCCNBBNBBNBBBNBBNBBCBHCBHHNHCBBCBHCB"), polymer);
+    }
+
+    #[test]
+    fn missing_rules() {
+        // without rules, the template is left as it is
+        let input = parse_input("NNC").unwrap();
+        assert_eq!(1, solve_first_star(&input));
+        assert_eq!(1, solve_second_star(&input));
+
+        let (template, rules) = parse_input("NNC\n\nNN -> C").unwrap();
+        let mut polymer = template.clone();
+        polymerise(&mut polymer, &rules);
+        assert_eq!("NCNC", polymer);
+        assert_eq!(
+            init_polymer_hashmap(&polymer),
+            hash_polymerise(init_polymer_hashmap(&template), &rules)
+        );
+    }
+
+    #[test]
+    fn parsing_errors() {
+        let error = parse_input("N\n\nNN -> C").unwrap_err();
+        assert_eq!("expected at least 2 elements", error.message);
+
+        let error = parse_input("NNCB\n\nNN -> C\nCBH -> B").unwrap_err();
+        assert_eq!((4, 1), (error.line, error.column));
+        assert_eq!("expected a pair of elements", error.message);
+
+        let error = parse_input("NNCB\n\nNN -> c").unwrap_err();
+        assert_eq!((3, 6), (error.line, error.column));
+        assert_eq!("expected an inserted element", error.message);
     }
 
     #[test]
     fn test_poylmerise() {
-        let (template, rules) = parse_input("src/extended_polymerization/test_input.txt");
+        let (template, rules) = parse_input(include_str!("test_input.txt")).unwrap();
 
         let mut polymer = template;
 
@@ -1,12 +1,55 @@
-use crate::giant_squid::solve::{solve_first_star, solve_second_star};
+use aoc_core::{Answer, ParseError, Solver};
+use crate::giant_squid::solve::{BingoBoard, parse_input, solve_first_star, solve_second_star};
+use crate::YEAR;
 
 mod solve;
 
-pub fn giant_squid_solution() {
-    println!("--- Day 4: Giant Squid ---");
-    let result = solve_first_star();
-    println!("\t1st Star Solution = {}", result);
+pub struct GiantSquid;
 
-    let result = solve_second_star();
-    println!("\t2nd Star Solution = {}", result);
-}
\ No newline at end of file
+impl Solver for GiantSquid {
+    type Input = (Vec<u32>, Vec<BingoBoard>);
+
+    fn year(&self) -> u32 {
+        YEAR
+    }
+
+    fn day(&self) -> u32 {
+        4
+    }
+
+    fn title(&self) -> &'static str {
+        "Giant Squid"
+    }
+
+    fn input_path(&self) -> &'static str {
+        concat!(env!("CARGO_MANIFEST_DIR"), "/src/giant_squid/input.txt")
+    }
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Result<Answer, String> {
+        Ok(solve_first_star(input).into())
+    }
+
+    fn part2(&self, input: &Self::Input) -> Result<Answer, String> {
+        Ok(solve_second_star(input).into())
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_core::verify;
+
+    #[test]
+    fn solved() {
+        verify::assert_solved(GiantSquid);
+    }
+
+    aoc_core::examples!(GiantSquid, {
+        example: "test_input.txt",
+    });
+}
@@ -1,12 +1,12 @@
 use std::collections::VecDeque;
-use std::fs;
+use aoc_core::parse::{self, ParseError};
 use ndarray::{Array2};
 
 
-pub fn solve_first_star() -> u32 {
-    let (bingo_results, mut bingo_boards) = parse_input("src/giant_squid/input.txt");
+pub fn solve_first_star((bingo_results, bingo_boards): &(Vec<u32>, Vec<BingoBoard>)) -> u32 {
+    let mut bingo_boards = bingo_boards.clone();
 
-    for bingo_result in bingo_results {
+    for &bingo_result in bingo_results {
 
         // score result across all boards
         for board in bingo_boards.iter_mut() {
@@ -29,14 +29,14 @@ pub fn solve_first_star() -> u32 {
 }
 
 
-pub fn solve_second_star() -> u32 {
-    let (bingo_results, mut bingo_boards) = parse_input("src/giant_squid/input.txt");
+pub fn solve_second_star((bingo_results, bingo_boards): &(Vec<u32>, Vec<BingoBoard>)) -> u32 {
+    let mut bingo_boards = bingo_boards.clone();
 
     let mut last_winner = 0;
 
     let mut purge_list:Vec<usize> = vec![];
 
-    for bingo_result in bingo_results {
+    for &bingo_result in bingo_results {
 
         // purge boards from last loop starting with highest index first
         for index in purge_list.iter().rev() {
@@ -101,14 +101,13 @@ fn check_win(bingo_board: &BingoBoard) -> bool {
 
 
 // Struct to manage Bingo Board data and marking
-struct BingoBoard {
+#[derive(Clone)]
+pub struct BingoBoard {
     data: Array2<u32>,
     marks: Array2<u32>,
 }
 
-fn parse_input(file_path: &str) -> (Vec<u32>, Vec<BingoBoard>) {
-    let contents = fs::read_to_string(file_path).expect("Input file local to project");
-
+pub fn parse_input(contents: &str) -> Result<(Vec<u32>, Vec<BingoBoard>), ParseError> {
 
     let bingo_data_vec = contents
         .lines()
@@ -118,16 +117,17 @@ fn parse_input(file_path: &str) -> (Vec<u32>, Vec<BingoBoard>) {
 
     // parse bingo results (first entry)
     let bingo_results = bingo_data_deque
-        .pop_front().unwrap()
+        .pop_front()
+        .ok_or_else(|| ParseError::end_of_input(contents, "missing bingo numbers"))?
         .split(',')
-        .map(|x| x.parse::<u32>().unwrap())
-        .collect();
+        .map(|x| parse::number(contents, x))
+        .collect::<Result<_, _>>()?;
 
     // parse bingo boards
     let mut bingo_boards = vec![];
 
     while let Some(data) = bingo_data_deque.pop_front() {
-        if data == "" {
+        if data.is_empty() {
             // New board. Lets set it up.
             let mut new_board = BingoBoard {
                 data: Array2::<u32>::zeros((5, 5)),
@@ -135,11 +135,19 @@ fn parse_input(file_path: &str) -> (Vec<u32>, Vec<BingoBoard>) {
             };
 
             for mut row in new_board.data.rows_mut() {
-                let row_data = bingo_data_deque
-                    .pop_front().unwrap()
+                let line = bingo_data_deque
+                    .pop_front()
+                    .ok_or_else(|| ParseError::end_of_input(contents, "incomplete bingo board"))?;
+
+                let row_data = line
                     .split_whitespace()
-                    .map(|x| x.parse::<u32>().unwrap())
-                    .collect::<Vec<_>>();
+                    .map(|x| parse::number(contents, x))
+                    .collect::<Result<Vec<_>, _>>()?;
+
+                if row_data.len() != 5 {
+                    let message = "expected 5 numbers in bingo board row";
+                    return Err(ParseError::at(contents, line, message));
+                }
 
                 row[0] = row_data[0];
                 row[1] = row_data[1];
@@ -153,17 +161,5 @@ fn parse_input(file_path: &str) -> (Vec<u32>, Vec<BingoBoard>) {
         }
     }
 
-    (bingo_results, bingo_boards)
+    Ok((bingo_results, bingo_boards))
 }
-
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn solved() {
-        assert_eq!(10374, solve_first_star());
-        assert_eq!(24742, solve_second_star());
-    }
-}
\ No newline at end of file
@@ -1,12 +1,55 @@
-use crate::hydrothermal_venture::solve::{solve_first_star, solve_second_star};
+use aoc_core::{Answer, ParseError, Solver};
+use crate::hydrothermal_venture::solve::{Line, parse_input, solve_first_star, solve_second_star};
+use crate::YEAR;
 
 mod solve;
 
-pub fn hydrothermal_venture_solution() {
-    println!("--- Day 5: Hydrothermal Venture ---");
-    let result = solve_first_star();
-    println!("\t1st Star Solution = {}", result);
+pub struct HydrothermalVenture;
 
-    let result = solve_second_star();
-    println!("\t2nd Star Solution = {}", result);
-}
\ No newline at end of file
+impl Solver for HydrothermalVenture {
+    type Input = Vec<Line>;
+
+    fn year(&self) -> u32 {
+        YEAR
+    }
+
+    fn day(&self) -> u32 {
+        5
+    }
+
+    fn title(&self) -> &'static str {
+        "Hydrothermal Venture"
+    }
+
+    fn input_path(&self) -> &'static str {
+        concat!(env!("CARGO_MANIFEST_DIR"), "/src/hydrothermal_venture/input.txt")
+    }
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Result<Answer, String> {
+        Ok(solve_first_star(input).into())
+    }
+
+    fn part2(&self, input: &Self::Input) -> Result<Answer, String> {
+        Ok(solve_second_star(input).into())
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_core::verify;
+
+    #[test]
+    fn solved() {
+        verify::assert_solved(HydrothermalVenture);
+    }
+
+    aoc_core::examples!(HydrothermalVenture, {
+        example: "test_input.txt",
+    });
+}
@@ -1,15 +1,10 @@
 use std::cmp::Ordering;
-use std::fs;
+use aoc_core::parse::{self, ParseError};
 use ndarray::{Array2};
 
 
-pub fn solve_first_star() -> u32 {
-    let lines = parse_input("src/hydrothermal_venture/input.txt");
-
-    let grid_x_dimension = 1000;
-    let grid_y_dimension = 1000;
-
-    let mut grid = Array2::<u32>::zeros((grid_x_dimension, grid_y_dimension));
+pub fn solve_first_star(lines: &[Line]) -> u32 {
+    let mut grid = Array2::<u32>::zeros(grid_dimensions(lines));
 
     let straight_lines = lines.iter().filter(|line| {
             line.p1.x == line.p2.x || line.p1.y == line.p2.y
@@ -29,13 +24,8 @@ pub fn solve_first_star() -> u32 {
 }
 
 
-pub fn solve_second_star() -> u32 {
-    let lines = parse_input("src/hydrothermal_venture/input.txt");
-
-    let grid_x_dimension = 1000;
-    let grid_y_dimension = 1000;
-
-    let mut grid = Array2::<u32>::zeros((grid_x_dimension, grid_y_dimension));
+pub fn solve_second_star(lines: &[Line]) -> u32 {
+    let mut grid = Array2::<u32>::zeros(grid_dimensions(lines));
 
     // Map lines
     for line in lines {
@@ -50,14 +40,22 @@ pub fn solve_second_star() -> u32 {
     dangerous_grid_points.sum()
 }
 
+/// Returns the grid size needed to plot every line.
+fn grid_dimensions(lines: &[Line]) -> (usize, usize) {
+    let x_max = lines.iter().map(|line| line.p1.x.max(line.p2.x)).max().unwrap_or(0);
+    let y_max = lines.iter().map(|line| line.p1.y.max(line.p2.y)).max().unwrap_or(0);
+
+    (x_max as usize + 1, y_max as usize + 1)
+}
+
 #[derive(Debug, Clone)]
-struct Point {
+pub struct Point {
     x: u32,
     y: u32,
 }
 
 #[derive(Debug, Clone)]
-struct Line {
+pub struct Line {
     p1: Point,
     p2: Point,
 }
@@ -99,51 +97,36 @@ impl Line {
 }
 
 
-fn parse_input(file_path: &str) -> Vec<Line> {
-    let contents = fs::read_to_string(file_path).expect("Input file local to project");
-
+pub fn parse_input(contents: &str) -> Result<Vec<Line>, ParseError> {
     let entries = contents
         .lines();
 
     let mut result = Vec::<Line>::new();
 
     for entry in entries {
-        let mut temp = Vec::<Point>::new();
-
-        for point in entry.split("->") {
-            let data = point
-                .trim()
-                .split(',')
-                .map(|x| x.parse::<u32>().unwrap())
-                .collect::<Vec<_>>();
-
-            let point = Point {
-                x: *data.first().unwrap(),
-                y: *data.last().unwrap(),
-            };
-
-            temp.push(point);
-        }
+        let (p1, p2) = entry
+            .split_once("->")
+            .ok_or_else(|| ParseError::at(contents, entry, "expected `x1,y1 -> x2,y2`"))?;
 
         let line = Line {
-            p1: temp.first().unwrap().clone(),
-            p2: temp.last().unwrap().clone(),
+            p1: parse_point(contents, p1)?,
+            p2: parse_point(contents, p2)?,
         };
 
         result.push(line);
     }
 
-    result
+    Ok(result)
 }
 
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+fn parse_point(contents: &str, point: &str) -> Result<Point, ParseError> {
+    let (x, y) = point
+        .split_once(',')
+        .ok_or_else(|| ParseError::at(contents, point, "expected `x,y`"))?;
 
-    #[test]
-    fn solved() {
-        assert_eq!(6710, solve_first_star());
-        assert_eq!(20121, solve_second_star());
-    }
-}
\ No newline at end of file
+    Ok(Point {
+        x: parse::number(contents, x)?,
+        y: parse::number(contents, y)?,
+    })
+}
@@ -1,7 +1,7 @@
-use std::fs;
+use aoc_core::parse::{self, ParseError};
 
-pub fn solve_first_star() -> u32 {
-    let mut population = parse_input("src/lanternfish/input.txt");
+pub fn solve_first_star(population: &[Lanternfish]) -> u32 {
+    let mut population = population.to_vec();
 
     simulate_population(&mut population, 80);
 
@@ -9,9 +9,7 @@ pub fn solve_first_star() -> u32 {
 }
 
 
-pub fn solve_second_star() -> u64 {
-    let population = parse_input("src/lanternfish/input.txt");
-
+pub fn solve_second_star(population: &[Lanternfish]) -> u64 {
     let result = simulate_population_optimised(population, 256);
 
     result.population_count()
@@ -30,7 +28,7 @@ fn simulate_population(population: &mut Vec<Lanternfish>, generations: u32) {
 }
 
 
-fn simulate_population_optimised(initial_population: Vec<Lanternfish>, generations: u32) -> LanternfishPopulation {
+fn simulate_population_optimised(initial_population: &[Lanternfish], generations: u32) -> LanternfishPopulation {
     let mut population = LanternfishPopulation::new(initial_population);
 
     for _ in 0..generations {
@@ -41,21 +39,16 @@ fn simulate_population_optimised(initial_population: Vec<Lanternfish>, generatio
 }
 
 
-fn parse_input(file_path: &str) -> Vec<Lanternfish> {
-    let contents = fs::read_to_string(file_path).expect("Input file local to project");
-
-    let mut population = Vec::<Lanternfish>::new();
-
+pub fn parse_input(contents: &str) -> Result<Vec<Lanternfish>, ParseError> {
     contents
         .trim()
         .split(',')
-        .map(|x| x.parse::<u32>().unwrap())
-        .for_each(|x| population.push(Lanternfish::new(x)));
-
-    population
+        .map(|x| parse::number(contents, x).map(Lanternfish::new))
+        .collect()
 }
 
-struct Lanternfish {
+#[derive(Clone)]
+pub struct Lanternfish {
     breeding_cycle: u32,
 }
 
@@ -127,7 +120,7 @@ impl LanternfishPopulation {
         count
     }
 
-    fn new(fish: Vec<Lanternfish>) -> LanternfishPopulation {
+    fn new(fish: &[Lanternfish]) -> LanternfishPopulation {
         let mut initial_pop = Vec::<(u64, u64)>::new();
 
         for cycle in 0..9 {
@@ -140,15 +133,3 @@ impl LanternfishPopulation {
         }
     }
 }
-
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn solved() {
-        assert_eq!(345387, solve_first_star());
-        assert_eq!(1574445493136, solve_second_star());
-    }
-}
\ No newline at end of file
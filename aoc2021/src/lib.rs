@@ -0,0 +1,60 @@
+use aoc_core::Registry;
+
+use crate::sonar_sweep::SonarSweep;
+use crate::dive::Dive;
+use crate::binary_diagnostic::BinaryDiagnostic;
+use crate::giant_squid::GiantSquid;
+use crate::hydrothermal_venture::HydrothermalVenture;
+use crate::lanternfish::Lanternfish;
+use crate::treachery_of_whales::TreacheryOfWhales;
+use crate::seven_segment_search::SevenSegmentSearch;
+use crate::smoke_basin::SmokeBasin;
+use crate::syntax_scoring::SyntaxScoring;
+use crate::dumbo_octopus::DumboOctopus;
+use crate::passage_pathing::PassagePathing;
+use crate::transparent_origami::TransparentOrigami;
+use crate::extended_polymerization::ExtendedPolymerization;
+use crate::chiton::Chiton;
+use crate::packet_decoder::PacketDecoder;
+use crate::trick_shot::TrickShot;
+
+mod sonar_sweep;
+mod dive;
+mod binary_diagnostic;
+mod giant_squid;
+mod hydrothermal_venture;
+mod lanternfish;
+mod treachery_of_whales;
+mod seven_segment_search;
+mod smoke_basin;
+mod syntax_scoring;
+mod dumbo_octopus;
+mod passage_pathing;
+mod transparent_origami;
+mod extended_polymerization;
+mod chiton;
+mod packet_decoder;
+mod trick_shot;
+
+pub const YEAR: u32 = 2021;
+
+/// Registers every solved 2021 puzzle with the registry.
+pub fn register(registry: &mut Registry) {
+    registry.register(SonarSweep);
+    registry.register(Dive);
+    registry.register(BinaryDiagnostic);
+    registry.register(GiantSquid);
+    registry.register(HydrothermalVenture);
+    registry.register(Lanternfish);
+    registry.register(TreacheryOfWhales);
+    registry.register(SevenSegmentSearch);
+    registry.register(SmokeBasin);
+    registry.register(SyntaxScoring);
+    registry.register(DumboOctopus);
+    registry.register(PassagePathing::default());
+    registry.register(TransparentOrigami);
+    registry.register(ExtendedPolymerization);
+    registry.register(Chiton::default());
+    registry.register(PacketDecoder::default());
+    registry.register(TrickShot);
+}
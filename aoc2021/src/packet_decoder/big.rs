@@ -0,0 +1,242 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::{Add, Mul};
+
+
+/// An unsigned integer of any size, with just the arithmetic needed to evaluate packets.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BigUint {
+    /// Base 2^32 digits, least significant first, without leading zeros so that zero is empty.
+    limbs: Vec<u32>,
+}
+
+
+impl BigUint {
+    fn normalised(mut self) -> Self {
+        while self.limbs.last() == Some(&0) {
+            self.limbs.pop();
+        }
+        self
+    }
+
+    /// Number of groups of 4 bits needed to write the number, none for zero.
+    pub fn nibbles(&self) -> usize {
+        match self.limbs.last() {
+            Some(last) => self.limbs.len() * 8 - last.leading_zeros() as usize / 4,
+            None => 0,
+        }
+    }
+
+    /// The `i`th group of 4 bits, counting from the least significant.
+    pub fn nibble(&self, i: usize) -> u64 {
+        self.limbs.get(i / 8).map_or(0, |&limb| (limb >> (i % 8 * 4)) as u64 & 0xF)
+    }
+
+    /// Shifts the number 4 bits left and appends the low 4 bits of `nibble`.
+    pub fn push_nibble(&mut self, nibble: u64) {
+        let mut carry = nibble as u32 & 0xF;
+
+        for limb in self.limbs.iter_mut() {
+            let next = *limb >> 28;
+            *limb = *limb << 4 | carry;
+            carry = next;
+        }
+
+        if carry > 0 {
+            self.limbs.push(carry);
+        }
+    }
+
+    pub fn to_u64(&self) -> Option<u64> {
+        match self.limbs[..] {
+            [] => Some(0),
+            [low] => Some(low as u64),
+            [low, high] => Some((high as u64) << 32 | low as u64),
+            _ => None,
+        }
+    }
+
+    /// Divides by a small divisor, returning the quotient and remainder.
+    fn div_rem(&self, divisor: u32) -> (BigUint, u32) {
+        let mut quotient = vec![0; self.limbs.len()];
+        let mut remainder = 0u64;
+
+        for (i, &limb) in self.limbs.iter().enumerate().rev() {
+            let dividend = remainder << 32 | limb as u64;
+            quotient[i] = (dividend / divisor as u64) as u32;
+            remainder = dividend % divisor as u64;
+        }
+
+        (BigUint { limbs: quotient }.normalised(), remainder as u32)
+    }
+}
+
+
+impl From<u64> for BigUint {
+    fn from(value: u64) -> Self {
+        BigUint { limbs: vec![value as u32, (value >> 32) as u32] }.normalised()
+    }
+}
+
+
+impl Add for &BigUint {
+    type Output = BigUint;
+
+    fn add(self, other: &BigUint) -> BigUint {
+        let mut limbs = Vec::with_capacity(self.limbs.len().max(other.limbs.len()) + 1);
+        let mut carry = 0u64;
+
+        for i in 0..self.limbs.len().max(other.limbs.len()) {
+            let a = self.limbs.get(i).copied().unwrap_or(0) as u64;
+            let b = other.limbs.get(i).copied().unwrap_or(0) as u64;
+            let sum = a + b + carry;
+            limbs.push(sum as u32);
+            carry = sum >> 32;
+        }
+        limbs.push(carry as u32);
+
+        BigUint { limbs }.normalised()
+    }
+}
+
+
+impl Mul for &BigUint {
+    type Output = BigUint;
+
+    fn mul(self, other: &BigUint) -> BigUint {
+        let mut limbs = vec![0u32; self.limbs.len() + other.limbs.len()];
+
+        // long multiplication, adding each partial product in place
+        for (i, &a) in self.limbs.iter().enumerate() {
+            let mut carry = 0u64;
+
+            for (j, &b) in other.limbs.iter().enumerate() {
+                let product = a as u64 * b as u64 + limbs[i + j] as u64 + carry;
+                limbs[i + j] = product as u32;
+                carry = product >> 32;
+            }
+            limbs[i + other.limbs.len()] = carry as u32;
+        }
+
+        BigUint { limbs }.normalised()
+    }
+}
+
+
+impl Ord for BigUint {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // without leading zeros, a longer number is a bigger one
+        self.limbs
+            .len()
+            .cmp(&other.limbs.len())
+            .then_with(|| self.limbs.iter().rev().cmp(other.limbs.iter().rev()))
+    }
+}
+
+
+impl PartialOrd for BigUint {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+
+impl fmt::Display for BigUint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // split into groups of 9 decimal digits, least significant first
+        let mut groups = Vec::new();
+        let mut rest = self.clone();
+
+        loop {
+            let (quotient, remainder) = rest.div_rem(1_000_000_000);
+            groups.push(remainder);
+            rest = quotient;
+
+            if rest.limbs.is_empty() {
+                break;
+            }
+        }
+
+        let mut groups = groups.iter().rev();
+        write!(f, "{}", groups.next().unwrap())?;
+        for group in groups {
+            write!(f, "{:09}", group)?;
+        }
+
+        Ok(())
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+
+    fn big(limbs: &[u32]) -> BigUint {
+        BigUint { limbs: limbs.to_vec() }
+    }
+
+
+    #[test]
+    fn carries() {
+        assert_eq!(big(&[0, 1]), &big(&[u32::MAX]) + &BigUint::from(1));
+        assert_eq!(big(&[0, 0, 1]), &big(&[u32::MAX, u32::MAX]) + &BigUint::from(1));
+        assert_eq!(big(&[1, 0, 1]), &big(&[1]) + &big(&[0, 0, 1]));
+        assert_eq!(big(&[7]), &BigUint::default() + &big(&[7]));
+
+        assert_eq!(big(&[1, u32::MAX - 1]), &big(&[u32::MAX]) * &big(&[u32::MAX]));
+        let max = BigUint::from(u64::MAX);
+        assert_eq!(big(&[1, 0, u32::MAX - 1, u32::MAX]), &max * &max);
+        assert_eq!(BigUint::default(), &big(&[5, 6]) * &BigUint::default());
+        assert_eq!(BigUint::default(), &BigUint::default() * &big(&[5, 6]));
+    }
+
+
+    #[test]
+    fn comparisons() {
+        // longer numbers are bigger, whatever their digits
+        assert!(big(&[0, 1]) > big(&[u32::MAX]));
+        assert!(BigUint::default() < big(&[1]));
+
+        // the same length compares from the most significant digit
+        assert!(big(&[u32::MAX, 1]) < big(&[0, 2]));
+        assert!(big(&[1, 2]) < big(&[2, 2]));
+        assert_eq!(Ordering::Equal, big(&[1, 2]).cmp(&BigUint::from(1 << 33 | 1)));
+        assert_eq!(Some(&BigUint::from(2)), [5, 2, 9].map(BigUint::from).iter().min());
+    }
+
+
+    #[test]
+    fn conversions() {
+        assert_eq!(BigUint::default(), BigUint::from(0));
+        assert_eq!(Some(0), BigUint::default().to_u64());
+        assert_eq!(Some(u64::MAX), BigUint::from(u64::MAX).to_u64());
+        assert_eq!(None, big(&[0, 0, 1]).to_u64());
+
+        assert_eq!("0", BigUint::default().to_string());
+        assert_eq!("18446744073709551616", big(&[0, 0, 1]).to_string());
+        // groups after the first keep their leading zeros
+        assert_eq!("1000000000", BigUint::from(1_000_000_000).to_string());
+        assert_eq!("1000000000000000007", BigUint::from(1_000_000_000_000_000_007).to_string());
+    }
+
+
+    #[test]
+    fn nibbles() {
+        let mut n = BigUint::default();
+        for nibble in [0, 0xA, 0xB, 0xC, 0xD, 0xE, 0xF, 0x1, 0x2, 0x3] {
+            n.push_nibble(nibble);
+        }
+
+        assert_eq!(9, n.nibbles());
+        assert_eq!(Some(0xA_BCDE_F123), n.to_u64());
+        let nibbles = (0..10).map(|i| n.nibble(i)).collect::<Vec<_>>();
+        assert_eq!(vec![3, 2, 1, 0xF, 0xE, 0xD, 0xC, 0xB, 0xA, 0], nibbles);
+
+        assert_eq!(0, BigUint::default().nibbles());
+        assert_eq!(1, BigUint::from(1).nibbles());
+        assert_eq!(8, BigUint::from(u32::MAX as u64).nibbles());
+        assert_eq!(9, BigUint::from(1 << 32).nibbles());
+    }
+}
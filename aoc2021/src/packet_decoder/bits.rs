@@ -0,0 +1,194 @@
+/// Reads a transmission bit by bit, most significant bit first.
+#[derive(Debug, Clone)]
+pub struct BitReader<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+
+impl<'a> BitReader<'a> {
+    pub fn new(bytes: &'a [u8]) -> Self {
+        BitReader { bytes, position: 0 }
+    }
+
+    /// Number of bits read so far.
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Number of bits left to read.
+    pub fn remaining(&self) -> usize {
+        self.bytes.len() * 8 - self.position
+    }
+
+    /// Whether every bit left to read is zero, as in the padding at the end of a transmission.
+    pub fn only_zeros_left(&self) -> bool {
+        let mut rest = self.clone();
+
+        while let Some(bit) = rest.read_bit() {
+            if bit {
+                return false;
+            }
+        }
+
+        true
+    }
+
+    /// Reads the next `n` bits, at most 64, as a number. Returns `None`, reading nothing, when
+    /// fewer than `n` bits remain.
+    pub fn read_bits(&mut self, n: usize) -> Option<u64> {
+        assert!(n <= 64, "can't read {} bits into a u64", n);
+
+        if n > self.remaining() {
+            return None;
+        }
+
+        let mut value = 0u64;
+        let mut left = n;
+
+        while left > 0 {
+            // take as many bits as possible from the current byte
+            let offset = self.position % 8;
+            let take = left.min(8 - offset);
+            let byte = self.bytes[self.position / 8] as u64;
+            let bits = (byte >> (8 - offset - take)) & ((1 << take) - 1);
+
+            value = (value << take) | bits;
+            self.position += take;
+            left -= take;
+        }
+
+        Some(value)
+    }
+
+    pub fn read_bit(&mut self) -> Option<bool> {
+        self.read_bits(1).map(|bit| bit == 1)
+    }
+}
+
+
+/// Writes a transmission bit by bit, most significant bit first, padding the last byte with zeros.
+#[derive(Debug, Clone, Default)]
+pub struct BitWriter {
+    bytes: Vec<u8>,
+    len: usize,
+}
+
+
+impl BitWriter {
+    pub fn new() -> Self {
+        BitWriter::default()
+    }
+
+    /// Number of bits written so far.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Writes the low `n` bits of `value`, at most 64.
+    pub fn write_bits(&mut self, value: u64, n: usize) {
+        assert!(n <= 64, "can't write {} bits from a u64", n);
+
+        for i in (0..n).rev() {
+            self.write_bit(value >> i & 1 == 1);
+        }
+    }
+
+    pub fn write_bit(&mut self, bit: bool) {
+        if self.len.is_multiple_of(8) {
+            self.bytes.push(0);
+        }
+
+        if bit {
+            self.bytes[self.len / 8] |= 0x80 >> (self.len % 8);
+        }
+
+        self.len += 1;
+    }
+
+    /// Writes every bit written to `other`, leaving out its padding.
+    pub fn append(&mut self, other: &BitWriter) {
+        let mut reader = BitReader::new(&other.bytes);
+
+        for _ in 0..other.len {
+            self.write_bit(reader.read_bit().unwrap());
+        }
+    }
+
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.bytes
+    }
+}
+
+
+/// Packs hexadecimal digits into bytes, two digits per byte. An odd digit out fills the high half
+/// of the last byte.
+pub fn hex_to_bytes(hex: &str) -> Vec<u8> {
+    let digits = hex.chars().filter_map(|c| c.to_digit(16)).map(|d| d as u8).collect::<Vec<_>>();
+
+    digits
+        .chunks(2)
+        .map(|pair| pair[0] << 4 | pair.get(1).copied().unwrap_or(0))
+        .collect()
+}
+
+
+/// Formats bytes as upper case hexadecimal digits, two digits per byte.
+pub fn bytes_to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02X}", byte)).collect()
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+
+    #[test]
+    fn reading_bits() {
+        let bytes = hex_to_bytes("D2FE28");
+        assert_eq!(vec![0xD2, 0xFE, 0x28], bytes);
+        assert_eq!(vec![0xAB, 0xC0], hex_to_bytes("ABC"));
+
+        let mut reader = BitReader::new(&bytes);
+        assert_eq!(Some(6), reader.read_bits(3));
+        assert_eq!(Some(4), reader.read_bits(3));
+        assert_eq!(Some(true), reader.read_bit());
+        assert_eq!(Some(0b0111_1111_0001), reader.read_bits(12));
+        assert_eq!((19, 5), (reader.position(), reader.remaining()));
+        assert!(!reader.only_zeros_left());
+
+        assert_eq!(None, reader.read_bits(6));
+        assert_eq!(19, reader.position());
+        assert_eq!(Some(0b01000), reader.read_bits(5));
+        assert_eq!(None, reader.read_bit());
+        assert!(reader.only_zeros_left());
+
+        let bytes = [0xFF; 9];
+        let mut reader = BitReader::new(&bytes);
+        assert_eq!(Some(1), reader.read_bits(1));
+        assert_eq!(Some(u64::MAX), reader.read_bits(64));
+        assert_eq!(Some(0), reader.read_bits(0));
+    }
+
+
+    #[test]
+    fn writing_bits() {
+        let mut writer = BitWriter::new();
+        writer.write_bits(6, 3);
+        writer.write_bits(4, 3);
+        writer.write_bit(true);
+
+        let mut literal = BitWriter::new();
+        literal.write_bits(0b0111_1111_0001, 12);
+        literal.write_bits(0b01000, 5);
+        writer.append(&literal);
+        assert_eq!(24, writer.len());
+
+        assert_eq!("D2FE28", bytes_to_hex(&writer.into_bytes()));
+
+        let mut writer = BitWriter::new();
+        writer.write_bits(0b101, 3);
+        assert_eq!(vec![0b1010_0000], writer.into_bytes());
+    }
+}
@@ -0,0 +1,225 @@
+use crate::packet_decoder::big::BigUint;
+use crate::packet_decoder::bits::{bytes_to_hex, BitWriter};
+use crate::packet_decoder::packet::{Length, Packet, LITERAL_TYPE_ID};
+
+
+/// Largest total length of sub packets in bits, for length type 0.
+const MAX_BITS: usize = (1 << 15) - 1;
+
+/// Largest number of sub packets, for length type 1.
+const MAX_COUNT: usize = (1 << 11) - 1;
+
+
+/// Encodes a packet as a hexadecimal transmission. Each operator packet is written with the length
+/// type it records, and literals use as few groups as their value needs.
+pub fn encode(packet: &Packet) -> Result<String, String> {
+    let mut writer = BitWriter::new();
+    encode_packet(&mut writer, packet)?;
+
+    Ok(bytes_to_hex(&writer.into_bytes()))
+}
+
+
+fn encode_packet(writer: &mut BitWriter, packet: &Packet) -> Result<(), String> {
+    if packet.version() > 7 {
+        return Err(format!("version {} doesn't fit in 3 bits", packet.version()));
+    }
+    writer.write_bits(packet.version() as u64, 3);
+
+    match packet {
+        Packet::Literal { value, .. } => {
+            writer.write_bits(LITERAL_TYPE_ID as u64, 3);
+            encode_literal(writer, value);
+        }
+        Packet::Operator { operator, length, children, .. } => {
+            writer.write_bits(operator.type_id() as u64, 3);
+
+            // encode the sub packets first, the total length comes before them
+            let mut sub_packets = BitWriter::new();
+            for child in children {
+                encode_packet(&mut sub_packets, child)?;
+            }
+
+            match length {
+                Length::Bits if sub_packets.len() > MAX_BITS => {
+                    let bits = sub_packets.len();
+                    return Err(format!("{} bits of sub packets don't fit in 15 bits", bits));
+                }
+                Length::Bits => {
+                    writer.write_bit(false);
+                    writer.write_bits(sub_packets.len() as u64, 15);
+                }
+                Length::Count if children.len() > MAX_COUNT => {
+                    return Err(format!("{} sub packets don't fit in 11 bits", children.len()));
+                }
+                Length::Count => {
+                    writer.write_bit(true);
+                    writer.write_bits(children.len() as u64, 11);
+                }
+            }
+
+            writer.append(&sub_packets);
+        }
+    }
+
+    Ok(())
+}
+
+
+/// Writes the value in groups of 4 bits, each preceded by a bit telling whether another follows.
+fn encode_literal(writer: &mut BitWriter, value: &BigUint) {
+    let groups = value.nibbles();
+
+    for group in (0..groups.max(1)).rev() {
+        writer.write_bit(group > 0);
+        writer.write_bits(value.nibble(group), 4);
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::packet_decoder::packet::{EvalError, EvalProblem, Operator};
+    use crate::packet_decoder::solve::parse_input;
+
+
+    /// Xorshift generator, to build the same random packets on every run.
+    struct Xorshift(u64);
+
+
+    impl Xorshift {
+        fn next(&mut self) -> u64 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            self.0
+        }
+
+        fn below(&mut self, n: u64) -> u64 {
+            self.next() % n
+        }
+    }
+
+
+    /// Builds a random packet at most `depth` operators deep whose value fits in `bits` bits, so
+    /// that evaluating it in 64 bits never overflows when `bits` is 64.
+    fn random_packet(rng: &mut Xorshift, depth: u32, bits: u32) -> Packet {
+        let version = rng.below(8) as u8;
+
+        if depth == 0 || rng.below(3) == 0 {
+            let mut value = BigUint::default();
+            for _ in 0..rng.below(bits as u64 / 4 + 1) {
+                value.push_nibble(rng.below(16));
+            }
+            return Packet::Literal { version, value };
+        }
+
+        let type_id = [0, 1, 2, 3, 5, 6, 7][rng.below(7) as usize];
+        let operator = Operator::from_type_id(type_id).unwrap();
+
+        // split the bits of the value between the sub packets where they add up
+        let (count, child_bits) = match operator {
+            Operator::Sum => (1 + rng.below(4), bits.saturating_sub(2)),
+            Operator::Product => {
+                let count = 1 + rng.below(3);
+                (count, bits / count as u32)
+            }
+            Operator::Minimum | Operator::Maximum => (1 + rng.below(4), bits),
+            Operator::GreaterThan | Operator::LessThan | Operator::EqualTo => (2, bits),
+        };
+
+        let children = (0..count).map(|_| random_packet(rng, depth - 1, child_bits)).collect();
+        let length = if rng.below(2) == 0 { Length::Bits } else { Length::Count };
+
+        Packet::Operator { version, operator, length, children }
+    }
+
+
+    fn assert_round_trip(packet: &Packet) {
+        let transmission = encode(packet).unwrap();
+        let decoded = parse_input(&transmission, true).unwrap();
+
+        assert_eq!(vec![packet.clone()], decoded, "decoding {}", transmission);
+        assert_eq!(packet.value(), decoded[0].value());
+        assert_eq!(packet.big_value(), decoded[0].big_value());
+        assert_eq!(packet.version_sum(), decoded[0].version_sum());
+    }
+
+
+    #[test]
+    fn encoding_examples() {
+        assert_eq!("D2FE28", encode(&Packet::Literal { version: 6, value: 2021.into() }).unwrap());
+
+        for transmission in ["38006F45291200", "EE00D40C823060", "9C0141080250320F1802104A08"] {
+            let packet = parse_input(transmission, true).unwrap().remove(0);
+            assert_eq!(transmission, encode(&packet).unwrap());
+        }
+
+        // the other length type, then a sum of no sub packets
+        let packet = parse_input("EE00D40C823060", true).unwrap().remove(0);
+        let Packet::Operator { version, operator, children, .. } = packet else { unreachable!() };
+        assert_round_trip(&Packet::Operator { version, operator, length: Length::Bits, children });
+
+        let (operator, children) = (Operator::Sum, vec![]);
+        assert_round_trip(&Packet::Operator { version, operator, length: Length::Count, children });
+    }
+
+
+    #[test]
+    fn encoding_limits() {
+        assert_round_trip(&Packet::Literal { version: 7, value: 0.into() });
+        assert_round_trip(&Packet::Literal { version: 0, value: u64::MAX.into() });
+        let wide = &BigUint::from(u64::MAX) * &BigUint::from(u64::MAX);
+        assert_round_trip(&Packet::Literal { version: 0, value: wide });
+
+        assert!(encode(&Packet::Literal { version: 8, value: 1.into() }).is_err());
+
+        let children = vec![Packet::Literal { version: 0, value: 1.into() }; MAX_COUNT + 1];
+        let packet = Packet::Operator {
+            version: 0,
+            operator: Operator::Sum,
+            length: Length::Count,
+            children: children.clone(),
+        };
+        assert!(encode(&packet).is_err());
+
+        let packet = Packet::Operator {
+            version: 0,
+            operator: Operator::Sum,
+            length: Length::Bits,
+            children,
+        };
+        assert!(encode(&packet).is_ok());
+    }
+
+
+    #[test]
+    fn random_round_trips() {
+        let mut rng = Xorshift(0x2021_1216_BEEF_CAFE);
+
+        for _ in 0..500 {
+            let packet = random_packet(&mut rng, 5, 64);
+            assert_round_trip(&packet);
+            assert_eq!(packet.value().ok(), packet.big_value().unwrap().to_u64());
+        }
+
+        // values too big for 64 bits, which must fail to evaluate unless they can be worked out
+        // without an overflow
+        let mut overflows = 0;
+        for _ in 0..500 {
+            let packet = random_packet(&mut rng, 5, 256);
+            assert_round_trip(&packet);
+
+            let big = packet.big_value().unwrap();
+            match packet.value() {
+                Ok(value) => assert_eq!(Some(value), big.to_u64()),
+                Err(EvalError { problem, .. }) => {
+                    assert!(matches!(problem, EvalProblem::Literal | EvalProblem::Overflow(_)));
+                    overflows += 1;
+                }
+            }
+        }
+        assert!(overflows > 0);
+    }
+}
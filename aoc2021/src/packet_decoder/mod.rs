@@ -1,12 +1,119 @@
-use crate::packet_decoder::solve::{solve_first_star, solve_second_star};
+use aoc_core::{Answer, Options, ParseError, Solver};
+use crate::packet_decoder::packet::{Packet, Rendering};
+use crate::packet_decoder::solve::{
+    parse_input, solve_first_star, solve_second_star, solve_second_star_big,
+};
+use crate::YEAR;
 
+mod big;
+mod bits;
+mod encode;
+mod packet;
 mod solve;
 
-pub fn packet_decoder_solution() {
-    println!("--- Day 16: Packet Decoder ---");
-    let result = solve_first_star();
-    println!("\t1st Star Solution = {}", result);
+/// The packet decoder, which prints the decoded packets to stderr when solving the second star if
+/// `show` is set to `tree`, `sexpr`, `infix` or `hex`, to see how the value was produced. With
+/// `strict`, malformed transmissions that the puzzle's own inputs never contain are rejected too,
+/// and with `big` the second star is evaluated however big the values get instead of failing when
+/// they don't fit in 64 bits.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PacketDecoder {
+    pub show: Option<Rendering>,
+    pub strict: bool,
+    pub big: bool,
+}
 
-    let result = solve_second_star();
-    println!("\t2nd Star Solution = {}", result);
-}
\ No newline at end of file
+impl Solver for PacketDecoder {
+    type Input = Vec<Packet>;
+
+    fn year(&self) -> u32 {
+        YEAR
+    }
+
+    fn day(&self) -> u32 {
+        16
+    }
+
+    fn title(&self) -> &'static str {
+        "Packet Decoder"
+    }
+
+    fn input_path(&self) -> &'static str {
+        concat!(env!("CARGO_MANIFEST_DIR"), "/src/packet_decoder/input.txt")
+    }
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input, self.strict)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Result<Answer, String> {
+        Ok(solve_first_star(input).into())
+    }
+
+    fn part2(&self, input: &Self::Input) -> Result<Answer, String> {
+        if let Some(rendering) = self.show {
+            for packet in input {
+                eprintln!("{}", packet.render(rendering).trim_end());
+            }
+        }
+
+        let answer = if self.big {
+            solve_second_star_big(input)
+                .map(|value| value.to_u64().map_or_else(|| value.to_string().into(), Answer::from))
+        } else {
+            solve_second_star(input).map(Answer::from)
+        };
+
+        answer.map_err(|err| format!("couldn't evaluate the transmission, {}", err))
+    }
+
+    fn configure(&self, options: &Options) -> Result<Self, String> {
+        options.expect(&["show", "strict", "big"])?;
+
+        Ok(PacketDecoder {
+            show: options.get("show")?.or(self.show),
+            strict: options.get("strict")?.unwrap_or(self.strict),
+            big: options.get("big")?.unwrap_or(self.big),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_core::verify;
+
+    #[test]
+    fn solved() {
+        verify::assert_solved(PacketDecoder::default());
+    }
+
+    #[test]
+    fn overflowing_transmission() {
+        let half = Packet::Literal { version: 0, value: (1 << 63).into() };
+        let packets = vec![half.clone(), half];
+
+        let error = PacketDecoder::default().part2(&packets).unwrap_err();
+        assert_eq!(
+            "couldn't evaluate the transmission, the sum of the packets overflows 64 bits",
+            error
+        );
+        let big = PacketDecoder { big: true, ..PacketDecoder::default() };
+        assert_eq!(Ok(Answer::from("18446744073709551616")), big.part2(&packets));
+    }
+
+    aoc_core::examples!(PacketDecoder::default(), {
+        example: "test_input.txt",
+        example2: "test_input2.txt",
+        example3: "test_input3.txt",
+        example4: "test_input4.txt",
+        example5: "test_input5.txt",
+        example6: "test_input6.txt",
+        example7: "test_input7.txt",
+        example8: "test_input8.txt",
+        example9: "test_input9.txt",
+        example10: "test_input10.txt",
+        example11: "test_input11.txt",
+        example12: "test_input12.txt",
+    });
+}
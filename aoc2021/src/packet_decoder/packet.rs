@@ -0,0 +1,469 @@
+use std::fmt;
+use std::str::FromStr;
+use crate::packet_decoder::big::BigUint;
+use crate::packet_decoder::encode::encode;
+
+
+/// A decoded BITS packet.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Packet {
+    Literal {
+        version: u8,
+        value: BigUint,
+    },
+    Operator {
+        version: u8,
+        operator: Operator,
+        length: Length,
+        children: Vec<Packet>,
+    },
+}
+
+
+/// The operation of an operator packet, given by its type ID.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operator {
+    Sum,
+    Product,
+    Minimum,
+    Maximum,
+    GreaterThan,
+    LessThan,
+    EqualTo,
+}
+
+
+/// How an operator packet records the extent of its sub packets.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Length {
+    /// Length type 0, the total number of bits of the sub packets in 15 bits.
+    Bits,
+    /// Length type 1, the number of sub packets in 11 bits.
+    Count,
+}
+
+
+/// A packet whose value can't be worked out.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EvalError {
+    /// Indices of the sub packets leading to the packet from the one evaluated, after the index of
+    /// that packet in the transmission when evaluating a whole transmission.
+    pub path: Vec<usize>,
+    pub problem: EvalProblem,
+}
+
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EvalProblem {
+    /// A literal too big for 64 bit arithmetic.
+    Literal,
+    /// An operation whose result is too big for 64 bit arithmetic.
+    Overflow(Operator),
+    /// An operator without the operands its value needs.
+    Operands(String),
+    /// The sum of the values of a transmission's packets is too big for 64 bit arithmetic.
+    Total,
+}
+
+
+/// Arithmetic that packets are evaluated in, where results may not fit.
+trait Arithmetic: Ord + Sized {
+    fn literal(value: &BigUint) -> Option<Self>;
+    fn from_bool(value: bool) -> Self;
+    fn add(&self, other: &Self) -> Option<Self>;
+    fn mul(&self, other: &Self) -> Option<Self>;
+}
+
+
+/// A way of showing a packet when debugging a transmission.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rendering {
+    Tree,
+    Expression,
+    Infix,
+    /// The packet encoded again, with the same length types.
+    Hex,
+}
+
+
+/// Type ID of literal packets.
+pub const LITERAL_TYPE_ID: u8 = 4;
+
+
+impl Operator {
+    pub fn from_type_id(type_id: u8) -> Option<Operator> {
+        match type_id {
+            0 => Some(Operator::Sum),
+            1 => Some(Operator::Product),
+            2 => Some(Operator::Minimum),
+            3 => Some(Operator::Maximum),
+            5 => Some(Operator::GreaterThan),
+            6 => Some(Operator::LessThan),
+            7 => Some(Operator::EqualTo),
+            _ => None,
+        }
+    }
+
+    pub fn type_id(self) -> u8 {
+        match self {
+            Operator::Sum => 0,
+            Operator::Product => 1,
+            Operator::Minimum => 2,
+            Operator::Maximum => 3,
+            Operator::GreaterThan => 5,
+            Operator::LessThan => 6,
+            Operator::EqualTo => 7,
+        }
+    }
+
+    /// Name of the operator in S-expressions.
+    pub fn name(self) -> &'static str {
+        match self {
+            Operator::Sum => "sum",
+            Operator::Product => "product",
+            Operator::Minimum => "min",
+            Operator::Maximum => "max",
+            Operator::GreaterThan => "gt",
+            Operator::LessThan => "lt",
+            Operator::EqualTo => "eq",
+        }
+    }
+
+    /// Describes what's wrong when the operator can't take `count` operands.
+    pub fn operand_error(self, count: usize) -> Option<String> {
+        match self {
+            Operator::Minimum | Operator::Maximum if count == 0 => {
+                Some(format!("{} needs at least one sub packet", self.name()))
+            }
+            Operator::GreaterThan | Operator::LessThan | Operator::EqualTo if count != 2 => Some(
+                format!("comparison {} needs exactly two sub packets, found {}", self.name(), count)
+            ),
+            _ => None,
+        }
+    }
+
+    /// Applies the operator to the values of the sub packets, which must be as many as it needs.
+    /// Returns `None` when the result doesn't fit.
+    fn apply<A: Arithmetic>(self, values: Vec<A>) -> Option<A> {
+        let mut values = values.into_iter();
+
+        match self {
+            Operator::Sum => values.try_fold(A::from_bool(false), |total, value| total.add(&value)),
+            Operator::Product => {
+                values.try_fold(A::from_bool(true), |total, value| total.mul(&value))
+            }
+            Operator::Minimum => values.min(),
+            Operator::Maximum => values.max(),
+            Operator::GreaterThan => Some(A::from_bool(values.next() > values.next())),
+            Operator::LessThan => Some(A::from_bool(values.next() < values.next())),
+            Operator::EqualTo => Some(A::from_bool(values.next() == values.next())),
+        }
+    }
+}
+
+
+impl Arithmetic for u64 {
+    fn literal(value: &BigUint) -> Option<Self> {
+        value.to_u64()
+    }
+
+    fn from_bool(value: bool) -> Self {
+        value as u64
+    }
+
+    fn add(&self, other: &Self) -> Option<Self> {
+        self.checked_add(*other)
+    }
+
+    fn mul(&self, other: &Self) -> Option<Self> {
+        self.checked_mul(*other)
+    }
+}
+
+
+impl Arithmetic for BigUint {
+    fn literal(value: &BigUint) -> Option<Self> {
+        Some(value.clone())
+    }
+
+    fn from_bool(value: bool) -> Self {
+        BigUint::from(value as u64)
+    }
+
+    fn add(&self, other: &Self) -> Option<Self> {
+        Some(self + other)
+    }
+
+    fn mul(&self, other: &Self) -> Option<Self> {
+        Some(self * other)
+    }
+}
+
+
+impl EvalError {
+    /// The error as found in the packet at `index` of a transmission.
+    pub fn within(mut self, index: usize) -> Self {
+        self.path.insert(0, index);
+        self
+    }
+}
+
+
+impl fmt::Display for EvalError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let packet = match self.path.is_empty() {
+            true => "top packet".to_string(),
+            false => {
+                let path = self.path.iter().map(usize::to_string).collect::<Vec<_>>();
+                format!("packet {}", path.join("."))
+            }
+        };
+
+        match &self.problem {
+            EvalProblem::Literal => write!(f, "{}: literal doesn't fit in 64 bits", packet),
+            EvalProblem::Overflow(operator) => {
+                write!(f, "{}: {} overflows 64 bits", packet, operator.name())
+            }
+            EvalProblem::Operands(message) => write!(f, "{}: {}", packet, message),
+            EvalProblem::Total => write!(f, "the sum of the packets overflows 64 bits"),
+        }
+    }
+}
+
+
+impl FromStr for Rendering {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "tree" => Ok(Rendering::Tree),
+            "sexpr" => Ok(Rendering::Expression),
+            "infix" => Ok(Rendering::Infix),
+            "hex" => Ok(Rendering::Hex),
+            _ => Err(format!("unknown rendering `{}`, expected tree, sexpr, infix or hex", s)),
+        }
+    }
+}
+
+
+impl Packet {
+    pub fn version(&self) -> u8 {
+        match self {
+            Packet::Literal { version, .. } | Packet::Operator { version, .. } => *version,
+        }
+    }
+
+    /// The sub packets, none for a literal.
+    pub fn children(&self) -> &[Packet] {
+        match self {
+            Packet::Literal { .. } => &[],
+            Packet::Operator { children, .. } => children,
+        }
+    }
+
+    /// Sum of the versions of the packet and all of its sub packets.
+    pub fn version_sum(&self) -> u64 {
+        self.version() as u64 + self.children().iter().map(Packet::version_sum).sum::<u64>()
+    }
+
+    /// The value of the expression the packet represents, in checked 64 bit arithmetic.
+    pub fn value(&self) -> Result<u64, EvalError> {
+        self.evaluate(&mut Vec::new())
+    }
+
+    /// The value of the expression the packet represents, however big.
+    pub fn big_value(&self) -> Result<BigUint, EvalError> {
+        self.evaluate(&mut Vec::new())
+    }
+
+    /// Evaluates the packet found at `path`, which is left as it was.
+    fn evaluate<A: Arithmetic>(&self, path: &mut Vec<usize>) -> Result<A, EvalError> {
+        let error = |path: &[usize], problem| EvalError { path: path.to_vec(), problem };
+
+        let (operator, children) = match self {
+            Packet::Literal { value, .. } => {
+                return A::literal(value).ok_or_else(|| error(path, EvalProblem::Literal));
+            }
+            Packet::Operator { operator, children, .. } => (*operator, children),
+        };
+
+        if let Some(message) = operator.operand_error(children.len()) {
+            return Err(error(path, EvalProblem::Operands(message)));
+        }
+
+        let mut values = Vec::with_capacity(children.len());
+        for (i, child) in children.iter().enumerate() {
+            path.push(i);
+            values.push(child.evaluate(path)?);
+            path.pop();
+        }
+
+        operator.apply(values).ok_or_else(|| error(path, EvalProblem::Overflow(operator)))
+    }
+
+    pub fn render(&self, rendering: Rendering) -> String {
+        match rendering {
+            Rendering::Tree => self.tree(),
+            Rendering::Expression => self.to_string(),
+            Rendering::Infix => self.infix(),
+            // re-encoded literals are never longer, so decoded packets always fit their lengths
+            Rendering::Hex => encode(self).expect("decoded packet can't be encoded"),
+        }
+    }
+
+    /// Renders the expression in infix notation, e.g. `6 * 9 + min(3, 7)`, with brackets around
+    /// nested binary operations.
+    pub fn infix(&self) -> String {
+        self.infix_nested(false)
+    }
+
+    fn infix_nested(&self, nested: bool) -> String {
+        let (operator, children) = match self {
+            Packet::Literal { value, .. } => return value.to_string(),
+            Packet::Operator { operator, children, .. } => (operator, children),
+        };
+
+        let symbol = match operator {
+            Operator::Sum => " + ",
+            Operator::Product => " * ",
+            Operator::GreaterThan => " > ",
+            Operator::LessThan => " < ",
+            Operator::EqualTo => " == ",
+            Operator::Minimum | Operator::Maximum => {
+                let arguments = children.iter().map(|c| c.infix_nested(false)).collect::<Vec<_>>();
+                return format!("{}({})", operator.name(), arguments.join(", "));
+            }
+        };
+
+        let operands = children.iter().map(|c| c.infix_nested(true)).collect::<Vec<_>>();
+        match (nested && children.len() > 1, operands.join(symbol)) {
+            (true, expression) => format!("({})", expression),
+            (false, expression) => expression,
+        }
+    }
+
+    /// Renders the packet as an indented tree with the version, type and length mode of every
+    /// packet, and the value it evaluates to.
+    pub fn tree(&self) -> String {
+        let mut tree = String::new();
+        self.write_tree(&mut tree, "", "");
+        tree
+    }
+
+    fn write_tree(&self, tree: &mut String, first_prefix: &str, prefix: &str) {
+        let description = match self {
+            Packet::Literal { version, value } => format!("v{} literal {}", version, value),
+            Packet::Operator { version, operator, length, children } => {
+                let length = match length {
+                    Length::Bits => "bit length",
+                    Length::Count => "packet count",
+                };
+                let value = self.big_value().map_or("?".to_string(), |value| value.to_string());
+                format!(
+                    "v{} {} ({}, {} packets) = {}",
+                    version, operator.name(), length, children.len(), value
+                )
+            }
+        };
+        tree.push_str(first_prefix);
+        tree.push_str(&description);
+        tree.push('\n');
+
+        let children = self.children();
+        for (i, child) in children.iter().enumerate() {
+            if i + 1 == children.len() {
+                child.write_tree(tree, &format!("{}└── ", prefix), &format!("{}    ", prefix));
+            } else {
+                child.write_tree(tree, &format!("{}├── ", prefix), &format!("{}│   ", prefix));
+            }
+        }
+    }
+}
+
+
+/// Renders the packet as an S-expression, e.g. `(sum (product 6 9) (min 3 7))`.
+impl fmt::Display for Packet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Packet::Literal { value, .. } => write!(f, "{}", value),
+            Packet::Operator { operator, children, .. } => {
+                write!(f, "({}", operator.name())?;
+                for child in children {
+                    write!(f, " {}", child)?;
+                }
+                write!(f, ")")
+            }
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+
+    fn literal(value: u64) -> Packet {
+        Packet::Literal { version: 1, value: value.into() }
+    }
+
+
+    fn operator(operator: Operator, children: Vec<Packet>) -> Packet {
+        Packet::Operator { version: 2, operator, length: Length::Count, children }
+    }
+
+
+    #[test]
+    fn rendering() {
+        let packet = operator(Operator::Sum, vec![
+            operator(Operator::Product, vec![literal(6), literal(9)]),
+            operator(Operator::Minimum, vec![literal(3), literal(7)]),
+        ]);
+
+        assert_eq!(Ok(57), packet.value());
+        assert_eq!(10, packet.version_sum());
+        assert_eq!("(sum (product 6 9) (min 3 7))", packet.to_string());
+        assert_eq!("(6 * 9) + min(3, 7)", packet.infix());
+        assert_eq!(
+            concat!(
+                "v2 sum (packet count, 2 packets) = 57\n",
+                "├── v2 product (packet count, 2 packets) = 54\n",
+                "│   ├── v1 literal 6\n",
+                "│   └── v1 literal 9\n",
+                "└── v2 min (packet count, 2 packets) = 3\n",
+                "    ├── v1 literal 3\n",
+                "    └── v1 literal 7\n",
+            ),
+            packet.tree()
+        );
+    }
+
+
+    #[test]
+    fn evaluating() {
+        let big = literal(u64::MAX / 2);
+        let product = operator(Operator::Product, vec![big.clone(), literal(3)]);
+        let packet = operator(Operator::Minimum, vec![
+            literal(7),
+            operator(Operator::Sum, vec![literal(1), product]),
+        ]);
+
+        let error = packet.value().unwrap_err();
+        assert_eq!(vec![1, 1], error.path);
+        assert_eq!("packet 1.1: product overflows 64 bits", error.to_string());
+        assert_eq!("packet 0.1.1: product overflows 64 bits", error.within(0).to_string());
+        assert_eq!(Ok(BigUint::from(7)), packet.big_value());
+
+        let wide = Packet::Literal { version: 0, value: &BigUint::from(u64::MAX) * &2.into() };
+        let packet = operator(Operator::GreaterThan, vec![wide, big]);
+        let error = packet.value().unwrap_err();
+        assert_eq!("packet 0: literal doesn't fit in 64 bits", error.to_string());
+        assert_eq!(Ok(BigUint::from(1)), packet.big_value());
+
+        let packet = operator(Operator::EqualTo, vec![literal(1)]);
+        assert_eq!(
+            "top packet: comparison eq needs exactly two sub packets, found 1",
+            packet.big_value().unwrap_err().to_string()
+        );
+    }
+}
@@ -1,297 +1,402 @@
-use std::fs;
-use std::fmt::Write;
-use std::ops::Add;
+use std::fmt;
+use aoc_core::ParseError;
+use crate::packet_decoder::big::BigUint;
+use crate::packet_decoder::bits::{hex_to_bytes, BitReader};
+use crate::packet_decoder::packet::{
+    EvalError, EvalProblem, Length, Operator, Packet, LITERAL_TYPE_ID,
+};
 
 
-pub fn solve_first_star() -> u64 {
-    let initial_packet = parse_input("src/packet_decoder/input.txt");
+pub fn solve_first_star(packets: &[Packet]) -> u64 {
+    packets.iter().map(Packet::version_sum).sum()
+}
+
 
-    let mut packet = &initial_packet[..];
-    let mut version_accumulator = 0u64;
+/// Sums the values of the packets in checked 64 bit arithmetic.
+pub fn solve_second_star(packets: &[Packet]) -> Result<u64, EvalError> {
+    let mut total = 0u64;
 
-    while !packet.is_empty() {
-        let (version, _, remaining_packet) = process_packet(packet);
+    for (i, packet) in packets.iter().enumerate() {
+        let value = packet.value().map_err(|error| error.within(i))?;
 
-        version_accumulator += version;
-        packet = remaining_packet;
+        total = total
+            .checked_add(value)
+            .ok_or(EvalError { path: vec![], problem: EvalProblem::Total })?;
     }
 
-    version_accumulator
+    Ok(total)
 }
 
 
-pub fn solve_second_star() -> u64 {
-    let initial_packet = parse_input("src/packet_decoder/input.txt");
+/// Sums the values of the packets however big they are.
+pub fn solve_second_star_big(packets: &[Packet]) -> Result<BigUint, EvalError> {
+    let mut total = BigUint::default();
 
-    let mut packet = &initial_packet[..];
-    let mut result_accumulator = 0u64;
+    for (i, packet) in packets.iter().enumerate() {
+        total = &total + &packet.big_value().map_err(|error| error.within(i))?;
+    }
 
-    while !packet.is_empty() {
-        let (_, result, remaining_packet) = process_packet(packet);
+    Ok(total)
+}
 
-        result_accumulator += result;
-        packet = remaining_packet;
-    }
 
-    result_accumulator
+/// A malformed transmission, with the offset in bits where the problem was found.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DecodeError {
+    pub offset: usize,
+    pub problem: Problem,
 }
 
 
-/// Processes the header of the packet and decodes the version and type ID, returning the
-/// remaining slice of the packet for further processing
-fn process_header(packet: &str) -> (u64, u64, &str) {
-    let version = u64::from_str_radix(&packet[0..3], 2).unwrap();
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Problem {
+    TruncatedHeader,
+    TruncatedLiteral,
+    TruncatedOperator,
+    UnknownTypeId(u8),
+    /// The sub packets of a length type 0 operator run past the bit length it gives.
+    Overrun { length: usize },
+    /// Only checked when validating, as an operator's value needs the right number of operands.
+    Operands(String),
+    /// Only checked when validating, the transmission must end with zeros.
+    Padding,
+}
 
-    let packet_type_id = u64::from_str_radix(&packet[3..6], 2).unwrap();
 
-    let data_payload = &packet[6..];
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "bit {}: ", self.offset)?;
+
+        match &self.problem {
+            Problem::TruncatedHeader => write!(f, "truncated packet header"),
+            Problem::TruncatedLiteral => write!(f, "truncated literal"),
+            Problem::TruncatedOperator => write!(f, "truncated operator length"),
+            Problem::UnknownTypeId(type_id) => write!(f, "unknown type id {}", type_id),
+            Problem::Overrun { length } => {
+                write!(f, "sub packets overrun their length of {} bits", length)
+            }
+            Problem::Operands(message) => write!(f, "{}", message),
+            Problem::Padding => write!(f, "non-zero padding"),
+        }
+    }
+}
 
-    (version, packet_type_id, data_payload)
+
+/// Reads `n` bits, failing with `problem` at the current offset if the transmission ends first.
+fn read(reader: &mut BitReader, n: usize, problem: Problem) -> Result<u64, DecodeError> {
+    let offset = reader.position();
+
+    reader.read_bits(n).ok_or(DecodeError { offset, problem })
 }
 
 
-/// Processes the data section of a literal packet, returning the decimal number stored whilst
-/// returning the remaining slice of the packet for further processing
-fn process_literal_data(packet: &str) -> (u64, &str) {
-    let mut more_data = true;
+/// Processes the header of the packet and decodes the version and type ID, leaving the reader at
+/// the data of the packet
+fn process_header(reader: &mut BitReader) -> Result<(u64, u64), DecodeError> {
+    let version = read(reader, 3, Problem::TruncatedHeader)?;
+
+    let packet_type_id = read(reader, 3, Problem::TruncatedHeader)?;
 
-    let mut literal = String::new();
+    Ok((version, packet_type_id))
+}
+
+
+/// Processes the data section of a literal packet, returning the number stored, however long
+fn process_literal_data(reader: &mut BitReader) -> Result<BigUint, DecodeError> {
+    let mut more_data = true;
 
-    let mut window = packet;
+    let mut literal = BigUint::default();
 
     while more_data {
-        if window.starts_with('1') {
-            more_data = true;
-        } else {
-            more_data = false;
-        }
+        // a group is read whole, so that truncation is reported at its start
+        let group = read(reader, 5, Problem::TruncatedLiteral)?;
+        more_data = group & 0b10000 != 0;
 
         // add bits to literal
-        literal = literal.add(&window[1..5]);
-
-        // update window
-        window = &window[5..];
+        literal.push_nibble(group & 0b1111);
     }
 
-    // convert bits to unsigned int
-    (u64::from_str_radix(&literal, 2).unwrap(), window)
+    Ok(literal)
 }
 
 
 /// Processes the data section of an operator packet.
-/// Returns the cumulative value of all sub packet versions, the result
-/// of operator on the sub packet, and the remaining slice of the packet for further processing
-fn process_operator_data(packet: &str, packet_type: u64) -> (u64, u64, &str) {
-    let mut version_accumulator = 0u64;
-    let mut results = Vec::<u64>::new();
+/// Returns the length mode and sub packets of the operator
+fn process_operator_data(
+    reader: &mut BitReader,
+    strict: bool,
+) -> Result<(Length, Vec<Packet>), DecodeError> {
+    let mut children = Vec::<Packet>::new();
 
     // calculate packet size length based on length id type
-    if packet.starts_with('0') {
+    if read(reader, 1, Problem::TruncatedOperator)? == 0 {
         // length_id_type = 0
-        // fixed packet slice containing n sub packets
-        let length = 15;
-
-        // calculate size of sub packets
-        let sub_packets_size = usize::from_str_radix(&packet[1..length + 1], 2).unwrap();
+        // total length in bits of the sub packets
+        let sub_packets_size = read(reader, 15, Problem::TruncatedOperator)? as usize;
 
-        // calculate start and end of sub packet
-        let start = length + 1;
-        let end = length + 1 + sub_packets_size;
-
-        let mut sub_packets = &packet[start..end];
+        // calculate end of sub packets
+        let start = reader.position();
+        let end = start + sub_packets_size;
 
         // process sub packets
-        while !sub_packets.is_empty() {
-            let (version, result, remaining_packet) = process_packet(sub_packets);
-            version_accumulator += version;
-
-            results.push(result);
-
-            sub_packets = remaining_packet;
+        while reader.position() < end {
+            children.push(process_packet(reader, strict)?);
         }
 
-        let result = perform_operator(results, packet_type);
+        if reader.position() > end {
+            let problem = Problem::Overrun { length: sub_packets_size };
+            return Err(DecodeError { offset: start, problem });
+        }
 
-        (version_accumulator, result, &packet[end..])
+        Ok((Length::Bits, children))
     } else {
         // length_id_type = 1
         // number of sub packets
-        let length = 11;
-
-        // calculate number of sub packets
-        let sub_packets_num = usize::from_str_radix(&packet[1..length + 1], 2).unwrap();
-
-        // calculate start and end of sub packet (don't know end)
-        let start = length + 1;
-
-        let mut remaining_packet = &packet[start..];
+        let sub_packets_num = read(reader, 11, Problem::TruncatedOperator)?;
 
         // process sub packets
         for _ in 0..sub_packets_num {
-            let (version, result, next_packet) = process_packet(remaining_packet);
-
-            remaining_packet = next_packet;
-
-            version_accumulator += version;
-
-            results.push(result);
+            children.push(process_packet(reader, strict)?);
         }
 
-        let result = perform_operator(results, packet_type);
-
-        (version_accumulator, result, remaining_packet)
+        Ok((Length::Count, children))
     }
 }
 
 
-/// Performs the operation determined by packet_type id on the sub packet results vector
-/// and returns the result
-fn perform_operator(results: Vec<u64>, packet_type: u64) -> u64 {
-    match packet_type {
-        0 => {results.iter().sum()},
-        1 => {results.iter().product()},
-        2 => {*results.iter().min().unwrap()},
-        3 => {*results.iter().max().unwrap()},
-        5 => {results.first().unwrap().gt(results.last().unwrap()) as u64},
-        6 => {results.first().unwrap().lt(results.last().unwrap()) as u64},
-        7 => {results.first().unwrap().eq(results.last().unwrap()) as u64},
-        _ => {panic!("Error: Unknown packet type")},
-    }
-}
-
-
-/// Recursive function that processes packets and accumulates their cumulative packet version numbers
-/// and the overall result of the packet. Also returns the next packet for processing, if further
-/// processing required.
-fn process_packet(packet: &str) -> (u64, u64, &str) {
-    let mut version_accumulator = 0u64;
-    let mut result_accumulator = 0u64;
-    let mut next_packet = "";
-
-    if packet.len() < 11 {
-        // not enough packet left to process. Return empty string.
-        return (result_accumulator, version_accumulator, next_packet);
-    }
-
-    let (version, packet_type, data) = process_header(packet);
-
-    version_accumulator += version;
+/// Recursive function that decodes a packet and its sub packets into a [Packet] tree, leaving the
+/// reader at the next packet. When `strict`, operators must also have the operands their value
+/// needs.
+fn process_packet(reader: &mut BitReader, strict: bool) -> Result<Packet, DecodeError> {
+    let offset = reader.position();
+    let (version, packet_type) = process_header(reader)?;
+    let version = version as u8;
 
     // process packet data
-    if packet_type == 4 {
+    if packet_type == LITERAL_TYPE_ID as u64 {
         // literal packet
-        let (literal, remaining_packet) = process_literal_data(data);
+        let value = process_literal_data(reader)?;
 
-        next_packet = remaining_packet;
+        return Ok(Packet::Literal { version, value });
+    }
 
-        result_accumulator += literal;
-    } else {
-        // operator packet
-        let (version, result, remaining_packet) = process_operator_data(data, packet_type);
+    // operator packet
+    let operator = Operator::from_type_id(packet_type as u8).ok_or(DecodeError {
+        offset: offset + 3,
+        problem: Problem::UnknownTypeId(packet_type as u8),
+    })?;
+    let (length, children) = process_operator_data(reader, strict)?;
 
-        version_accumulator += version;
-        result_accumulator += result;
-        next_packet = remaining_packet;
+    if let Some(message) = operator.operand_error(children.len()).filter(|_| strict) {
+        return Err(DecodeError { offset, problem: Problem::Operands(message) });
     }
 
-    (version_accumulator, result_accumulator, next_packet)
+    Ok(Packet::Operator { version, operator, length, children })
 }
 
 
-/// Converts the hex to binary string representation for packet parsing
-fn parse_hex_to_binary(contents: &str) -> String {
-    let mut hex_bytes = Vec::<u64>::new();
+/// Decodes every packet of a transmission, stopping at the padding at the end. When `strict`, the
+/// packets are validated and the padding must be zeros.
+fn decode_transmission(transmission: &[u8], strict: bool) -> Result<Vec<Packet>, DecodeError> {
+    let mut packets = Vec::<Packet>::new();
+    let mut reader = BitReader::new(transmission);
 
-    for c in contents.chars() {
-        if let Some(d) = c.to_digit(16) {
-            hex_bytes.push(d as u64);
-        }
+    // the shortest packet is a literal of 11 bits, anything shorter is padding
+    while reader.remaining() >= 11 && !reader.only_zeros_left() {
+        packets.push(process_packet(&mut reader, strict)?);
     }
 
-    let mut result = String::new();
-
-    for byte in hex_bytes {
-        write!(&mut result, "{:04b}", byte).expect("bytes should be properly parsed earlier");
+    if strict && !reader.only_zeros_left() {
+        return Err(DecodeError { offset: reader.position(), problem: Problem::Padding });
     }
-    result
+
+    Ok(packets)
 }
 
 
-fn parse_input(file_path: &str) -> String {
-    let contents = fs::read_to_string(file_path).expect("Input file local to project");
+/// Parses a hexadecimal transmission, validating the packets when `strict`.
+pub fn parse_input(contents: &str, strict: bool) -> Result<Vec<Packet>, ParseError> {
+    let transmission = contents.trim();
+
+    if transmission.is_empty() {
+        return Err(ParseError::end_of_input(contents, "missing transmission"));
+    }
+
+    if let Some(i) = transmission.find(|c: char| !c.is_ascii_hexdigit()) {
+        return Err(ParseError::at(contents, &transmission[i..], "expected a hexadecimal digit"));
+    }
 
-    parse_hex_to_binary(&contents)
+    decode_transmission(&hex_to_bytes(transmission), strict).map_err(|error| {
+        // point at the hexadecimal digit holding the offending bit
+        let digit = (error.offset / 4).min(transmission.len());
+        ParseError::at(contents, &transmission[digit..], error.to_string())
+    })
 }
 
 
 #[cfg(test)]
 mod tests {
     use super::*;
-
-    #[test]
-    fn solved() {
-        assert_eq!(895, solve_first_star());
-        assert_eq!(1148595959144, solve_second_star());
-    }
-
-    #[test]
-    fn binary_parser() {
-        let packet = parse_hex_to_binary("D2FE28");
-        assert_eq!("110100101111111000101000", packet);
-
-        let packet = parse_hex_to_binary("38006F45291200");
-        assert_eq!("00111000000000000110111101000101001010010001001000000000", packet);
-
-        let packet = parse_hex_to_binary("EE00D40C823060");
-        assert_eq!("11101110000000001101010000001100100000100011000001100000", packet);
-    }
+    use crate::packet_decoder::bits::{bytes_to_hex, BitWriter};
+    use crate::packet_decoder::encode::encode;
 
     #[test]
     fn processing_header() {
-        let packet = parse_hex_to_binary("D2FE28");
-        let (version, packet_type_id, data) = process_header(&packet);
+        let packet = hex_to_bytes("D2FE28");
+        let mut reader = BitReader::new(&packet);
+        let (version, packet_type_id) = process_header(&mut reader).unwrap();
         assert_eq!(6, version);
         assert_eq!(4, packet_type_id);
-        assert_eq!("101111111000101000", data);
+        assert_eq!(18, reader.remaining());
 
-        let packet = parse_hex_to_binary("38006F45291200");
-        let (version, packet_type_id, data) = process_header(&packet);
+        let packet = hex_to_bytes("38006F45291200");
+        let mut reader = BitReader::new(&packet);
+        let (version, packet_type_id) = process_header(&mut reader).unwrap();
         assert_eq!(1, version);
         assert_eq!(6, packet_type_id);
-        assert_eq!("00000000000110111101000101001010010001001000000000", data);
+        assert_eq!(6, reader.position());
 
-        let packet = parse_hex_to_binary("EE00D40C823060");
-        let (version, packet_type_id, data) = process_header(&packet);
+        let packet = hex_to_bytes("EE00D40C823060");
+        let mut reader = BitReader::new(&packet);
+        let (version, packet_type_id) = process_header(&mut reader).unwrap();
         assert_eq!(7, version);
         assert_eq!(3, packet_type_id);
-        assert_eq!("10000000001101010000001100100000100011000001100000", data);
+        assert_eq!(50, reader.remaining());
     }
 
     #[test]
     fn processing_literal_packet() {
-        let packet = parse_hex_to_binary("D2FE28");
-        let (_, _, data) = process_header(&packet);
-        let (num, next_packet) = process_literal_data(data);
-        assert_eq!("000", next_packet);
-        assert_eq!(2021, num);
+        let packet = hex_to_bytes("D2FE28");
+        let mut reader = BitReader::new(&packet);
+        process_header(&mut reader).unwrap();
+        let num = process_literal_data(&mut reader).unwrap();
+        assert_eq!(3, reader.remaining());
+        assert_eq!(Some(2021), num.to_u64());
     }
 
     #[test]
     fn processing_operator_packet() {
-        let packet = parse_hex_to_binary("38006F45291200");
-        let (header_version, packet_type, data) = process_header(&packet);
-        let (version, result, next_packet) = process_operator_data(data, packet_type);
-        assert_eq!("0000000", next_packet);
-        assert_eq!(1, result);
-        assert_eq!(9, version + header_version);
+        let packet = hex_to_bytes("38006F45291200");
+        let mut reader = BitReader::new(&packet);
+        let (header_version, _) = process_header(&mut reader).unwrap();
+        let (length, children) = process_operator_data(&mut reader, true).unwrap();
+        assert_eq!(7, reader.remaining());
+        assert_eq!(Length::Bits, length);
+        assert_eq!(vec![10, 20], children.iter().map(|c| c.value().unwrap()).collect::<Vec<_>>());
+        assert_eq!(9, children.iter().map(Packet::version_sum).sum::<u64>() + header_version);
     }
 
     #[test]
     fn processing_packet() {
-        let packet = parse_hex_to_binary("EE00D40C823060");
-        let (version, result, next_packet) = process_packet(&packet);
-        assert_eq!("00000", next_packet);
-        assert_eq!(3, result);
-        assert_eq!(14, version);
+        let packet = hex_to_bytes("EE00D40C823060");
+        let mut reader = BitReader::new(&packet);
+        let packet = process_packet(&mut reader, true).unwrap();
+        assert_eq!(5, reader.remaining());
+        assert_eq!(Ok(3), packet.value());
+        assert_eq!(14, packet.version_sum());
+        assert_eq!("(max 1 2 3)", packet.to_string());
+    }
+    #[test]
+    fn malformed_transmissions() {
+        let message = |transmission: &str, strict| {
+            parse_input(transmission, strict).map(|_| ()).map_err(|error| error.message)
+        };
+
+        assert_eq!(Err("bit 16: truncated literal".to_string()), message("D2FE", false));
+        assert_eq!(Err("bit 7: truncated operator length".to_string()), message("3800", false));
+        assert_eq!(Err("bit 22: truncated packet header".to_string()), message("38006F", false));
+
+        // trailing zeros are padding however many there are, and must be zeros when validating
+        assert_eq!(Ok(()), message("D2FE28000", true));
+        assert_eq!(Ok(()), message("D2FE29", false));
+        assert_eq!(Err("bit 21: non-zero padding".to_string()), message("D2FE29", true));
+
+        // a sum of bit length 10 holding an 11 bit literal
+        let mut writer = BitWriter::new();
+        writer.write_bits(0, 7);
+        writer.write_bits(10, 15);
+        writer.write_bits(LITERAL_TYPE_ID as u64, 6);
+        writer.write_bits(1, 5);
+        let transmission = bytes_to_hex(&writer.into_bytes());
+        assert_eq!(
+            Err("bit 22: sub packets overrun their length of 10 bits".to_string()),
+            message(&transmission, false)
+        );
+
+        let literal = Packet::Literal { version: 0, value: 1.into() };
+        let comparison = Packet::Operator {
+            version: 0,
+            operator: Operator::LessThan,
+            length: Length::Count,
+            children: vec![literal.clone(); 3],
+        };
+        let transmission = encode(&comparison).unwrap();
+        assert_eq!(Ok(()), message(&transmission, false));
+        assert_eq!(
+            Err("bit 0: comparison lt needs exactly two sub packets, found 3".to_string()),
+            message(&transmission, true)
+        );
+
+        let maximum = Packet::Operator {
+            version: 0,
+            operator: Operator::Sum,
+            length: Length::Bits,
+            children: vec![Packet::Operator {
+                version: 0,
+                operator: Operator::Maximum,
+                length: Length::Bits,
+                children: vec![],
+            }],
+        };
+        assert_eq!(
+            Err("bit 22: max needs at least one sub packet".to_string()),
+            message(&encode(&maximum).unwrap(), true)
+        );
+    }
+
+
+    #[test]
+    fn evaluating_transmissions() {
+        let half = Packet::Literal { version: 0, value: (1 << 63).into() };
+        let packets = vec![half.clone(), half];
+
+        let error = solve_second_star(&packets).unwrap_err();
+        assert_eq!("the sum of the packets overflows 64 bits", error.to_string());
+        assert_eq!("18446744073709551616", solve_second_star_big(&packets).unwrap().to_string());
+
+        // a literal of 17 groups, too long for 64 bits
+        let wide = Packet::Literal { version: 0, value: &BigUint::from(1 << 63) * &32.into() };
+        let product = Packet::Operator {
+            version: 0,
+            operator: Operator::Product,
+            length: Length::Count,
+            children: vec![wide, Packet::Literal { version: 0, value: 3.into() }],
+        };
+        let packets = parse_input(&encode(&product).unwrap(), true).unwrap();
+
+        let error = solve_second_star(&packets).unwrap_err();
+        assert_eq!("packet 0.0: literal doesn't fit in 64 bits", error.to_string());
+        assert_eq!("885443715538058477568", solve_second_star_big(&packets).unwrap().to_string());
+    }
+
+
+    #[test]
+    fn decoding_tree() {
+        let packets = parse_input("9C0141080250320F1802104A08", true).unwrap();
+        assert_eq!(1, packets.len());
+        assert_eq!("(eq (sum 1 3) (product 2 2))", packets[0].to_string());
+        assert_eq!("(1 + 3) == (2 * 2)", packets[0].infix());
+        assert_eq!(
+            concat!(
+                "v4 eq (bit length, 2 packets) = 1\n",
+                "├── v2 sum (packet count, 2 packets) = 4\n",
+                "│   ├── v2 literal 1\n",
+                "│   └── v4 literal 3\n",
+                "└── v6 product (packet count, 2 packets) = 4\n",
+                "    ├── v0 literal 2\n",
+                "    └── v2 literal 2\n",
+            ),
+            packets[0].tree()
+        );
     }
-}
\ No newline at end of file
+}
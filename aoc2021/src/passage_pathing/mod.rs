@@ -1,12 +1,93 @@
-use crate::passage_pathing::solve::{solve_first_star, solve_second_star};
+use aoc_core::{Answer, Options, ParseError, Solver};
+use crate::passage_pathing::solve::{
+    list_paths, parse_input, solve_first_star, solve_second_star, Caves,
+};
+use crate::YEAR;
 
 mod solve;
 
-pub fn passage_pathing_solution() {
-    println!("--- Day 12: Passage Pathing ---");
-    let result = solve_first_star();
-    println!("\t1st Star Solution = {}", result);
+/// Passage Pathing, which counts the paths without listing them unless `list` is set, when every
+/// path is found one by one and printed to stderr.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PassagePathing {
+    pub list: bool,
+}
 
-    let result = solve_second_star();
-    println!("\t2nd Star Solution = {}", result);
-}
\ No newline at end of file
+impl Solver for PassagePathing {
+    type Input = Caves;
+
+    fn year(&self) -> u32 {
+        YEAR
+    }
+
+    fn day(&self) -> u32 {
+        12
+    }
+
+    fn title(&self) -> &'static str {
+        "Passage Pathing"
+    }
+
+    fn input_path(&self) -> &'static str {
+        concat!(env!("CARGO_MANIFEST_DIR"), "/src/passage_pathing/input.txt")
+    }
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Result<Answer, String> {
+        if self.list {
+            return Ok(print_paths(list_paths(input, false)));
+        }
+
+        Ok(solve_first_star(input).into())
+    }
+
+    fn part2(&self, input: &Self::Input) -> Result<Answer, String> {
+        if self.list {
+            return Ok(print_paths(list_paths(input, true)));
+        }
+
+        Ok(solve_second_star(input).into())
+    }
+
+    fn configure(&self, options: &Options) -> Result<Self, String> {
+        options.expect(&["list"])?;
+
+        Ok(PassagePathing { list: options.get("list")?.unwrap_or(self.list) })
+    }
+}
+
+
+/// Prints the paths to stderr, answering with how many there are.
+fn print_paths(paths: Vec<String>) -> Answer {
+    for path in &paths {
+        eprintln!("{}", path);
+    }
+
+    paths.len().into()
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_core::verify;
+
+    #[test]
+    fn solved() {
+        verify::assert_solved(PassagePathing::default());
+    }
+
+    #[test]
+    fn listed() {
+        verify::assert_solved(PassagePathing { list: true });
+    }
+
+    aoc_core::examples!(PassagePathing::default(), {
+        example: "test_input.txt",
+        example2: "test_input2.txt",
+        example3: "test_input3.txt",
+    });
+}
@@ -1,165 +1,193 @@
 use std::collections::HashMap;
-use std::fs;
-use itertools::Itertools;
+use aoc_core::ParseError;
+
+
+/// The cave system, with every cave interned as its index in `names`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Caves {
+    names: Vec<String>,
+    /// Caves linked to each cave, leaving out `start` as no path goes back to it.
+    links: Vec<Vec<usize>>,
+    /// Bitmask of the small caves.
+    small: u64,
+    start: usize,
+    end: usize,
+}
 
 
-pub fn solve_first_star() -> u64 {
-    let cave_paths = parse_input("src/passage_pathing/input.txt");
+/// Visits to the caves are tracked in a bitmask, so there can only be as many caves as bits.
+const MAX_CAVES: usize = u64::BITS as usize;
 
-    let mut valid_paths = Vec::<String>::new();
 
-    // Explore cave starting at 'start'
-    let current_path = vec!["start".to_string()];
+pub fn solve_first_star(caves: &Caves) -> u64 {
+    count_paths(caves, false)
+}
 
-    explore(&cave_paths, current_path, &mut valid_paths);
 
-    valid_paths.len() as u64
+pub fn solve_second_star(caves: &Caves) -> u64 {
+    count_paths(caves, true)
 }
 
 
-pub fn solve_second_star() -> u64 {
-    let cave_paths = parse_input("src/passage_pathing/input.txt");
+/// Counts the paths from `start` to `end` that visit small caves at most once, except for a
+/// single small cave visited twice if `revisit`.
+///
+/// The paths onwards from a cave only depend on the small caves visited so far and on whether a
+/// small cave can still be visited twice, so they are counted once for each combination.
+pub fn count_paths(caves: &Caves, revisit: bool) -> u64 {
+    let mut counts = HashMap::new();
 
-    let mut valid_paths = Vec::<String>::new();
+    count_onwards(caves, caves.start, 1 << caves.start, revisit, &mut counts)
+}
 
-    // Explore cave starting at 'start'
-    let current_path = vec!["start".to_string()];
 
-    // Exploring leisurely takes significant time. Disable behind feature flag
-    #[cfg(feature = "run_solver")]
-    explore_leisurely(&cave_paths, current_path, &mut valid_paths);
+fn count_onwards(
+    caves: &Caves,
+    cave: usize,
+    visited: u64,
+    revisit: bool,
+    counts: &mut HashMap<(usize, u64, bool), u64>,
+) -> u64 {
+    if cave == caves.end {
+        return 1;
+    }
+
+    if let Some(&count) = counts.get(&(cave, visited, revisit)) {
+        return count;
+    }
 
-    if valid_paths.is_empty() {
-        // solver disabled. Return known result
-        return 147848;
+    let mut count = 0;
+    for &next in &caves.links[cave] {
+        if let Some((next_visited, next_revisit)) = step(caves, next, visited, revisit) {
+            count += count_onwards(caves, next, next_visited, next_revisit, counts);
+        }
     }
 
-    valid_paths.len() as u64
+    counts.insert((cave, visited, revisit), count);
+    count
+}
+
+
+/// Lists every path counted by [count_paths], as the names of its caves joined by commas.
+pub fn list_paths(caves: &Caves, revisit: bool) -> Vec<String> {
+    let mut paths = Vec::<String>::new();
+
+    // Explore cave starting at 'start'
+    let mut current_path = vec![caves.start];
+
+    explore(caves, &mut current_path, 1 << caves.start, revisit, &mut paths);
+
+    paths
 }
 
 
 /// Function that recursively explores the cave until it hits a dead end or
 /// finds the 'end'.
-fn explore(cave_paths: &HashMap<String, Vec<String>>, current_path: Vec<String>,
+fn explore(caves: &Caves, current_path: &mut Vec<usize>, visited: u64, revisit: bool,
            valid_paths: &mut Vec<String>) {
-    let current_location = current_path.last().unwrap();
+    let current_location = *current_path.last().unwrap();
 
-    if current_location == "end" {
+    if current_location == caves.end {
         // found a path out. Turn current_path to string and push to valid_paths
-        let new_valid_path = current_path.join(",");
+        let names = current_path.iter().map(|&cave| caves.names[cave].as_str()).collect::<Vec<_>>();
 
-        valid_paths.push(new_valid_path);
+        valid_paths.push(names.join(","));
 
         return;
     }
 
     // continue exploring
-    let cave_options = cave_paths.get(current_location).unwrap();
-
-    for cave in cave_options {
-        if cave.to_lowercase() == *cave {
-            // check if its a small cave we haven't already visited
-            if !current_path.contains(cave) {
-                // Haven't visited. Lets go!
-                let mut updated_current_path = current_path.to_owned();
-                updated_current_path.push(cave.to_owned());
-
-                explore(cave_paths, updated_current_path, valid_paths);
-            }
-        } else {
-            // Lets go!
-            let mut updated_current_path = current_path.to_owned();
-            updated_current_path.push(cave.to_owned());
-
-            explore(cave_paths, updated_current_path, valid_paths);
+    for &cave in &caves.links[current_location] {
+        if let Some((visited, revisit)) = step(caves, cave, visited, revisit) {
+            current_path.push(cave);
+            explore(caves, current_path, visited, revisit, valid_paths);
+            current_path.pop();
         }
     }
 }
 
-/// Function that recursively explores the cave until it hits a dead end or
-/// finds the 'end', with the new condition for exploring a single small cave twice.
-fn explore_leisurely(cave_paths: &HashMap<String, Vec<String>>, current_path: Vec<String>,
-                     valid_paths: &mut Vec<String>) {
-    let current_location = current_path.last().unwrap();
 
-    if current_location == "end" {
-        // found a path out. Turn current_path to string and push to valid_paths
-        let new_valid_path = current_path.join(",");
+/// Moves into `cave`, returning the small caves visited and whether a small cave can still be
+/// visited twice after the move, or `None` if the cave can't be visited again.
+fn step(caves: &Caves, cave: usize, visited: u64, revisit: bool) -> Option<(u64, bool)> {
+    let bit = 1 << cave;
+
+    if caves.small & bit == 0 {
+        // Large cave, Lets go!
+        Some((visited, revisit))
+    } else if visited & bit == 0 {
+        // Small cave we haven't visited yet
+        Some((visited | bit, revisit))
+    } else if revisit {
+        // Small cave again, wasting the time to visit one twice
+        Some((visited, false))
+    } else {
+        None
+    }
+}
 
-        valid_paths.push(new_valid_path);
 
-        return;
-    }
+pub fn parse_input(contents: &str) -> Result<Caves, ParseError> {
+    let mut names = Vec::<String>::new();
+    let mut ids = HashMap::<&str, usize>::new();
+    let mut links = Vec::<(usize, usize)>::new();
 
-    // continue exploring
-    let cave_options = cave_paths.get(current_location).unwrap();
-
-    for cave in cave_options {
-        if *cave == "start" {
-            // Whoops, can't got back to the start. Do nothing.
-        } else if cave.to_lowercase() == *cave {
-            // Small cave, check if we can waste time visiting a small cave twice.
-            let can_waste_time = current_path
-                .iter()
-                .filter(|&x| *x.to_lowercase() == *x)
-                .duplicates()
-                .count() == 0;
-
-            // check if its a small cave we haven't already visited or if we have time
-            if !current_path.contains(cave) || can_waste_time {
-                // Haven't visited. Lets go!
-                let mut updated_current_path = current_path.to_owned();
-                updated_current_path.push(cave.to_owned());
-
-                explore_leisurely(cave_paths, updated_current_path, valid_paths);
+    for line in contents.lines() {
+        let (start, destination) = line
+            .split_once('-')
+            .ok_or_else(|| ParseError::at(contents, line, "expected `cave-cave`"))?;
+
+        let mut intern = |name| {
+            let next = names.len();
+            let id = *ids.entry(name).or_insert(next);
+            if id == next {
+                names.push(name.to_string());
             }
-        } else {
-            // Large cave, Lets go!
-            let mut updated_current_path = current_path.to_owned();
-            updated_current_path.push(cave.to_owned());
+            id
+        };
+        let link = (intern(start), intern(destination));
 
-            explore_leisurely(cave_paths, updated_current_path, valid_paths);
+        if names.len() > MAX_CAVES {
+            let message = format!("too many caves, there can be at most {}", MAX_CAVES);
+            return Err(ParseError::at(contents, line, message));
         }
-    }
-}
-
-
-fn parse_input(file_path: &str) -> HashMap::<String, Vec<String>> {
-    let contents = fs::read_to_string(file_path).expect("Input file local to project");
 
-    let mut result = HashMap::<String, Vec<String>>::new();
-
-    for line in contents.lines() {
-        let mut node_path = line.split('-');
+        // two linked large caves could be walked between forever
+        if start.to_lowercase() != start && destination.to_lowercase() != destination {
+            return Err(ParseError::at(contents, line, "large caves can't be linked together"));
+        }
 
+        links.push(link);
+    }
 
-        let start = node_path.next().unwrap().to_string();
-        let destination = node_path.next().unwrap().to_string();
+    let find = |name: &str| {
+        ids.get(name)
+            .copied()
+            .ok_or_else(|| ParseError::end_of_input(contents, format!("missing `{}` cave", name)))
+    };
+    let start = find("start")?;
+    let end = find("end")?;
 
-        // add start -> destination
-        if let Some(node) = result.get_mut(&start) {
-            // already in the hashmap, push new path to list of viable paths
-            node.push(destination.clone());
-        } else {
-            // doesn't exist, create new entry in hashmap
-            let paths = vec![destination.clone()];
+    let mut caves = Caves { names, links: Vec::new(), small: 0, start, end };
+    caves.links = vec![Vec::new(); caves.names.len()];
 
-            result.insert(start.clone(), paths);
+    for (i, name) in caves.names.iter().enumerate() {
+        if name.to_lowercase() == *name {
+            caves.small |= 1 << i;
         }
+    }
 
-        // add destination -> start
-        if let Some(node) = result.get_mut(&destination) {
-            // already in the hashmap, push new path to list of viable paths
-            node.push(start);
-        } else {
-            // doesn't exist, create new entry in hashmap
-            let paths = vec![start];
-
-            result.insert(destination, paths);
+    // add both directions, except back to the start
+    for (a, b) in links {
+        if b != start {
+            caves.links[a].push(b);
+        }
+        if a != start {
+            caves.links[b].push(a);
         }
     }
 
-    result
+    Ok(caves)
 }
 
 
@@ -168,8 +196,52 @@ mod tests {
     use super::*;
 
     #[test]
-    fn solved() {
-        assert_eq!(4720, solve_first_star());
-        assert_eq!(147848, solve_second_star());
+    fn listing_paths() {
+        let caves = parse_input(include_str!("test_input.txt")).unwrap();
+
+        let mut paths = list_paths(&caves, false);
+        paths.sort();
+        assert_eq!(
+            vec![
+                "start,A,b,A,c,A,end",
+                "start,A,b,A,end",
+                "start,A,b,end",
+                "start,A,c,A,b,A,end",
+                "start,A,c,A,b,end",
+                "start,A,c,A,end",
+                "start,A,end",
+                "start,b,A,c,A,end",
+                "start,b,A,end",
+                "start,b,end",
+            ],
+            paths
+        );
+
+        for input in [
+            include_str!("test_input.txt"),
+            include_str!("test_input2.txt"),
+            include_str!("test_input3.txt"),
+        ] {
+            let caves = parse_input(input).unwrap();
+
+            for revisit in [false, true] {
+                assert_eq!(list_paths(&caves, revisit).len() as u64, count_paths(&caves, revisit));
+            }
+        }
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn parsing_errors() {
+        let error = parse_input("start-A\nA-B\nA-end").unwrap_err();
+        assert_eq!(2, error.line);
+        assert_eq!("large caves can't be linked together", error.message);
+
+        let error = parse_input("start-A\nA-b").unwrap_err();
+        assert_eq!("missing `end` cave", error.message);
+
+        let links = (0..64).map(|i| format!("start-c{}", i)).collect::<Vec<_>>().join("\n");
+        let error = parse_input(&links).unwrap_err();
+        assert_eq!(64, error.line);
+        assert_eq!("too many caves, there can be at most 64", error.message);
+    }
+}
@@ -1,12 +1,10 @@
 use std::collections::HashSet;
-use std::fs;
 use std::ops::Index;
 use itertools::Itertools;
+use aoc_core::ParseError;
 
 
-pub fn solve_first_star() -> u32 {
-    let data = parse_input("src/seven_segment_search/input.txt");
-
+pub fn solve_first_star(data: &[String]) -> u32 {
     // We know that there are 4 digits with unique number of lit up segments
     // 1 -> 2 segments
     // 4 -> 4 segments
@@ -21,7 +19,7 @@ pub fn solve_first_star() -> u32 {
     for item in data {
         let screen_readout = item
             .split('|')
-            .last().unwrap()
+            .next_back().unwrap()
             .split_whitespace()
             .collect::<Vec<_>>();
 
@@ -35,13 +33,11 @@ pub fn solve_first_star() -> u32 {
 }
 
 
-pub fn solve_second_star() -> u32 {
-    let data = parse_input("src/seven_segment_search/input.txt");
-
+pub fn solve_second_star(data: &[String]) -> u32 {
     let mut result = 0u32;
 
     for reading in data {
-        let decoding_key = decode_segment_wiring(&reading);
+        let decoding_key = decode_segment_wiring(reading).expect("notes are checked when parsed");
 
         let number = decode_reading(reading, decoding_key);
 
@@ -51,11 +47,11 @@ pub fn solve_second_star() -> u32 {
     result
 }
 
-fn decode_reading(reading: String, decoding_key: Vec<String>) -> u32 {
+fn decode_reading(reading: &str, decoding_key: Vec<String>) -> u32 {
 
     let screen_readout = reading
         .split('|')
-        .last()
+        .next_back()
         .unwrap()
         .split_whitespace()
         .map(String::from)
@@ -95,7 +91,9 @@ fn decode_reading(reading: String, decoding_key: Vec<String>) -> u32 {
 }
 
 
-fn decode_segment_wiring(reading: &String) -> Vec<String> {
+/// Works out the segments of each digit from the signal patterns, or `None` if the patterns
+/// don't pin down one wire per segment.
+fn decode_segment_wiring(reading: &str) -> Option<Vec<String>> {
     // Create hashset for each of the segment and initialise with full spread of chars
     let segment_wiring = ['a', 'b', 'c', 'd', 'e', 'f', 'g'];
 
@@ -205,18 +203,18 @@ fn decode_segment_wiring(reading: &String) -> Vec<String> {
             7 => {
                 // 8 Digit - Not informative. Do nothing.
             }
-            _ => { panic!("Issue with digit wiring decoding") }
+            _ => return None,
         };
     }
 
     // extract segment wiring solution
-    let tr = top_right_segment.drain().next().unwrap();
-    let t = top_segment.drain().next().unwrap();
-    let tl = top_left_segment.drain().next().unwrap();
-    let m = middle_segment.drain().next().unwrap();
-    let br = bottom_right_segment.drain().next().unwrap();
-    let bl = bottom_left_segment.drain().next().unwrap();
-    let b = bottom_segment.drain().next().unwrap();
+    let tr = single_wire(top_right_segment)?;
+    let t = single_wire(top_segment)?;
+    let tl = single_wire(top_left_segment)?;
+    let m = single_wire(middle_segment)?;
+    let br = single_wire(bottom_right_segment)?;
+    let bl = single_wire(bottom_left_segment)?;
+    let b = single_wire(bottom_segment)?;
 
     // construct numbers using the decoded segment wiring, sort ascending and cast as a String
     let zero = vec![t, tl, tr, br, bl, b].into_iter().sorted().collect::<String>();
@@ -233,27 +231,91 @@ fn decode_segment_wiring(reading: &String) -> Vec<String> {
     // construct decoding key as a vector from 0 -> 9 in that order.
     let decoding_key = vec![zero, one, two, three, four, five, six, seven, eight, nine];
 
-    decoding_key
+    Some(decoding_key)
 }
 
 
-fn parse_input(file_path: &str) -> Vec<String> {
-    let contents = fs::read_to_string(file_path).expect("Input file local to project");
+/// The wire left for a segment, if exactly one is.
+fn single_wire(segment: HashSet<char>) -> Option<char> {
+    match segment.len() {
+        1 => segment.into_iter().next(),
+        _ => None,
+    }
+}
 
+
+pub fn parse_input(contents: &str) -> Result<Vec<String>, ParseError> {
     contents
         .lines()
-        .map(String::from)
+        .map(|line| {
+            validate_note(contents, line)?;
+            Ok(String::from(line))
+        })
         .collect()
 }
 
 
+/// Checks a note has 10 signal patterns and 4 output digits made from segments 'a' to 'g', and
+/// that the patterns are the ten digits wired up in some order, shown again by the outputs.
+fn validate_note(contents: &str, line: &str) -> Result<(), ParseError> {
+    let (patterns, outputs) = line
+        .split_once('|')
+        .ok_or_else(|| ParseError::at(contents, line, "expected `patterns | output`"))?;
+
+    for (section, expected) in [(patterns, 10), (outputs, 4)] {
+        let words = section.split_whitespace().collect::<Vec<_>>();
+
+        if words.len() != expected {
+            let message = format!("expected {} segment groups, found {}", expected, words.len());
+            return Err(ParseError::at(contents, section, message));
+        }
+
+        let invalid = words.iter().find(|word| !word.chars().all(|c| ('a'..='g').contains(&c)));
+
+        if let Some(word) = invalid {
+            return Err(ParseError::at(contents, word, "segments must be 'a' to 'g'"));
+        }
+    }
+
+    let sorted = |word: &str| word.chars().sorted().collect::<String>();
+    let digits = patterns.split_whitespace().map(sorted).collect::<HashSet<_>>();
+
+    let wired = decode_segment_wiring(line)
+        .is_some_and(|decoding_key| digits == decoding_key.into_iter().collect());
+    if !wired {
+        return Err(ParseError::at(contents, patterns, "signal patterns aren't the ten digits"));
+    }
+
+    let unknown = outputs.split_whitespace().find(|word| !digits.contains(&sorted(word)));
+    if let Some(word) = unknown {
+        return Err(ParseError::at(contents, word, "output isn't one of the signal patterns"));
+    }
+
+    Ok(())
+}
+
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    const PATTERNS: &str = "acedgfb cdfbe gcdfa fbcad dab cefabd cdfgeb eafb cagedb ab";
+
     #[test]
-    fn solved() {
-        assert_eq!(440, solve_first_star());
-        assert_eq!(1046281, solve_second_star());
+    fn parsing_errors() {
+        let note = format!("{} | cdfeb fcadb cdfeb cdbaf", PATTERNS);
+        assert_eq!(5353, solve_second_star(&parse_input(&note).unwrap()));
+
+        let error = parse_input(&format!("{} | cdfeb fcadb abc cdbaf", PATTERNS)).unwrap_err();
+        assert_eq!(74, error.column);
+        assert_eq!("output isn't one of the signal patterns", error.message);
+
+        for patterns in [PATTERNS.replace("ab", "a"), PATTERNS.replace("cagedb", "cefabd")] {
+            let note = format!("{} | cdfeb fcadb cdfeb cdbaf", patterns);
+
+            let error = parse_input(&note).unwrap_err();
+            assert_eq!(1, error.column);
+            assert_eq!("signal patterns aren't the ten digits", error.message);
+        }
     }
-}
\ No newline at end of file
+}
@@ -1,15 +1,13 @@
-use std::fs;
+use aoc_core::parse::{self, ParseError};
 
 
-pub fn solve_first_star() -> u32 {
-    let depths = parse_input("src/sonar_sweep/input.txt");
-
+pub fn solve_first_star(depths: &[u64]) -> u32 {
     let mut result = 0u32;
 
     // initialise previous depth as there is no previous measurement to compare against
     let mut prev_depth = depths[0];
 
-    for depth in depths {
+    for &depth in depths {
         if depth > prev_depth { result += 1; }
 
         prev_depth = depth;
@@ -19,16 +17,15 @@ pub fn solve_first_star() -> u32 {
 }
 
 
-pub fn solve_second_star() -> u32 {
-    let depths = parse_input("src/sonar_sweep/input.txt");
-
+pub fn solve_second_star(depths: &[u64]) -> u32 {
     let mut result = 0u32;
 
     // set up sliding window of 3 measurements
-    let mut depths_window = depths.windows(3);
+    let mut depths_window = depths.windows(WINDOW);
 
     // initialise previous avg depth as there is no previous measurement to compare against
-    let mut prev_avg_depth: u64 = depths_window.next().unwrap().iter().sum();
+    let first_window = depths_window.next().expect("parsing checks for a window");
+    let mut prev_avg_depth: u64 = first_window.iter().sum();
 
     for new_readings in depths_window {
         let new_avg_depth = new_readings.iter().sum();
@@ -42,16 +39,23 @@ pub fn solve_second_star() -> u32 {
 }
 
 
-fn parse_input(file_path: &str) -> Vec<u64> {
-    let contents = fs::read_to_string(file_path).expect("Input file local to project");
+/// Depths needed for one window of the second star.
+const WINDOW: usize = 3;
 
-    let result = contents
+
+pub fn parse_input(contents: &str) -> Result<Vec<u64>, ParseError> {
+    let depths = contents
         .lines()
         .filter(|s| !s.is_empty())
-        .map(|x| x.parse::<u64>().unwrap())
-        .collect();
+        .map(|x| parse::number(contents, x))
+        .collect::<Result<Vec<_>, _>>()?;
 
-    result
+    if depths.len() < WINDOW {
+        let message = format!("expected at least {} depths, found {}", WINDOW, depths.len());
+        return Err(ParseError::end_of_input(contents, message));
+    }
+
+    Ok(depths)
 }
 
 
@@ -60,8 +64,11 @@ mod tests {
     use super::*;
 
     #[test]
-    fn solved() {
-        assert_eq!(1195, solve_first_star());
-        assert_eq!(1235, solve_second_star());
+    fn parsing_errors() {
+        assert_eq!(Ok(vec![199, 200, 208]), parse_input("199\n200\n208\n"));
+
+        let error = parse_input("199\n200\n").unwrap_err();
+        assert_eq!("expected at least 3 depths, found 2", error.message);
+        assert!(parse_input("").is_err());
     }
-}
\ No newline at end of file
+}
@@ -1,12 +1,55 @@
-use crate::syntax_scoring::solve::{solve_first_star, solve_second_star};
+use aoc_core::{Answer, ParseError, Solver};
+use crate::syntax_scoring::solve::{parse_input, solve_first_star, solve_second_star};
+use crate::YEAR;
 
 mod solve;
 
-pub fn syntax_scoring_solution() {
-    println!("--- Day 10: Syntax Scoring ---");
-    let result = solve_first_star();
-    println!("\t1st Star Solution = {}", result);
+pub struct SyntaxScoring;
 
-    let result = solve_second_star();
-    println!("\t2nd Star Solution = {}", result);
-}
\ No newline at end of file
+impl Solver for SyntaxScoring {
+    type Input = Vec<String>;
+
+    fn year(&self) -> u32 {
+        YEAR
+    }
+
+    fn day(&self) -> u32 {
+        10
+    }
+
+    fn title(&self) -> &'static str {
+        "Syntax Scoring"
+    }
+
+    fn input_path(&self) -> &'static str {
+        concat!(env!("CARGO_MANIFEST_DIR"), "/src/syntax_scoring/input.txt")
+    }
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Result<Answer, String> {
+        Ok(solve_first_star(input).into())
+    }
+
+    fn part2(&self, input: &Self::Input) -> Result<Answer, String> {
+        solve_second_star(input).map(Answer::from)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_core::verify;
+
+    #[test]
+    fn solved() {
+        verify::assert_solved(SyntaxScoring);
+    }
+
+    aoc_core::examples!(SyntaxScoring, {
+        example: "test_input.txt",
+    });
+}
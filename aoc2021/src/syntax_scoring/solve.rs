@@ -1,27 +1,23 @@
-use std::fs;
-use std::ops::Index;
+use aoc_core::ParseError;
 
 
-pub fn solve_first_star() -> u64 {
-    let replies = parse_input("src/syntax_scoring/input.txt");
-
+pub fn solve_first_star(replies: &[String]) -> u64 {
 
     let (error_score, _) = parse_syntax_scoring(replies);
 
     error_score
 }
 
-pub fn solve_second_star() -> u64 {
-    let replies = parse_input("src/syntax_scoring/input.txt");
-
+pub fn solve_second_star(replies: &[String]) -> Result<u64, String> {
 
     let (_, incomplete_score) = parse_syntax_scoring(replies);
 
-    incomplete_score
+    incomplete_score.ok_or_else(|| "no incomplete lines to score".to_string())
 }
 
 
-fn parse_syntax_scoring(replies: Vec<String>) -> (u64, u64) {
+/// Scores the corrupted lines, and the median score of the incomplete lines if there are any.
+fn parse_syntax_scoring(replies: &[String]) -> (u64, Option<u64>) {
     let mut square_errors = 0;
     let mut parentheses_errors = 0;
     let mut curly_errors = 0;
@@ -69,7 +65,7 @@ fn parse_syntax_scoring(replies: Vec<String>) -> (u64, u64) {
                                 corrupted = true
                             }
                         }
-                        _ => { panic!("Error, unknown input in tokens") }
+                        _ => unreachable!("brackets are checked when parsed"),
                     }
                 } else {
                     // Closing bracket with no opening bracket - Not encountered in input.txt
@@ -78,7 +74,7 @@ fn parse_syntax_scoring(replies: Vec<String>) -> (u64, u64) {
                         ')' => parentheses_errors += 1,
                         '}' => curly_errors += 1,
                         '>' => angle_errors += 1,
-                        _ => { panic!("Error, unknown input in tokens") }
+                        _ => unreachable!("brackets are checked when parsed"),
                     }
                 }
             }
@@ -92,7 +88,7 @@ fn parse_syntax_scoring(replies: Vec<String>) -> (u64, u64) {
                     '[' => incomplete_score = incomplete_score * 5 + 2,
                     '{' => incomplete_score = incomplete_score * 5 + 3,
                     '<' => incomplete_score = incomplete_score * 5 + 4,
-                    _ => { panic!("Error, unknown input in tokens") }
+                    _ => unreachable!("brackets are checked when parsed"),
                 }
             }
 
@@ -115,14 +111,18 @@ fn parse_syntax_scoring(replies: Vec<String>) -> (u64, u64) {
     // Sort incomplete sorts so the median can be returned
     incomplete_scores.sort();
 
-    (error_score, *incomplete_scores.index(incomplete_scores.len() / 2))
+    (error_score, incomplete_scores.get(incomplete_scores.len() / 2).copied())
 }
 
 
-fn parse_input(file_path: &str) -> Vec<String> {
-    let contents = fs::read_to_string(file_path).expect("Input file local to project");
-
-    contents.lines().map(String::from).collect::<Vec<_>>()
+pub fn parse_input(contents: &str) -> Result<Vec<String>, ParseError> {
+    contents
+        .lines()
+        .map(|line| match line.find(|c| !"()[]{}<>".contains(c)) {
+            Some(i) => Err(ParseError::at(contents, &line[i..], "expected a bracket")),
+            None => Ok(String::from(line)),
+        })
+        .collect()
 }
 
 
@@ -131,8 +131,13 @@ mod tests {
     use super::*;
 
     #[test]
-    fn solved() {
-        assert_eq!(311895, solve_first_star());
-        assert_eq!(2904180541, solve_second_star());
+    fn scoring() {
+        let replies = parse_input("{([(<{}[<>[]}>{[]{[(<()>\n[[<[([]))<([[{}[[()]]]").unwrap();
+        assert_eq!(1197 + 3, solve_first_star(&replies));
+        assert_eq!(Err("no incomplete lines to score".to_string()), solve_second_star(&replies));
+
+        let error = parse_input("[({(<(())[]>[[{[]{<()<>>\n[(()[<>])]({[<{<<[]>>(x").unwrap_err();
+        assert_eq!((2, 23), (error.line, error.column));
+        assert_eq!("expected a bracket", error.message);
     }
-}
\ No newline at end of file
+}
@@ -1,12 +1,68 @@
-use crate::transparent_origami::solve::{solve_first_star, solve_second_star};
+use aoc_core::{Answer, ParseError, Solver};
+use crate::transparent_origami::solve::{parse_input, solve_first_star, solve_second_star};
+use crate::YEAR;
 
 mod solve;
 
-pub fn transparent_origami_solution() {
-    println!("--- Day 13: Transparent Origami ---");
-    let result = solve_first_star();
-    println!("\t1st Star Solution = {}", result);
+pub struct TransparentOrigami;
 
-    let result = solve_second_star();
-    println!("\t2nd Star Solution = {}", result);
-}
\ No newline at end of file
+impl Solver for TransparentOrigami {
+    type Input = solve::Origami;
+
+    fn year(&self) -> u32 {
+        YEAR
+    }
+
+    fn day(&self) -> u32 {
+        13
+    }
+
+    fn title(&self) -> &'static str {
+        "Transparent Origami"
+    }
+
+    fn input_path(&self) -> &'static str {
+        concat!(env!("CARGO_MANIFEST_DIR"), "/src/transparent_origami/input.txt")
+    }
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Result<Answer, String> {
+        Ok(solve_first_star(input).into())
+    }
+
+    fn part2(&self, input: &Self::Input) -> Result<Answer, String> {
+        solve_second_star(input)
+            .map(Answer::from)
+            .map_err(|err| format!("couldn't read the activation code, {}", err))
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_core::verify;
+
+    #[test]
+    fn solved() {
+        verify::assert_solved(TransparentOrigami);
+    }
+
+    #[test]
+    fn unreadable_code() {
+        let input = parse_input(include_str!("test_input.txt")).unwrap();
+
+        let error = TransparentOrigami.part2(&input).unwrap_err();
+        assert_eq!(
+            "couldn't read the activation code, can't read letters 7 pixels high, expected 6 or 10",
+            error
+        );
+    }
+
+    aoc_core::examples!(TransparentOrigami, {
+        example: "test_input.txt",
+    });
+}
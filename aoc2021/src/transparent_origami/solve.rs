@@ -1,39 +1,43 @@
 use std::collections::VecDeque;
-use std::fs;
+use aoc_core::ocr::{self, OcrError};
+use aoc_core::parse::{self, ParseError};
 use ndarray::{Array2, Axis, s};
 
 
-pub fn solve_first_star() -> u64 {
-    let (paper, instructions) = parse_input("src/transparent_origami/input.txt");
+/// Transparent paper with dots, and the fold instructions.
+pub type Origami = (Array2<bool>, Vec<Fold>);
 
-    let folded_paper = fold_paper(paper, *instructions.first().unwrap());
 
-    folded_paper.iter().filter(|&x| *x).count() as u64
+/// A fold instruction, folding along the column `x` or the row `y` down the middle of the paper.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Fold {
+    X(usize),
+    Y(usize),
 }
 
 
-pub fn solve_second_star() -> &'static str {
-    let (paper, instructions) = parse_input("src/transparent_origami/input.txt");
+pub fn solve_first_star((paper, instructions): &Origami) -> u64 {
+    // parsing checks there are folds along both axes
+    let folded_paper = fold_paper(paper.clone(), instructions[0]);
+
+    folded_paper.iter().filter(|&x| *x).count() as u64
+}
+
 
-    let mut folded_paper = paper;
+pub fn solve_second_star((paper, instructions): &Origami) -> Result<String, OcrError> {
+    let mut folded_paper = paper.clone();
 
-    for instruction in instructions {
+    for &instruction in instructions {
         folded_paper = fold_paper(folded_paper, instruction);
     }
 
-    // To declutter printed output, disable behind feature flag
-    #[cfg(feature = "print_long_ans")]
-    println!("Origami = \n{:?}", folded_paper.map(|x| if *x {'#'} else {'.'}));
-
-    // reviewing the printed output from the folded_paper we get the following code
-    "FPEKBEJL"
+    // the folded paper shows the activation code in capital letters
+    ocr::recognise(&folded_paper)
 }
 
-fn fold_paper(paper: Array2<bool>, instruction: (char, usize)) -> Array2<bool> {
-    match instruction.0 {
-        'x' => {
-
-            let fold_col = instruction.1;
+fn fold_paper(paper: Array2<bool>, instruction: Fold) -> Array2<bool> {
+    match instruction {
+        Fold::X(fold_col) => {
 
             // divide paper into two halves along the fold crease
             let mut left_side = paper.slice(s![.., ..fold_col]).to_owned();
@@ -47,9 +51,7 @@ fn fold_paper(paper: Array2<bool>, instruction: (char, usize)) -> Array2<bool> {
 
             left_side
         }
-        'y' => {
-
-            let fold_row = instruction.1;
+        Fold::Y(fold_row) => {
 
             // divide paper into two halves along the fold crease
             let mut top_side = paper.slice(s![..fold_row, ..]).to_owned();
@@ -63,14 +65,11 @@ fn fold_paper(paper: Array2<bool>, instruction: (char, usize)) -> Array2<bool> {
 
             top_side
         }
-        _ => { panic!("Error in folding instructions") }
     }
 }
 
 
-fn parse_input(file_path: &str) -> (Array2<bool>, Vec<(char, usize)>) {
-    let contents = fs::read_to_string(file_path).expect("Input file local to project");
-
+pub fn parse_input(contents: &str) -> Result<Origami, ParseError> {
     let mut lines = contents.lines().collect::<VecDeque<_>>();
 
     let mut points = Vec::<(usize, usize)>::new();
@@ -81,50 +80,73 @@ fn parse_input(file_path: &str) -> (Array2<bool>, Vec<(char, usize)>) {
             "" => { break; }
             _ => {
                 // add point to paper
-                let mut point_location = input.split(',');
-
-                let col = point_location.next().unwrap().parse::<usize>().unwrap();
-                let row = point_location.next().unwrap().parse::<usize>().unwrap();
+                let (col, row) = input
+                    .split_once(',')
+                    .ok_or_else(|| ParseError::at(contents, input, "expected `x,y`"))?;
 
-                points.push((row, col));
+                points.push((parse::number(contents, row)?, parse::number(contents, col)?));
             }
         }
     }
 
     // construct fold instructions
-    let mut instructions = Vec::<(char, usize)>::new();
+    let mut instructions = Vec::<(Fold, &str)>::new();
 
     while let Some(input) = lines.pop_front() {
-        let mut fold_text = input.split('=');
+        let fold_text = input
+            .strip_prefix("fold along ")
+            .ok_or_else(|| ParseError::at(contents, input, "expected `fold along`"))?;
 
-        let fold_axis = fold_text.next().unwrap().chars().last().unwrap();
+        let fold_instruction = match fold_text.split_once('=') {
+            Some(("x", fold_line)) => Fold::X(parse::number(contents, fold_line)?),
+            Some(("y", fold_line)) => Fold::Y(parse::number(contents, fold_line)?),
+            _ => return Err(ParseError::at(contents, fold_text, "expected `x=` or `y=`")),
+        };
 
-        let fold_line = fold_text.next().unwrap().parse::<usize>().unwrap();
-
-        instructions.push((fold_axis, fold_line));
+        instructions.push((fold_instruction, input));
     }
 
     // calculate paper size from first x and y fold instruction
-    let (_, first_x_fold) = instructions
-        .iter().find(|(axis, _)| { *axis == 'x' })
-        .unwrap();
+    let first_x_fold = instructions
+        .iter().find_map(|&(fold, _)| match fold { Fold::X(col) => Some(col), _ => None })
+        .ok_or_else(|| ParseError::end_of_input(contents, "missing fold along x"))?;
 
-    let (_, first_y_fold) = instructions
-        .iter().find(|(axis, _)| { *axis == 'y' })
-        .unwrap();
+    let first_y_fold = instructions
+        .iter().find_map(|&(fold, _)| match fold { Fold::Y(row) => Some(row), _ => None })
+        .ok_or_else(|| ParseError::end_of_input(contents, "missing fold along y"))?;
 
     let paper_width = first_x_fold * 2 + 1;
 
     let paper_height = first_y_fold * 2 + 1;
 
+    // every fold has to fold the paper in half, for the halves to line up
+    let (mut width, mut height) = (paper_width, paper_height);
+
+    for &(fold, input) in &instructions {
+        let (line, size) = match fold {
+            Fold::X(col) => (col, &mut width),
+            Fold::Y(row) => (row, &mut height),
+        };
+
+        if line * 2 + 1 != *size {
+            return Err(ParseError::at(contents, input, "fold isn't down the middle of the paper"));
+        }
+        *size = line;
+    }
+
     // construct origami paper from points
     let mut paper = Array2::<bool>::from_elem((paper_height, paper_width), false);
 
     for (row, col) in points {
+        if row >= paper_height || col >= paper_width {
+            let message = format!("dot {},{} is outside the paper", col, row);
+            return Err(ParseError::end_of_input(contents, message));
+        }
+
         paper[[row, col]] = true;
     }
 
-    (paper, instructions)
+    Ok((paper, instructions.into_iter().map(|(fold, _)| fold).collect()))
 }
 
 
@@ -133,8 +155,17 @@ mod tests {
     use super::*;
 
     #[test]
-    fn solved() {
-        assert_eq!(701, solve_first_star());
-        assert_eq!("FPEKBEJL", solve_second_star());
+    fn parsing_errors() {
+        let (paper, instructions) = parse_input("0,0\n\nfold along y=3\nfold along x=2").unwrap();
+        assert_eq!((7, 5), paper.dim());
+        assert_eq!(vec![Fold::Y(3), Fold::X(2)], instructions);
+
+        let folds = "fold along y=3\nfold along x=2\nfold along y=2";
+        let error = parse_input(&format!("0,0\n\n{}", folds)).unwrap_err();
+        assert_eq!((5, 1), (error.line, error.column));
+        assert_eq!("fold isn't down the middle of the paper", error.message);
+
+        let error = parse_input("0,0\n\nfold along y=3").unwrap_err();
+        assert_eq!("missing fold along x", error.message);
     }
-}
\ No newline at end of file
+}
@@ -1,17 +1,12 @@
-use std::fs;
 use std::ops::{Index};
+use aoc_core::parse::{self, ParseError};
 use good_lp::{variables, variable, default_solver, SolverModel, Expression, Variable, constraint, Solution};
 
-pub fn solve_first_star() -> u64 {
-    let positions = parse_input("src/treachery_of_whales/input.txt");
-
+pub fn solve_first_star(positions: &[u64]) -> u64 {
     // Problem is a linear programming optimisation problem.
     // Lets try solving it using a linear programming solver.
     // Need to minimise abs(pos_1 - final_pos) + abs(pos_2 - final_pos) + ... + abs(pos_n - final_pos)
-    // Because Linear Solver is a bit computationally intense, I've disabled it behind a feature
-    // flag.
-    #[cfg(feature = "run_solver")]
-    {
+    let final_pos = {
         // Set up variables for solver
         variables! {
         problem:
@@ -41,27 +36,22 @@ pub fn solve_first_star() -> u64 {
 
         let solution = unsolved_solution.solve().unwrap();
 
-        let final_pos = solution.value(final_pos) as u32;
-    }
-
-    // Having run the solver, we know the solution is final_pos = 328
-    let final_pos = 328u64;
+        solution.value(final_pos).round() as u64
+    };
 
     // Compute and return answer.
     positions.iter().map(|x| x.abs_diff(final_pos)).sum::<u64>()
 }
 
 
-pub fn solve_second_star() -> u64 {
-    let positions = parse_input("src/treachery_of_whales/input.txt");
-
+pub fn solve_second_star(positions: &[u64]) -> u64 {
     // Lets brute force the problem which has now become a non-linear optimisation problem
     let mut solutions = Vec::<u64>::new();
 
-    // Because we're brute forcing optimisation problem and its computationally intense,
-    // I've disabled it behind a feature flag
-    #[cfg(feature = "run_solver")]
-    for final_pos in 0u64..1000u64 {
+    // The best position must lie within the range of crab positions
+    let furthest_pos = positions.iter().max().copied().unwrap_or(0);
+
+    for final_pos in 0u64..=furthest_pos {
         let distance = positions.iter()
             .map(|pos| pos.abs_diff(final_pos))
             .collect::<Vec<_>>();
@@ -71,12 +61,7 @@ pub fn solve_second_star() -> u64 {
         solutions.push(solution);
     }
 
-    if let Some(result) = solutions.iter().min() {
-        *result
-    } else {
-        95476244 // Not brute forcing solution. Return the known answer.
-    }
-
+    *solutions.iter().min().unwrap()
 }
 
 fn calculate_fuel_cost(distance: u64) -> u64 {
@@ -86,23 +71,9 @@ fn calculate_fuel_cost(distance: u64) -> u64 {
 }
 
 
-fn parse_input(file_path: &str) -> Vec<u64> {
-    let contents = fs::read_to_string(file_path).expect("Input file local to project");
-
+pub fn parse_input(contents: &str) -> Result<Vec<u64>, ParseError> {
     contents.trim()
         .split(',')
-        .map(|x| x.parse::<u64>().unwrap())
+        .map(|x| parse::number(contents, x))
         .collect()
 }
-
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn solved() {
-        assert_eq!(339321, solve_first_star());
-        assert_eq!(95476244, solve_second_star());
-    }
-}
\ No newline at end of file
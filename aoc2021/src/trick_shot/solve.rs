@@ -1,17 +1,22 @@
+use std::ops::RangeInclusive;
 use std::sync::mpsc::channel;
-use itertools::{Itertools, max};
+use aoc_core::ParseError;
+use nom::bytes::complete::tag;
+use nom::character::complete;
+use nom::IResult;
+use nom::sequence::separated_pair;
 use rayon::prelude::*;
 
 
-pub fn solve_first_star() -> i32 {
-    let initial_velocities = generate_initial_velocities();
+pub fn solve_first_star(target: &TargetArea) -> i32 {
+    let initial_velocities = generate_initial_velocities(target);
 
     // Set up channel to receive results from parallel iterator
     let (sender, receiver) = channel();
 
     // Run simulations in parallel using Rayon
     initial_velocities.par_iter().for_each_with(sender, |s, (x_dot_init, y_dot_init)| {
-        let (max_y, status) = run_simulation(x_dot_init, y_dot_init);
+        let (max_y, status) = run_simulation(x_dot_init, y_dot_init, target);
 
         // send result if objective met
         match status {
@@ -29,21 +34,21 @@ pub fn solve_first_star() -> i32 {
         .iter()
         .max_by(|(_, _, a), (_, _, b)| a.cmp(b));
 
-    let (x_init, y_init, y_max) = solution.unwrap();
+    let (_, _, y_max) = solution.unwrap();
 
     *y_max
 }
 
 
-pub fn solve_second_star() -> usize {
-    let initial_velocities = generate_initial_velocities();
+pub fn solve_second_star(target: &TargetArea) -> usize {
+    let initial_velocities = generate_initial_velocities(target);
 
     // Set up channel to receive results from parallel iterator
     let (sender, receiver) = channel();
 
     // Run simulations in parallel using Rayon
     initial_velocities.par_iter().for_each_with(sender, |s, (x_dot_init, y_dot_init)| {
-        let (max_y, status) = run_simulation(x_dot_init, y_dot_init);
+        let (max_y, status) = run_simulation(x_dot_init, y_dot_init, target);
 
         // send result if objective met
         match status {
@@ -61,6 +66,14 @@ pub fn solve_second_star() -> usize {
 }
 
 
+/// Target area the probe must land in, as inclusive x and y ranges.
+#[derive(PartialEq, Debug, Clone)]
+pub struct TargetArea {
+    x: RangeInclusive<i32>,
+    y: RangeInclusive<i32>,
+}
+
+
 #[derive(PartialEq, Debug)]
 enum ObjectiveStatus {
     Impossible,
@@ -69,11 +82,18 @@ enum ObjectiveStatus {
 }
 
 
-fn generate_initial_velocities() -> Vec<(i32, i32)> {
+/// Generates candidate launch velocities for a target area ahead of and below the probe.
+///
+/// Faster horizontal launches overshoot the far edge of the target on the first step. A probe
+/// launched upwards returns to y = 0 with its launch speed plus one, so faster vertical launches
+/// overshoot the bottom of the target on the following step.
+fn generate_initial_velocities(target: &TargetArea) -> Vec<(i32, i32)> {
     let mut initial_velocities = Vec::<(i32, i32)>::new();
 
-    for x_dot_init in 0..200 {
-        for y_dot_init in -200..500 {
+    let y_dot_max = target.y.start().abs();
+
+    for x_dot_init in 0..=*target.x.end() {
+        for y_dot_init in *target.y.start()..=y_dot_max {
             initial_velocities.push((x_dot_init, y_dot_init));
         }
     }
@@ -82,7 +102,7 @@ fn generate_initial_velocities() -> Vec<(i32, i32)> {
 }
 
 
-fn run_simulation(x_dot_init: &i32, y_dot_init: &i32) -> (i32, ObjectiveStatus) {
+fn run_simulation(x_dot_init: &i32, y_dot_init: &i32, target: &TargetArea) -> (i32, ObjectiveStatus) {
     // initialise simulation
     let mut x_dot = *x_dot_init;
     let mut y_dot = *y_dot_init;
@@ -94,12 +114,12 @@ fn run_simulation(x_dot_init: &i32, y_dot_init: &i32) -> (i32, ObjectiveStatus)
 
     let mut status = ObjectiveStatus::Incomplete;
 
-    while check_objective(x, y, x_dot, y_dot) == ObjectiveStatus::Incomplete {
+    while check_objective(x, y, x_dot, y_dot, target) == ObjectiveStatus::Incomplete {
         (x, y, x_dot, y_dot) = particle_simulation_step(x, y, x_dot, y_dot);
 
         max_y = max_y.max(y);
 
-        status = check_objective(x, y, x_dot, y_dot);
+        status = check_objective(x, y, x_dot, y_dot, target);
     }
     (max_y, status)
 }
@@ -132,17 +152,17 @@ fn particle_simulation_step(x: i32, y: i32, x_dot: i32, y_dot: i32) -> (i32, i32
 }
 
 
-fn check_objective(x: i32, y: i32, x_dot: i32, y_dot: i32) -> ObjectiveStatus {
-    let x_objective = 150..193 + 1;
-    let y_objective = -136..-86 + 1;
+fn check_objective(x: i32, y: i32, x_dot: i32, y_dot: i32, target: &TargetArea) -> ObjectiveStatus {
+    let x_objective = &target.x;
+    let y_objective = &target.y;
 
     if x_objective.contains(&x) && y_objective.contains(&y) {
         return ObjectiveStatus::Complete;
     }
 
-    let y_min = y_objective.min().unwrap();
-    let x_min = x_objective.clone().min().unwrap();
-    let x_max = x_objective.clone().max().unwrap();
+    let y_min = *y_objective.start();
+    let x_min = *x_objective.start();
+    let x_max = *x_objective.end();
 
     // check if objective is impossible - this is incorrect
     if x_dot.is_negative() && x < x_min { return ObjectiveStatus::Impossible; }
@@ -155,21 +175,47 @@ fn check_objective(x: i32, y: i32, x_dot: i32, y_dot: i32) -> ObjectiveStatus {
 }
 
 
+fn parse_range(input: &str) -> IResult<&str, RangeInclusive<i32>> {
+    let (rest, (start, end)) = separated_pair(complete::i32, tag(".."), complete::i32)(input)?;
+
+    Ok((rest, start..=end))
+}
+
+
+fn parse_target_area(input: &str) -> IResult<&str, TargetArea> {
+    let (rest, _) = tag("target area: x=")(input)?;
+    let (rest, x) = parse_range(rest)?;
+    let (rest, _) = tag(", y=")(rest)?;
+    let (rest, y) = parse_range(rest)?;
+
+    Ok((rest, TargetArea { x, y }))
+}
+
+
+pub fn parse_input(contents: &str) -> Result<TargetArea, ParseError> {
+    let (_, target) = parse_target_area(contents.trim())
+        .map_err(|err| ParseError::from_nom(contents, err))?;
+
+    Ok(target)
+}
+
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
-    fn solved() {
-        assert_eq!(9180, solve_first_star());
-        assert_eq!(3767, solve_second_star());
+    fn parsing_target_area() {
+        assert_eq!(Ok(("", TargetArea { x: 150..=193, y: -136..=-86 })),
+                   parse_target_area("target area: x=150..193, y=-136..-86"));
     }
 
     #[test]
     fn objective_test() {
-        assert_eq!(ObjectiveStatus::Incomplete, check_objective(0, 0, 24, 135));
-        assert_eq!(ObjectiveStatus::Impossible, check_objective(140, 200, 0, 135));
-        assert_eq!(ObjectiveStatus::Incomplete, check_objective(140, 200, 1, 135));
-        assert_eq!(ObjectiveStatus::Complete, check_objective(157, -132, 1, 135));
+        let target = TargetArea { x: 150..=193, y: -136..=-86 };
+        assert_eq!(ObjectiveStatus::Incomplete, check_objective(0, 0, 24, 135, &target));
+        assert_eq!(ObjectiveStatus::Impossible, check_objective(140, 200, 0, 135, &target));
+        assert_eq!(ObjectiveStatus::Incomplete, check_objective(140, 200, 1, 135, &target));
+        assert_eq!(ObjectiveStatus::Complete, check_objective(157, -132, 1, 135, &target));
     }
 }
\ No newline at end of file
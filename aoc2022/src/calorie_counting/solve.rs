@@ -1,15 +1,13 @@
-use std::fs;
+use aoc_core::parse::{self, ParseError};
 
 
-pub fn solve_first_star() -> u64 {
-    let calories = parse_input("src/calorie_counting/input.txt");
-
-    calories.into_iter().max().unwrap()
+pub fn solve_first_star(calories: &[u64]) -> u64 {
+    *calories.iter().max().expect("parsing checks for elves")
 }
 
 
-pub fn solve_second_star() -> u64 {
-    let mut calories = parse_input("src/calorie_counting/input.txt");
+pub fn solve_second_star(calories: &[u64]) -> u64 {
+    let mut calories = calories.to_vec();
 
     calories.sort();
 
@@ -18,9 +16,7 @@ pub fn solve_second_star() -> u64 {
 
 /// parse input, accumulating the calories on each elf and returning a vector entry per elf
 /// with their carried calories
-fn parse_input(file_path: &str) -> Vec<u64> {
-    let contents = fs::read_to_string(file_path).expect("Input file local to project");
-
+pub fn parse_input(contents: &str) -> Result<Vec<u64>, ParseError> {
     let lines = contents
         .lines();
 
@@ -35,11 +31,20 @@ fn parse_input(file_path: &str) -> Vec<u64> {
             result.push(acc);
             acc = 0;
         } else {
-            acc += line.parse::<u64>().unwrap();
+            acc += parse::number::<u64>(contents, line)?;
         }
     }
 
-    result
+    // the last elf isn't followed by a blank line
+    if contents.lines().last().is_some_and(|line| !line.is_empty()) {
+        result.push(acc);
+    }
+
+    if result.is_empty() {
+        return Err(ParseError::end_of_input(contents, "expected the calories of at least one elf"));
+    }
+
+    Ok(result)
 }
 
 
@@ -48,8 +53,13 @@ mod tests {
     use super::*;
 
     #[test]
-    fn solved() {
-        assert_eq!(71471, solve_first_star());
-        assert_eq!(211189, solve_second_star());
+    fn parsing_errors() {
+        assert_eq!(Ok(vec![3000, 4000]), parse_input("1000\n2000\n\n4000\n"));
+
+        let error = parse_input("").unwrap_err();
+        assert_eq!("expected the calories of at least one elf", error.message);
+
+        let error = parse_input("1000\n2kg").unwrap_err();
+        assert_eq!((2, 1), (error.line, error.column));
     }
-}
\ No newline at end of file
+}
@@ -1,19 +1,21 @@
 use std::collections::{HashSet};
-use std::fs;
+use aoc_core::ParseError;
 use nom::bytes::complete::{is_not, tag};
 use nom::character::complete::{char, digit1};
-use nom::error::Error;
 use nom::combinator::{map, map_res, rest};
 use nom::IResult;
 use nom::sequence::separated_pair;
 
-pub fn solve_first_star() -> u64 {
-    let assignment_pairs = parse_input("src/camp_cleanup/input.txt");
 
+/// Section assignments of a pair of elves
+pub type AssignmentPair = (HashSet<u32>, HashSet<u32>);
+
+
+pub fn solve_first_star(assignment_pairs: &[AssignmentPair]) -> u64 {
     let mut redundant_assignment = 0;
 
     for (assignment_1, assignment_2) in assignment_pairs {
-        let overlap = assignment_1.intersection(&assignment_2).count();
+        let overlap = assignment_1.intersection(assignment_2).count();
 
         if overlap == assignment_1.len() || overlap == assignment_2.len() {
             redundant_assignment += 1;
@@ -24,13 +26,11 @@ pub fn solve_first_star() -> u64 {
 }
 
 
-pub fn solve_second_star() -> u64 {
-    let assignment_pairs = parse_input("src/camp_cleanup/input.txt");
-
+pub fn solve_second_star(assignment_pairs: &[AssignmentPair]) -> u64 {
     let mut inefficient_assignment = 0;
 
     for (assignment_1, assignment_2) in assignment_pairs {
-        let overlap = assignment_1.intersection(&assignment_2).count();
+        let overlap = assignment_1.intersection(assignment_2).count();
 
         if overlap != 0 {
             inefficient_assignment += 1;
@@ -62,35 +62,29 @@ fn get_assignments(input: &str) -> IResult<&str, (&str, &str)> {
 }
 
 
-fn parse_input(file_path: &str) -> Vec<(HashSet<u32>, HashSet<u32>)> {
-    let contents = fs::read_to_string(file_path).expect("Input file local to project");
+pub fn parse_input(contents: &str) -> Result<Vec<AssignmentPair>, ParseError> {
+    let mut result = Vec::<AssignmentPair>::new();
 
-    let mut result = Vec::<(HashSet<u32>, HashSet<u32>)>::new();
+    let parse_error = |err| ParseError::from_nom(contents, err);
 
     for line in contents.lines() {
-        if let Ok((_, (first, second))) = get_assignments(line) {
-            let (_, assignment_1) = parse_assignment(first).unwrap();
-            let (_, assignment_2) = parse_assignment(second).unwrap();
+        let (_, (first, second)) = get_assignments(line).map_err(parse_error)?;
+        let (_, assignment_1) = parse_assignment(first).map_err(parse_error)?;
+        let (_, assignment_2) = parse_assignment(second).map_err(parse_error)?;
 
-            result.push((assignment_1, assignment_2));
-        }
+        result.push((assignment_1, assignment_2));
     }
 
-    result
+    Ok(result)
 }
 
 
 #[cfg(test)]
 mod tests {
     use nom::combinator::rest;
+    use nom::error::Error;
     use super::*;
 
-    #[test]
-    fn solved() {
-        assert_eq!(494, solve_first_star());
-        assert_eq!(833, solve_second_star());
-    }
-
     #[test]
     fn parsing_assignment() {
         let mut parser = separated_pair::<_, _, _, _, Error<_>, _, _, _>(is_not(","), tag(","), rest);
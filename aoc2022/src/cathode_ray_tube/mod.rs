@@ -1,12 +1,57 @@
-use crate::cathode_ray_tube::solve::{solve_first_star, solve_second_star};
+use aoc_core::{Answer, ParseError, Solver};
+use crate::cathode_ray_tube::solve::{Instruction, parse_input, solve_first_star, solve_second_star};
+use crate::YEAR;
 
 mod solve;
 
-pub fn cathode_ray_tube_solution() {
-    println!("--- Day 10: Cathode-Ray Tube ---");
-    let result = solve_first_star();
-    println!("\t1st Star Solution = {}", result);
+pub struct CathodeRayTube;
 
-    let result = solve_second_star();
-    println!("\t2nd Star Solution = {}", result);
-}
\ No newline at end of file
+impl Solver for CathodeRayTube {
+    type Input = Vec<Instruction>;
+
+    fn year(&self) -> u32 {
+        YEAR
+    }
+
+    fn day(&self) -> u32 {
+        10
+    }
+
+    fn title(&self) -> &'static str {
+        "Cathode-Ray Tube"
+    }
+
+    fn input_path(&self) -> &'static str {
+        concat!(env!("CARGO_MANIFEST_DIR"), "/src/cathode_ray_tube/input.txt")
+    }
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Result<Answer, String> {
+        Ok(solve_first_star(input).into())
+    }
+
+    fn part2(&self, input: &Self::Input) -> Result<Answer, String> {
+        solve_second_star(input)
+            .map(Answer::from)
+            .map_err(|err| format!("couldn't read the CRT letters, {}", err))
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_core::verify;
+
+    #[test]
+    fn solved() {
+        verify::assert_solved(CathodeRayTube);
+    }
+
+    aoc_core::examples!(CathodeRayTube, {
+        example: "test_input.txt",
+    });
+}
@@ -1,4 +1,6 @@
-use std::fs;
+use aoc_core::ocr::{self, OcrError};
+use aoc_core::ParseError;
+use ndarray::Array2;
 use nom::branch::alt;
 use nom::bytes::complete::tag;
 use nom::character::complete::{space0, space1};
@@ -6,9 +8,7 @@ use nom::character::*;
 use nom::IResult;
 
 
-pub fn solve_first_star() -> i32 {
-    let instructions = parse_input("src/cathode_ray_tube/input.txt");
-
+pub fn solve_first_star(instructions: &[Instruction]) -> i32 {
     let mut cpu = Cpu::default();
 
     let mut result = Vec::<i32>::new();
@@ -23,25 +23,19 @@ pub fn solve_first_star() -> i32 {
 }
 
 
-pub fn solve_second_star() -> String {
-    let instructions = parse_input("src/cathode_ray_tube/input.txt");
-
+pub fn solve_second_star(instructions: &[Instruction]) -> Result<String, OcrError> {
     let mut cpu = Cpu::default();
 
     for instruction in instructions {
         cpu.process_instruction(instruction);
     }
 
-    // render crt screen
-    #[cfg(feature = "print_long_ans")]
-    cpu.crt.render();
-
-    // return known result
-    "ZKGRKGRK".to_string()
+    // read the capital letters drawn on the crt screen
+    ocr::recognise(&cpu.crt.pixels())
 }
 
 #[derive(Debug, Eq, PartialEq)]
-enum Instruction {
+pub enum Instruction {
     Noop,
     Addx(i32),
 }
@@ -67,35 +61,33 @@ impl Cpu {
         }
     }
 
-    fn process_instruction(&mut self, instr: Instruction) -> Option<i32> {
-        let mut result: Option<i32> = None;
-
+    fn process_instruction(&mut self, instr: &Instruction) -> Option<i32> {
         match instr {
             Instruction::Noop => {
                 self.cycle += 1;
                 self.crt.write_pixel(self.cycle, self.regx);
-                result = self.emit_signal_strength()
+                self.emit_signal_strength()
             }
-            Instruction::Addx(val) => {
+            &Instruction::Addx(val) => {
                 self.cycle += 1;
                 self.crt.write_pixel(self.cycle, self.regx);
-                result = self.emit_signal_strength();
+                let mut result = self.emit_signal_strength();
                 self.cycle += 1;
                 self.crt.write_pixel(self.cycle, self.regx);
                 if result.is_none() {
                     result = self.emit_signal_strength();
                 }
                 self.regx += val;
+
+                result
             }
         }
-
-        result
     }
 
     fn emit_signal_strength(&self) -> Option<i32> {
         if self.cycle < 20 {
             None
-        } else if (self.cycle - 20) % 40 == 0 {
+        } else if (self.cycle - 20).is_multiple_of(40) {
             // emit signal strength
             Some(self.cycle as i32 * self.regx)
         } else {
@@ -125,13 +117,9 @@ impl Crt {
         }
     }
 
-    fn render(&self) {
-        for i in 0..240 {
-            if i % 40 == 0 {
-                print!("\n");
-            }
-            print!("{}", self.screen[i]);
-        }
+    /// Returns the screen as 6 rows of 40 pixels, lit where the screen shows '#'.
+    fn pixels(&self) -> Array2<bool> {
+        Array2::from_shape_fn((6, 40), |(y, x)| self.screen[y * 40 + x] == '#')
     }
 }
 
@@ -155,55 +143,21 @@ fn parse_addx(input: &str) -> IResult<&str, Instruction> {
 }
 
 
-fn parse_instruction(input: &str) -> Instruction {
+fn parse_instruction(input: &str) -> Result<Instruction, nom::Err<nom::error::Error<&str>>> {
     let mut parser = alt((parse_noop, parse_addx));
 
-    let parsed_result = parser(input);
+    let (_, instruction) = parser(input)?;
 
-    match parsed_result {
-        Ok((_, instruction)) => { instruction }
-        _ => { panic!("Unrecognised Instruction") }
-    }
+    Ok(instruction)
 }
 
 
-fn parse_input(file_path: &str) -> Vec<Instruction> {
-    let content = fs::read_to_string(file_path).expect("Input file local to project");
-
+pub fn parse_input(content: &str) -> Result<Vec<Instruction>, ParseError> {
     let mut result = Vec::<Instruction>::new();
 
     for line in content.lines() {
-        result.push(parse_instruction(line));
+        result.push(parse_instruction(line).map_err(|err| ParseError::from_nom(content, err))?);
     }
 
-    result
+    Ok(result)
 }
-
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn solved() {
-        assert_eq!(13820, solve_first_star());
-        assert_eq!("ZKGRKGRK", solve_second_star());
-    }
-
-    #[test]
-    fn test_cpu() {
-        let instructions = parse_input("src/cathode_ray_tube/test_input.txt");
-
-        let mut cpu = Cpu::default();
-
-        let mut result = Vec::<i32>::new();
-
-        for instruction in instructions {
-            if let Some(signal_strength) = cpu.process_instruction(instruction) {
-                result.push(signal_strength);
-            }
-        }
-
-        assert_eq!(13140, result.iter().sum())
-    }
-}
\ No newline at end of file
@@ -0,0 +1,39 @@
+use aoc_core::Registry;
+
+use crate::calorie_counting::CalorieCounting;
+use crate::rock_paper_scissors::RockPaperScissors;
+use crate::rucksack_reorganisation::RucksackReorganisation;
+use crate::camp_cleanup::CampCleanup;
+use crate::supply_stacks::SupplyStacks;
+use crate::tuning_trouble::TuningTrouble;
+use crate::no_space_left::NoSpaceLeft;
+use crate::treetop_tree_house::TreetopTreeHouse;
+use crate::rope_bridge::RopeBridge;
+use crate::cathode_ray_tube::CathodeRayTube;
+
+mod calorie_counting;
+mod rock_paper_scissors;
+mod rucksack_reorganisation;
+mod camp_cleanup;
+mod supply_stacks;
+mod tuning_trouble;
+mod no_space_left;
+mod treetop_tree_house;
+mod rope_bridge;
+mod cathode_ray_tube;
+
+pub const YEAR: u32 = 2022;
+
+/// Registers every solved 2022 puzzle with the registry.
+pub fn register(registry: &mut Registry) {
+    registry.register(CalorieCounting);
+    registry.register(RockPaperScissors);
+    registry.register(RucksackReorganisation);
+    registry.register(CampCleanup);
+    registry.register(SupplyStacks);
+    registry.register(TuningTrouble);
+    registry.register(NoSpaceLeft);
+    registry.register(TreetopTreeHouse);
+    registry.register(RopeBridge);
+    registry.register(CathodeRayTube);
+}
@@ -1,12 +1,56 @@
-use crate::no_space_left::solve::{solve_first_star, solve_second_star};
+use std::collections::HashMap;
+use aoc_core::{Answer, ParseError, Solver};
+use crate::no_space_left::solve::{parse_input, solve_first_star, solve_second_star};
+use crate::YEAR;
 
 mod solve;
 
-pub fn no_space_left_solution() {
-    println!("--- Day 7: No Space Left On Device ---");
-    let result = solve_first_star();
-    println!("\t1st Star Solution = {}", result);
+pub struct NoSpaceLeft;
 
-    let result = solve_second_star();
-    println!("\t2nd Star Solution = {}", result);
-}
\ No newline at end of file
+impl Solver for NoSpaceLeft {
+    type Input = HashMap<String, usize>;
+
+    fn year(&self) -> u32 {
+        YEAR
+    }
+
+    fn day(&self) -> u32 {
+        7
+    }
+
+    fn title(&self) -> &'static str {
+        "No Space Left On Device"
+    }
+
+    fn input_path(&self) -> &'static str {
+        concat!(env!("CARGO_MANIFEST_DIR"), "/src/no_space_left/input.txt")
+    }
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Result<Answer, String> {
+        Ok(solve_first_star(input).into())
+    }
+
+    fn part2(&self, input: &Self::Input) -> Result<Answer, String> {
+        solve_second_star(input).map(Answer::from)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_core::verify;
+
+    #[test]
+    fn solved() {
+        verify::assert_solved(NoSpaceLeft);
+    }
+
+    aoc_core::examples!(NoSpaceLeft, {
+        example: "test_input.txt",
+    });
+}
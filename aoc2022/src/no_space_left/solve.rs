@@ -1,6 +1,6 @@
 use std::collections::{HashMap};
-use std::fs;
 use itertools::Itertools;
+use aoc_core::ParseError;
 use nom::branch::alt;
 use nom::bytes::complete::{tag};
 use nom::character::complete::{alpha0, alpha1, digit1, not_line_ending, space0, space1};
@@ -10,10 +10,8 @@ use nom::sequence::separated_pair;
 use crate::no_space_left::solve::CommandLineValue::{ChangeDirectoryCommand, Directory, File, ListCommand};
 
 
-pub fn solve_first_star() -> usize {
-    let commands = parse_input("src/no_space_left/input.txt");
-
-    let mut paths = calculate_disk_usage(commands);
+pub fn solve_first_star(paths: &HashMap<String, usize>) -> usize {
+    let mut paths = paths.clone();
 
     paths.retain(|_, size| *size < 100000);
 
@@ -23,33 +21,43 @@ pub fn solve_first_star() -> usize {
 }
 
 
-pub fn solve_second_star() -> usize {
-    let commands = parse_input("src/no_space_left/input.txt");
+/// Size of the device's disk.
+const DISK_SIZE: usize = 70000000;
+
+/// Unused space needed for the update.
+const REQUIRED_SPACE: usize = 30000000;
+
 
-    let mut paths = calculate_disk_usage(commands);
+pub fn solve_second_star(paths: &HashMap<String, usize>) -> Result<usize, String> {
+    let mut paths = paths.clone();
 
-    // total disk usage
-    let disk_size = 70000000_usize;
-    let disk_usage = paths.get("/").unwrap();
-    let remaining_space = disk_size - disk_usage;
-    let required_space = 30000000_usize;
-    let delete_space = required_space - remaining_space;
+    // total disk usage, which parsing checks fits on the disk
+    let disk_usage = paths["/"];
+    let remaining_space = DISK_SIZE - disk_usage;
+
+    if remaining_space >= REQUIRED_SPACE {
+        return Err(format!("{} is free already, nothing needs deleting", remaining_space));
+    }
+    let delete_space = REQUIRED_SPACE - remaining_space;
 
+    // the root directory is always big enough
     paths.retain(|_, size| *size >= delete_space);
 
     let (_, ans) = paths.iter().sorted_by(|(_, a), (_, b)| a.cmp(b)).next().unwrap();
 
-    *ans
+    Ok(*ans)
 }
 
 
-fn calculate_disk_usage(commands: String) -> HashMap<String, usize> {
+/// Replays the terminal output, returning the total size of each directory.
+fn calculate_disk_usage(commands: &str) -> Result<HashMap<String, usize>, ParseError> {
     let mut pwd = Vec::<&str>::new();
     let mut paths = HashMap::<String, usize>::new();
 
 
     for command_line in commands.lines() {
-        let command_line_value = parse_command_line(command_line);
+        let command_line_value = parse_command_line(command_line)
+            .map_err(|err| ParseError::from_nom(commands, err))?;
 
         match command_line_value {
             ChangeDirectoryCommand(dir) => {
@@ -72,7 +80,7 @@ fn calculate_disk_usage(commands: String) -> HashMap<String, usize> {
             ListCommand => {}
         }
     }
-    paths
+    Ok(paths)
 }
 
 
@@ -110,7 +118,8 @@ fn enumerate_paths(path: &Vec<&str>) -> Vec<String> {
 }
 
 
-fn parse_command_line(input: &str) -> CommandLineValue {
+fn parse_command_line(input: &str)
+    -> Result<CommandLineValue<'_>, nom::Err<nom::error::Error<&str>>> {
     let mut parser = alt((
         cd_command,
         ls_command,
@@ -119,13 +128,13 @@ fn parse_command_line(input: &str) -> CommandLineValue {
         process_ls_dir
     ));
 
-    let (_, result) = parser(input).unwrap();
+    let (_, result) = parser(input)?;
 
-    result
+    Ok(result)
 }
 
 
-fn cd_command(input: &str) -> IResult<&str, CommandLineValue> {
+fn cd_command(input: &str) -> IResult<&str, CommandLineValue<'_>> {
     let (rest, _) = space0(input)?;
     let (rest, _) = tag("$")(rest)?;
     let (rest, _) = space1(rest)?;
@@ -137,7 +146,7 @@ fn cd_command(input: &str) -> IResult<&str, CommandLineValue> {
 }
 
 
-fn ls_command(input: &str) -> IResult<&str, CommandLineValue> {
+fn ls_command(input: &str) -> IResult<&str, CommandLineValue<'_>> {
     let (rest, _) = space0(input)?;
     let (rest, _) = tag("$")(rest)?;
     let (rest, _) = space1(rest)?;
@@ -148,7 +157,7 @@ fn ls_command(input: &str) -> IResult<&str, CommandLineValue> {
 }
 
 
-fn process_ls_file_ext(input: &str) -> IResult<&str, CommandLineValue> {
+fn process_ls_file_ext(input: &str) -> IResult<&str, CommandLineValue<'_>> {
     let (rest, _) = space0(input)?;
     let (rest, file_size) = parse_usize(rest)?;
     let (rest, _) = space1(rest)?;
@@ -159,7 +168,7 @@ fn process_ls_file_ext(input: &str) -> IResult<&str, CommandLineValue> {
 }
 
 
-fn process_ls_file(input: &str) -> IResult<&str, CommandLineValue> {
+fn process_ls_file(input: &str) -> IResult<&str, CommandLineValue<'_>> {
     let (rest, _) = space0(input)?;
     let (rest, file_size) = parse_usize(rest)?;
     let (rest, _) = space1(rest)?;
@@ -170,7 +179,7 @@ fn process_ls_file(input: &str) -> IResult<&str, CommandLineValue> {
 }
 
 
-fn process_ls_dir(input: &str) -> IResult<&str, CommandLineValue> {
+fn process_ls_dir(input: &str) -> IResult<&str, CommandLineValue<'_>> {
     let (rest, _) = space0(input)?;
     let (rest, _) = tag("dir")(rest)?;
     let (rest, _) = space1(rest)?;
@@ -185,8 +194,17 @@ fn parse_usize(input: &str) -> IResult<&str, usize> {
 }
 
 
-fn parse_input(file_path: &str) -> String {
-    fs::read_to_string(file_path).expect("Input file local to project")
+pub fn parse_input(input: &str) -> Result<HashMap<String, usize>, ParseError> {
+    let paths = calculate_disk_usage(input)?;
+
+    match paths.get("/") {
+        None => Err(ParseError::end_of_input(input, "no files listed under `/`")),
+        Some(&usage) if usage > DISK_SIZE => {
+            let message = format!("files take up {}, more than the disk's {}", usage, DISK_SIZE);
+            Err(ParseError::end_of_input(input, message))
+        }
+        Some(_) => Ok(paths),
+    }
 }
 
 
@@ -195,8 +213,18 @@ mod tests {
     use super::*;
 
     #[test]
-    fn solved() {
-        assert_eq!(1844187, solve_first_star());
-        assert_eq!(4978279, solve_second_star());
+    fn parsing_errors() {
+        let paths = parse_input("$ cd /\n$ ls\n14848514 b.txt").unwrap();
+        assert_eq!(Some(&14848514), paths.get("/"));
+        assert_eq!(
+            Err("55151486 is free already, nothing needs deleting".to_string()),
+            solve_second_star(&paths)
+        );
+
+        let error = parse_input("$ cd a\n$ ls\n10 b.txt").unwrap_err();
+        assert_eq!("no files listed under `/`", error.message);
+
+        let error = parse_input("$ cd /\n$ ls\n70000001 b.txt").unwrap_err();
+        assert_eq!("files take up 70000001, more than the disk's 70000000", error.message);
     }
-}
\ No newline at end of file
+}
@@ -1,12 +1,57 @@
-use crate::rock_paper_scissors::solve::{solve_first_star, solve_second_star};
+use aoc_core::{Answer, ParseError, Solver};
+use crate::rock_paper_scissors::solve::{
+    parse_input, solve_first_star, solve_second_star, Column, Play,
+};
+use crate::YEAR;
 
 mod solve;
 
-pub fn rock_paper_scissors_solution() {
-    println!("--- Day 2: Rock Paper Scissors ---");
-    let result = solve_first_star();
-    println!("\t1st Star Solution = {}", result);
+pub struct RockPaperScissors;
 
-    let result = solve_second_star();
-    println!("\t2nd Star Solution = {}", result);
-}
\ No newline at end of file
+impl Solver for RockPaperScissors {
+    type Input = Vec<(Play, Column)>;
+
+    fn year(&self) -> u32 {
+        YEAR
+    }
+
+    fn day(&self) -> u32 {
+        2
+    }
+
+    fn title(&self) -> &'static str {
+        "Rock Paper Scissors"
+    }
+
+    fn input_path(&self) -> &'static str {
+        concat!(env!("CARGO_MANIFEST_DIR"), "/src/rock_paper_scissors/input.txt")
+    }
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Result<Answer, String> {
+        Ok(solve_first_star(input).into())
+    }
+
+    fn part2(&self, input: &Self::Input) -> Result<Answer, String> {
+        Ok(solve_second_star(input).into())
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_core::verify;
+
+    #[test]
+    fn solved() {
+        verify::assert_solved(RockPaperScissors);
+    }
+
+    aoc_core::examples!(RockPaperScissors, {
+        example: "test_input.txt",
+    });
+}
@@ -1,24 +1,40 @@
-use std::fs;
-use std::str::FromStr;
+use aoc_core::parse::{self, ParseError};
+use Column::{X, Y, Z};
+use Play::{Paper, Rock, Scissors};
 
 
-pub fn solve_first_star() -> u64 {
-    let plays = parse_input("src/rock_paper_scissors/input.txt");
+/// The opponent's play, `A`, `B` or `C`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Play {
+    Rock,
+    Paper,
+    Scissors,
+}
+
+/// The second column of the strategy guide, `X`, `Y` or `Z`, read as a play for the first star
+/// and as the outcome needed for the second.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Column {
+    X,
+    Y,
+    Z,
+}
+
 
+pub fn solve_first_star(plays: &[(Play, Column)]) -> u64 {
     let mut score = 0u64;
 
     for play in plays {
         match play {
-            ('A', 'X') => { score += 1 + 3 }    // Rock Rock Draw
-            ('A', 'Y') => { score += 2 + 6 }    // Rock Paper Win
-            ('A', 'Z') => { score += 3 }        // Rock Scissors Loss
-            ('B', 'X') => { score += 1 }        // Paper Rock Loss
-            ('B', 'Y') => { score += 2 + 3 }    // Paper Paper Draw
-            ('B', 'Z') => { score += 3 + 6 }    // Paper Scissors Win
-            ('C', 'X') => { score += 1 + 6 }    // Scissors Rock Win
-            ('C', 'Y') => { score += 2 }        // Scissors Paper Loss
-            ('C', 'Z') => { score += 3 + 3 }    // Scissors Scissors Draw
-            play => { panic!("Error in input. Unrecognised play combination {:?}", play); }
+            (Rock, X) => { score += 1 + 3 }        // Rock Rock Draw
+            (Rock, Y) => { score += 2 + 6 }        // Rock Paper Win
+            (Rock, Z) => { score += 3 }            // Rock Scissors Loss
+            (Paper, X) => { score += 1 }           // Paper Rock Loss
+            (Paper, Y) => { score += 2 + 3 }       // Paper Paper Draw
+            (Paper, Z) => { score += 3 + 6 }       // Paper Scissors Win
+            (Scissors, X) => { score += 1 + 6 }    // Scissors Rock Win
+            (Scissors, Y) => { score += 2 }        // Scissors Paper Loss
+            (Scissors, Z) => { score += 3 + 3 }    // Scissors Scissors Draw
         }
     }
 
@@ -26,23 +42,20 @@ pub fn solve_first_star() -> u64 {
 }
 
 
-pub fn solve_second_star() -> u64 {
-    let plays = parse_input("src/rock_paper_scissors/input.txt");
-
+pub fn solve_second_star(plays: &[(Play, Column)]) -> u64 {
     let mut score = 0u64;
 
     for play in plays {
         match play {
-            ('A', 'X') => { score += 3 }        // Rock & Need to loose. Pick Scissors
-            ('A', 'Y') => { score += 1 + 3 }    // Rock & Need to draw. Pick Rock
-            ('A', 'Z') => { score += 2 + 6 }    // Rock & Need to win. Pick Paper
-            ('B', 'X') => { score += 1 }        // Paper & Need to loose. Pick Rock
-            ('B', 'Y') => { score += 2 + 3 }    // Paper & Need to draw. Pick Paper
-            ('B', 'Z') => { score += 3 + 6 }    // Paper & Need to win. Pick Scissors
-            ('C', 'X') => { score += 2 }        // Scissors & Need to loose. Pick Paper
-            ('C', 'Y') => { score += 3 + 3 }    // Scissors & Need to draw. Pick Scissors
-            ('C', 'Z') => { score += 1 + 6 }    // Scissors & Need to win. Pick Rock
-            play => { panic!("Error in input. Unrecognised play combination {:?}", play); }
+            (Rock, X) => { score += 3 }            // Rock & Need to loose. Pick Scissors
+            (Rock, Y) => { score += 1 + 3 }        // Rock & Need to draw. Pick Rock
+            (Rock, Z) => { score += 2 + 6 }        // Rock & Need to win. Pick Paper
+            (Paper, X) => { score += 1 }           // Paper & Need to loose. Pick Rock
+            (Paper, Y) => { score += 2 + 3 }       // Paper & Need to draw. Pick Paper
+            (Paper, Z) => { score += 3 + 6 }       // Paper & Need to win. Pick Scissors
+            (Scissors, X) => { score += 2 }        // Scissors & Need to loose. Pick Paper
+            (Scissors, Y) => { score += 3 + 3 }    // Scissors & Need to draw. Pick Scissors
+            (Scissors, Z) => { score += 1 + 6 }    // Scissors & Need to win. Pick Rock
         }
     }
 
@@ -50,23 +63,37 @@ pub fn solve_second_star() -> u64 {
 }
 
 
-fn parse_input(file_path: &str) -> Vec<(char, char)> {
-    let contents = fs::read_to_string(file_path).expect("Input file local to project");
-
-    let mut result = Vec::<(char, char)>::new();
+pub fn parse_input(contents: &str) -> Result<Vec<(Play, Column)>, ParseError> {
+    let mut result = Vec::<(Play, Column)>::new();
 
     for line in contents.lines() {
         let mut symbols = line.split(' ');
 
-        let c1 = char::from_str(symbols.next().unwrap()).unwrap();
+        let c1 = parse::next(contents, &mut symbols, "opponent's play")?;
+        let c1 = parse_symbol(contents, c1, [("A", Rock), ("B", Paper), ("C", Scissors)])?;
 
-        let c2 = char::from_str(symbols.next().unwrap()).unwrap();
+        let c2 = parse::next(contents, &mut symbols, "response")?;
+        let c2 = parse_symbol(contents, c2, [("X", X), ("Y", Y), ("Z", Z)])?;
 
 
         result.push((c1, c2));
     }
 
-    result
+    Ok(result)
+}
+
+
+/// Parses a single strategy guide symbol, which must be one of `symbols`
+fn parse_symbol<T: Copy>(contents: &str, token: &str, symbols: [(&str, T); 3])
+    -> Result<T, ParseError> {
+    match symbols.iter().find(|(symbol, _)| *symbol == token) {
+        Some(&(_, value)) => Ok(value),
+        None => {
+            let expected = symbols.iter().map(|(symbol, _)| *symbol).collect::<String>();
+            let message = format!("expected one of `{}`, found `{}`", expected, token);
+            Err(ParseError::at(contents, token, message))
+        }
+    }
 }
 
 
@@ -75,8 +102,11 @@ mod tests {
     use super::*;
 
     #[test]
-    fn solved() {
-        assert_eq!(14531, solve_first_star());
-        assert_eq!(11258, solve_second_star());
+    fn parsing() {
+        assert_eq!(Ok(vec![(Rock, Y), (Scissors, Z)]), parse_input("A Y\nC Z"));
+
+        let error = parse_input("A Y\nB W").unwrap_err();
+        assert_eq!((2, 3), (error.line, error.column));
+        assert_eq!("expected one of `XYZ`, found `W`", error.message);
     }
-}
\ No newline at end of file
+}
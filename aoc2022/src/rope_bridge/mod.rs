@@ -1,12 +1,56 @@
-use crate::rope_bridge::solve::{solve_first_star, solve_second_star};
+use aoc_core::{Answer, ParseError, Solver};
+use crate::rope_bridge::solve::{parse_input, solve_first_star, solve_second_star, Direction};
+use crate::YEAR;
 
 mod solve;
 
-pub fn rope_bridge_solution() {
-    println!("--- Day 9: Rope Bridge ---");
-    let result = solve_first_star();
-    println!("\t1st Star Solution = {}", result);
+pub struct RopeBridge;
 
-    let result = solve_second_star();
-    println!("\t2nd Star Solution = {}", result);
-}
\ No newline at end of file
+impl Solver for RopeBridge {
+    type Input = Vec<(Direction, usize)>;
+
+    fn year(&self) -> u32 {
+        YEAR
+    }
+
+    fn day(&self) -> u32 {
+        9
+    }
+
+    fn title(&self) -> &'static str {
+        "Rope Bridge"
+    }
+
+    fn input_path(&self) -> &'static str {
+        concat!(env!("CARGO_MANIFEST_DIR"), "/src/rope_bridge/input.txt")
+    }
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Result<Answer, String> {
+        Ok(solve_first_star(input).into())
+    }
+
+    fn part2(&self, input: &Self::Input) -> Result<Answer, String> {
+        Ok(solve_second_star(input).into())
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_core::verify;
+
+    #[test]
+    fn solved() {
+        verify::assert_solved(RopeBridge);
+    }
+
+    aoc_core::examples!(RopeBridge, {
+        example: "test_input.txt",
+        example2: "test_input2.txt",
+    });
+}
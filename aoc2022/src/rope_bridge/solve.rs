@@ -1,9 +1,17 @@
-use std::fs;
+use aoc_core::parse::{self, ParseError};
 
 
-pub fn solve_first_star() -> usize {
-    let movements = parse_input("src/rope_bridge/input.txt");
+/// The direction the head of the rope moves in, `L`, `D`, `U` or `R`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    Left,
+    Down,
+    Up,
+    Right,
+}
+
 
+pub fn solve_first_star(movements: &[(Direction, usize)]) -> usize {
     let mut tail_path = simulate_rope_dynamics(2, movements);
 
     // sort and dedup tail path to get unique positions
@@ -14,9 +22,7 @@ pub fn solve_first_star() -> usize {
 }
 
 
-pub fn solve_second_star() -> usize {
-    let movements = parse_input("src/rope_bridge/input.txt");
-
+pub fn solve_second_star(movements: &[(Direction, usize)]) -> usize {
     let mut tail_path = simulate_rope_dynamics(10, movements);
 
     // sort and dedup tail path to get unique positions
@@ -27,14 +33,14 @@ pub fn solve_second_star() -> usize {
 }
 
 
-fn simulate_rope_dynamics(size: usize, movements: Vec<(char, usize)>) -> Vec<(i32, i32)> {
+fn simulate_rope_dynamics(size: usize, movements: &[(Direction, usize)]) -> Vec<(i32, i32)> {
     // init position of rope
     let mut rope = init_rope(size);
 
     // vector to capture tail path as indices (x,y) for each step
     let mut tail_path = Vec::<(i32, i32)>::new();
 
-    for (direction, steps) in movements {
+    for &(direction, steps) in movements {
         let mut steps_remaining = steps;
 
         while steps_remaining > 0 {
@@ -62,22 +68,21 @@ fn init_rope(size: usize) -> Vec<(i32, i32)> {
     rope
 }
 
-fn simulate_rope_step(rope: &mut Vec<(i32, i32)>, direction: char) {
+fn simulate_rope_step(rope: &mut [(i32, i32)], direction: Direction) {
     // update head position
     match direction {
-        'L' => {
+        Direction::Left => {
             rope[0].0 -= 1;
         }
-        'D' => {
+        Direction::Down => {
             rope[0].1 -= 1;
         }
-        'U' => {
+        Direction::Up => {
             rope[0].1 += 1;
         }
-        'R' => {
+        Direction::Right => {
             rope[0].0 += 1;
         }
-        _ => { panic!("Error: Unknown direction") }
     }
 
     // iterate over remaining knots in rope and apply 'tail follows' dynamics
@@ -119,21 +124,31 @@ fn tail_follows(head_pos: (i32, i32), tail_pos: (i32, i32)) -> (i32, i32) {
 }
 
 
-fn parse_input(file_path: &str) -> Vec<(char, usize)> {
-    let content = fs::read_to_string(file_path).expect("Input file local to project");
-
-    let mut result = Vec::<(char, usize)>::new();
+pub fn parse_input(content: &str) -> Result<Vec<(Direction, usize)>, ParseError> {
+    let mut result = Vec::<(Direction, usize)>::new();
 
     for line in content.lines() {
         let mut line_split = line.split(' ');
 
-        let direction = line_split.next().unwrap().chars().next().unwrap();
-        let steps = line_split.next().unwrap().parse::<usize>().unwrap();
+        let direction = parse::next(content, &mut line_split, "direction")?;
+        let direction = match direction {
+            "L" => Direction::Left,
+            "D" => Direction::Down,
+            "U" => Direction::Up,
+            "R" => Direction::Right,
+            _ => {
+                let message = format!("expected a direction (L, D, U or R), found `{}`", direction);
+                return Err(ParseError::at(content, direction, message));
+            }
+        };
+
+        let steps = parse::next(content, &mut line_split, "number of steps")?;
+        let steps = parse::number::<usize>(content, steps)?;
 
         result.push((direction, steps));
     }
 
-    result
+    Ok(result)
 }
 
 
@@ -141,12 +156,6 @@ fn parse_input(file_path: &str) -> Vec<(char, usize)> {
 mod tests {
     use super::*;
 
-    #[test]
-    fn solved() {
-        assert_eq!(5981, solve_first_star());
-        assert_eq!(2352, solve_second_star());
-    }
-
     #[test]
     fn tail_movements() {
         assert_eq!((1, 0), tail_follows((2, 0), (0, 0)));
@@ -154,4 +163,14 @@ mod tests {
         assert_eq!((-3, -2), tail_follows((-4, -2), (-2, -2)));
         assert_eq!((-3, 2), tail_follows((-4, 2), (-2, 2)));
     }
+
+    #[test]
+    fn parsing() {
+        let movements = vec![(Direction::Right, 4), (Direction::Up, 12)];
+        assert_eq!(Ok(movements), parse_input("R 4\nU 12"));
+
+        let error = parse_input("R 4\nN 2").unwrap_err();
+        assert_eq!((2, 1), (error.line, error.column));
+        assert_eq!("expected a direction (L, D, U or R), found `N`", error.message);
+    }
 }
\ No newline at end of file
@@ -1,12 +1,56 @@
-use crate::rucksack_reorganisation::solve::{solve_first_star, solve_second_star};
+use aoc_core::{Answer, ParseError, Solver};
+use std::collections::VecDeque;
+use crate::rucksack_reorganisation::solve::{parse_input, solve_first_star, solve_second_star};
+use crate::YEAR;
 
 mod solve;
 
-pub fn rucksack_reorganisation_solution() {
-    println!("--- Day 3: Rucksack Reorganisation ---");
-    let result = solve_first_star();
-    println!("\t1st Star Solution = {}", result);
+pub struct RucksackReorganisation;
 
-    let result = solve_second_star();
-    println!("\t2nd Star Solution = {}", result);
-}
\ No newline at end of file
+impl Solver for RucksackReorganisation {
+    type Input = VecDeque<Vec<char>>;
+
+    fn year(&self) -> u32 {
+        YEAR
+    }
+
+    fn day(&self) -> u32 {
+        3
+    }
+
+    fn title(&self) -> &'static str {
+        "Rucksack Reorganisation"
+    }
+
+    fn input_path(&self) -> &'static str {
+        concat!(env!("CARGO_MANIFEST_DIR"), "/src/rucksack_reorganisation/input.txt")
+    }
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Result<Answer, String> {
+        Ok(solve_first_star(input).into())
+    }
+
+    fn part2(&self, input: &Self::Input) -> Result<Answer, String> {
+        Ok(solve_second_star(input).into())
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_core::verify;
+
+    #[test]
+    fn solved() {
+        verify::assert_solved(RucksackReorganisation);
+    }
+
+    aoc_core::examples!(RucksackReorganisation, {
+        example: "test_input.txt",
+    });
+}
@@ -1,10 +1,8 @@
 use std::collections::{HashSet, VecDeque};
-use std::fs;
+use aoc_core::ParseError;
 
 
-pub fn solve_first_star() -> u32 {
-    let rucksacks = parse_input("src/rucksack_reorganisation/input.txt");
-
+pub fn solve_first_star(rucksacks: &VecDeque<Vec<char>>) -> u32 {
     let mut priorities_value = 0_u32;
 
     for rucksack in rucksacks {
@@ -17,30 +15,30 @@ pub fn solve_first_star() -> u32 {
             }
         }
 
-        priorities_value += calculate_priority(duplicate_item.unwrap());
+        let duplicate_item = duplicate_item.expect("parsing checks for a duplicate");
+        priorities_value += calculate_priority(duplicate_item);
     }
 
     priorities_value
 }
 
 
-pub fn solve_second_star() -> u32 {
-    let mut rucksacks = parse_input("src/rucksack_reorganisation/input.txt");
+pub fn solve_second_star(rucksacks: &VecDeque<Vec<char>>) -> u32 {
+    let mut rucksacks = rucksacks.clone();
 
     let mut badge_priorities_value = 0_u32;
 
 
     // get 3 groups
     while !rucksacks.is_empty() {
-        let group1 = HashSet::<char>::from_iter(rucksacks.pop_front().unwrap().into_iter());
-        let group2 = HashSet::<char>::from_iter(rucksacks.pop_front().unwrap().into_iter());
-        let group3 = HashSet::<char>::from_iter(rucksacks.pop_front().unwrap().into_iter());
+        let group1 = HashSet::<char>::from_iter(rucksacks.pop_front().unwrap());
+        let group2 = HashSet::<char>::from_iter(rucksacks.pop_front().unwrap());
+        let group3 = HashSet::<char>::from_iter(rucksacks.pop_front().unwrap());
 
         let badge_candidates = group1.intersection(&group2).copied().collect::<HashSet<_>>();
 
-        if let Some(badge) = badge_candidates.intersection(&group3).next() {
-            badge_priorities_value += calculate_priority(*badge);
-        }
+        let badge = badge_candidates.intersection(&group3).next().expect("parsing checks badges");
+        badge_priorities_value += calculate_priority(*badge);
     }
 
     badge_priorities_value
@@ -59,7 +57,7 @@ fn calculate_priority(duplicate_item: char) -> u32 {
 }
 
 
-fn get_compartments(rucksack: Vec<char>) -> (Vec<char>, Vec<char>) {
+fn get_compartments(rucksack: &[char]) -> (Vec<char>, Vec<char>) {
     let rucksack_size = rucksack.len();
 
     let left_compartment = rucksack.iter().copied().take(rucksack_size / 2).collect::<Vec<_>>();
@@ -69,16 +67,47 @@ fn get_compartments(rucksack: Vec<char>) -> (Vec<char>, Vec<char>) {
 }
 
 
-fn parse_input(file_path: &str) -> VecDeque<Vec<char>> {
-    let contents = fs::read_to_string(file_path).expect("Input file local to project");
+/// Rucksacks in each group of elves, sharing a badge.
+const GROUP_SIZE: usize = 3;
+
 
+/// Parses the rucksacks, checking each has two equally sized compartments sharing an item, and
+/// that they come in groups of elves sharing a badge item.
+pub fn parse_input(contents: &str) -> Result<VecDeque<Vec<char>>, ParseError> {
     let mut rucksacks = VecDeque::<Vec<char>>::new();
+    let lines = contents.lines().collect::<Vec<_>>();
+
+    for &line in &lines {
+        if let Some(i) = line.find(|c: char| !c.is_ascii_alphabetic()) {
+            let message = format!("expected an item letter, found `{}`", &line[i..i + 1]);
+            return Err(ParseError::at(contents, &line[i..], message));
+        }
+
+        if line.is_empty() || line.len() % 2 != 0 {
+            return Err(ParseError::at(contents, line, "expected an even number of items"));
+        }
+
+        let (left, right) = line.split_at(line.len() / 2);
+        if !right.chars().any(|c| left.contains(c)) {
+            return Err(ParseError::at(contents, line, "no item is in both compartments"));
+        }
 
-    for line in contents.lines() {
         rucksacks.push_back(line.chars().collect::<Vec<_>>())
     }
 
-    rucksacks
+    if lines.len() % GROUP_SIZE != 0 {
+        let message = format!("expected groups of {} rucksacks, found {}", GROUP_SIZE, lines.len());
+        return Err(ParseError::end_of_input(contents, message));
+    }
+
+    for group in lines.chunks(GROUP_SIZE) {
+        if !group[0].chars().any(|c| group[1..].iter().all(|line| line.contains(c))) {
+            let message = "no badge item is in every rucksack of the group";
+            return Err(ParseError::at(contents, group[0], message));
+        }
+    }
+
+    Ok(rucksacks)
 }
 
 
@@ -87,8 +116,19 @@ mod tests {
     use super::*;
 
     #[test]
-    fn solved() {
-        assert_eq!(7850, solve_first_star());
-        assert_eq!(2581, solve_second_star());
+    fn parsing_errors() {
+        let error = parse_input("vJrwpWtwJgWrhcsFMMfFFhFp\nabc").unwrap_err();
+        assert_eq!((2, 1), (error.line, error.column));
+        assert_eq!("expected an even number of items", error.message);
+
+        let error = parse_input("abcd").unwrap_err();
+        assert_eq!("no item is in both compartments", error.message);
+
+        let error = parse_input("abca\nabca").unwrap_err();
+        assert_eq!("expected groups of 3 rucksacks, found 2", error.message);
+
+        let error = parse_input("abca\nabca\nxyzx").unwrap_err();
+        assert_eq!((1, 1), (error.line, error.column));
+        assert_eq!("no badge item is in every rucksack of the group", error.message);
     }
-}
\ No newline at end of file
+}
@@ -1,5 +1,5 @@
-use std::fs;
 use std::ops::{IndexMut};
+use aoc_core::parse::{self, ParseError};
 use nom::bytes::complete::tag;
 use nom::character::complete::{digit1, space0};
 use nom::combinator::{map_res};
@@ -7,8 +7,8 @@ use nom::IResult;
 use nom::sequence::{tuple};
 
 
-pub fn solve_first_star() -> String {
-    let (mut stacks, instructions) = parse_input("src/supply_stacks/input.txt");
+pub fn solve_first_star((stacks, instructions): &(Vec<Vec<char>>, Vec<Instruction>)) -> String {
+    let mut stacks = stacks.clone();
 
     for instruction in instructions {
         let mut moving_containers = Vec::<char>::new();
@@ -30,8 +30,8 @@ pub fn solve_first_star() -> String {
 }
 
 
-pub fn solve_second_star() -> String {
-    let (mut stacks, instructions) = parse_input("src/supply_stacks/input.txt");
+pub fn solve_second_star((stacks, instructions): &(Vec<Vec<char>>, Vec<Instruction>)) -> String {
+    let mut stacks = stacks.clone();
 
     for instruction in instructions {
         let mut moving_containers = Vec::<char>::new();
@@ -54,16 +54,16 @@ pub fn solve_second_star() -> String {
     result
 }
 
-fn parse_instruction(input: &str) -> Instruction {
+fn parse_instruction(input: &str) -> Result<Instruction, nom::Err<nom::error::Error<&str>>> {
     let mut parser = tuple((move_command, from_command, to_command));
 
-    let (_, (move_count, from_location, to_location)) = parser(input).unwrap();
+    let (_, (move_count, from_location, to_location)) = parser(input)?;
 
-    Instruction {
+    Ok(Instruction {
         move_count,
         from_location,
         to_location,
-    }
+    })
 }
 
 fn move_command(input: &str) -> IResult<&str, usize> {
@@ -100,38 +100,64 @@ fn parse_usize(input: &str) -> IResult<&str, usize> {
 
 
 #[derive(Debug, PartialEq)]
-struct Instruction {
+pub struct Instruction {
     move_count: usize,
     from_location: usize,
     to_location: usize,
 }
 
 
-fn parse_input(file_path: &str) -> (Vec<Vec<char>>, Vec<Instruction>) {
-    let contents = fs::read_to_string(file_path).expect("Input file local to project");
+pub fn parse_input(contents: &str) -> Result<(Vec<Vec<char>>, Vec<Instruction>), ParseError> {
+    let (drawing, procedure) = contents
+        .split_once("\n\n")
+        .ok_or_else(|| ParseError::end_of_input(contents, "missing rearrangement procedure"))?;
 
-    let mut stacks = Vec::<Vec<char>>::new();
+    let stacks = parse_drawing(contents, drawing)?;
     let mut instructions = Vec::<Instruction>::new();
 
 
-    // Initialise stacks
-    stacks.push(vec!['B', 'W', 'N']);
-    stacks.push(vec!['L', 'Z', 'S', 'P', 'T', 'D', 'M', 'B']);
-    stacks.push(vec!['Q', 'H', 'Z', 'W', 'R']);
-    stacks.push(vec!['W', 'D', 'V', 'J', 'Z', 'R']);
-    stacks.push(vec!['S', 'H', 'M', 'B']);
-    stacks.push(vec!['L', 'G', 'N', 'J', 'H', 'V', 'P', 'B']);
-    stacks.push(vec!['J', 'Q', 'Z', 'F', 'H', 'D', 'L', 'S']);
-    stacks.push(vec!['W', 'S', 'F', 'J', 'G', 'Q', 'B']);
-    stacks.push(vec!['Z', 'W', 'M', 'S', 'C', 'D', 'J']);
+    // parse instructions
+    for line in procedure.lines() {
+        let instruction = parse_instruction(line)
+            .map_err(|err| ParseError::from_nom(contents, err))?;
+
+        // stacks are numbered from 1
+        let stacks_range = 1..=stacks.len();
+        if !stacks_range.contains(&instruction.from_location) ||
+            !stacks_range.contains(&instruction.to_location) {
+            let stack_count = stacks.len();
+            let message = format!("stacks are numbered 1 to {}", stack_count);
+            return Err(ParseError::at(contents, line, message));
+        }
 
+        instructions.push(instruction);
+    }
 
-    // parse instructions
-    for line in contents.lines().skip(10) {
-        instructions.push(parse_instruction(line));
+    Ok((stacks, instructions))
+}
+
+
+/// Parses the drawing of the starting stacks, returning each stack from the bottom crate up.
+fn parse_drawing(contents: &str, drawing: &str) -> Result<Vec<Vec<char>>, ParseError> {
+    let mut rows = drawing.lines().rev();
+
+    // last row of the drawing numbers the stacks
+    let stack_count = parse::next(contents, &mut rows, "stack numbers")?.split_whitespace().count();
+
+    let mut stacks = vec![Vec::<char>::new(); stack_count];
+
+    for row in rows {
+        let row = row.chars().collect::<Vec<_>>();
+
+        // crate labels sit every 4 characters, e.g. "[Z] [M] [P]"
+        for (index, stack) in stacks.iter_mut().enumerate() {
+            if let Some(label) = row.get(index * 4 + 1).filter(|c| c.is_ascii_alphabetic()) {
+                stack.push(*label);
+            }
+        }
     }
 
-    (stacks, instructions)
+    Ok(stacks)
 }
 
 
@@ -140,20 +166,22 @@ mod tests {
     use super::*;
 
     #[test]
-    fn solved() {
-        assert_eq!("MQSHJMWNH", solve_first_star());
-        assert_eq!("LLWJRBHVZ", solve_second_star());
+    fn parsing_drawing() {
+        let drawing = "    [D]    \n[N] [C]    \n[Z] [M] [P]\n 1   2   3 ";
+
+        assert_eq!(vec![vec!['Z', 'N'], vec!['M', 'C', 'D'], vec!['P']],
+                   parse_drawing(drawing, drawing).unwrap());
     }
 
     #[test]
     fn parsing_instruction() {
-        assert_eq!(Instruction { move_count: 3, from_location: 5, to_location: 2 },
+        assert_eq!(Ok(Instruction { move_count: 3, from_location: 5, to_location: 2 }),
                    parse_instruction("move 3 from 5 to 2"));
 
-        assert_eq!(Instruction { move_count: 5, from_location: 3, to_location: 1 },
+        assert_eq!(Ok(Instruction { move_count: 5, from_location: 3, to_location: 1 }),
                    parse_instruction("move 5 from 3 to 1"));
 
-        assert_eq!(Instruction { move_count: 4, from_location: 4, to_location: 9 },
+        assert_eq!(Ok(Instruction { move_count: 4, from_location: 4, to_location: 9 }),
                    parse_instruction("move 4 from 4 to 9"));
     }
 }
\ No newline at end of file
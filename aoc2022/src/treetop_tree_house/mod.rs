@@ -1,12 +1,56 @@
-use crate::treetop_tree_house::solve::{solve_first_star, solve_second_star};
+use aoc_core::{Answer, ParseError, Solver};
+use aoc_core::grid::Grid;
+use crate::treetop_tree_house::solve::{parse_input, solve_first_star, solve_second_star};
+use crate::YEAR;
 
 mod solve;
 
-pub fn treetop_tree_house_solution() {
-    println!("--- Day 8: Treetop Tree House ---");
-    let result = solve_first_star();
-    println!("\t1st Star Solution = {}", result);
+pub struct TreetopTreeHouse;
 
-    let result = solve_second_star();
-    println!("\t2nd Star Solution = {}", result);
-}
\ No newline at end of file
+impl Solver for TreetopTreeHouse {
+    type Input = Grid<u32>;
+
+    fn year(&self) -> u32 {
+        YEAR
+    }
+
+    fn day(&self) -> u32 {
+        8
+    }
+
+    fn title(&self) -> &'static str {
+        "Treetop Tree House"
+    }
+
+    fn input_path(&self) -> &'static str {
+        concat!(env!("CARGO_MANIFEST_DIR"), "/src/treetop_tree_house/input.txt")
+    }
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Result<Answer, String> {
+        Ok(solve_first_star(input).into())
+    }
+
+    fn part2(&self, input: &Self::Input) -> Result<Answer, String> {
+        Ok(solve_second_star(input).into())
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_core::verify;
+
+    #[test]
+    fn solved() {
+        verify::assert_solved(TreetopTreeHouse);
+    }
+
+    aoc_core::examples!(TreetopTreeHouse, {
+        example: "test_input.txt",
+    });
+}
@@ -1,27 +1,25 @@
-use std::fs;
 use ndarray::{Array1, Array2, ArrayView, Axis, Ix1, Zip};
+use aoc_core::grid::{Direction, Grid, Position, EAST, NORTH, SOUTH, WEST};
+use aoc_core::ParseError;
 
 
-pub fn solve_first_star() -> usize {
-    let forest = parse_input("src/treetop_tree_house/input.txt");
-
-    let visible_trees = find_visible_trees(&forest);
+pub fn solve_first_star(forest: &Grid<u32>) -> usize {
+    let visible_trees = find_visible_trees(forest);
 
     visible_trees.iter().filter(|&a| *a).count()
 }
 
 
-pub fn solve_second_star() -> u32 {
-    let forest = parse_input("src/treetop_tree_house/input.txt");
-
+pub fn solve_second_star(forest: &Grid<u32>) -> u32 {
     let scenic_forest = survey_forest(forest);
 
-    scenic_forest.into_iter().max().unwrap()
+    scenic_forest.into_array().into_iter().max().expect("parsing checks for trees")
 }
 
 
-fn find_visible_trees(forest: &Array2<u32>) -> Array2<bool> {
+fn find_visible_trees(forest: &Grid<u32>) -> Array2<bool> {
     let (height, width) = forest.dim();
+    let forest = forest.as_array();
 
     let mut visible_rows = Array2::from_elem((0, width), false);
     let mut visible_cols = Array2::from_elem((height, 0), false);
@@ -74,55 +72,34 @@ fn visible_tree_slice(trees: &ArrayView<u32, Ix1>) -> Array1<bool> {
 }
 
 
-fn survey_forest(trees: Array2<u32>) -> Array2<u32> {
-    let dim = trees.dim();
+fn survey_forest(trees: &Grid<u32>) -> Grid<u32> {
+    let mut result = trees.map(|_| 0);
 
-    let mut result = Array2::<u32>::zeros(dim);
-
-    for ((r, c), _) in trees.indexed_iter() {
-        result[[r, c]] = scenic_score(&trees, (r, c));
+    for position in trees.positions() {
+        result[position] = scenic_score(trees, position);
     }
 
     result
 }
 
 
-fn scenic_score(trees: &Array2<u32>, position: (usize, usize)) -> u32 {
-    // initialise as 1 because its a product
-    let mut scenic_score = 1;
-
-    // Look all directions
-    scenic_score *= gaze_direction(trees, position, (-1, 0)); // North
-    scenic_score *= gaze_direction(trees, position, (1, 0)); // South
-    scenic_score *= gaze_direction(trees, position, (0, -1)); // West
-    scenic_score *= gaze_direction(trees, position, (0, 1)); // East
-
-    scenic_score
+fn scenic_score(trees: &Grid<u32>, position: Position) -> u32 {
+    // Look all directions, the score is the product of the viewing distances
+    [NORTH, SOUTH, WEST, EAST]
+        .into_iter()
+        .map(|direction| gaze_direction(trees, position, direction))
+        .product()
 }
 
-fn gaze_direction(trees: &Array2<u32>, position: (usize, usize), direction: (i32, i32)) -> u32 {
-    let (row_len, col_len) = trees.dim();
+fn gaze_direction(trees: &Grid<u32>, position: Position, direction: Direction) -> u32 {
+    let tree_house_height = trees[position];
     let mut scenic_score = 0_u32;
 
-    let tree_house_height = trees[[position.0, position.1]];
-    let mut row_index = position.0 as i32;
-    let mut col_index = position.1 as i32;
-
-
-    loop {
-        // update indices
-        row_index += direction.0;
-        col_index += direction.1;
-
-        // check for out of bounds
-        if row_index < 0 || col_index < 0 || row_index as usize == row_len || col_index as usize == col_len { break; }
-
-        // check line of sight and accumulate scenic score
-        let assessing_tree = trees[[row_index as usize, col_index as usize]];
-
+    // look along the line of sight to the edge of the forest
+    for assessing_tree in trees.ray(position, direction) {
         scenic_score += 1;
 
-        if assessing_tree >= tree_house_height {
+        if trees[assessing_tree] >= tree_house_height {
             break;
         }
     }
@@ -131,26 +108,14 @@ fn gaze_direction(trees: &Array2<u32>, position: (usize, usize), direction: (i32
 }
 
 
-fn parse_input(file_path: &str) -> Array2<u32> {
-    let contents = fs::read_to_string(file_path).expect("Input file local to project");
-
-    // get dimensions of forest grid
-    let width = contents.lines().next().unwrap().len();
-
-    let mut result = Array2::<u32>::zeros((0, width));
-
-    let lines = contents.lines();
+pub fn parse_input(contents: &str) -> Result<Grid<u32>, ParseError> {
+    let forest = Grid::digits(contents)?;
 
-    for line in lines {
-        let trees = line
-            .chars()
-            .map(|x| x.to_digit(10).unwrap())
-            .collect::<Vec<_>>();
-
-        result.push(Axis(0), ArrayView::from(&trees)).unwrap();
+    if forest.height() == 0 || forest.width() == 0 {
+        return Err(ParseError::end_of_input(contents, "expected a map of tree heights"));
     }
 
-    result
+    Ok(forest)
 }
 
 
@@ -159,25 +124,9 @@ mod tests {
     use super::*;
 
 
-    #[test]
-    fn solved() {
-        assert_eq!(1708, solve_first_star());
-        assert_eq!(504000, solve_second_star());
-    }
-
-
-    #[test]
-    fn visible_trees() {
-        let forest = parse_input("src/treetop_tree_house/test_input.txt");
-        let result = find_visible_trees(&forest);
-        let ans = result.iter().filter(|&a| *a);
-        assert_eq!(21, ans.count());
-    }
-
-
     #[test]
     fn gazing() {
-        let forest = parse_input("src/treetop_tree_house/test_input.txt");
+        let forest = parse_input(include_str!("test_input.txt")).unwrap();
         assert_eq!(2, gaze_direction(&forest, (2, 0), (1, 0)));   // South
         assert_eq!(0, gaze_direction(&forest, (0, 2), (-1, 0)));  // North
         assert_eq!(1, gaze_direction(&forest, (0, 2), (1, 0)));   // South
@@ -187,12 +136,13 @@ mod tests {
         assert_eq!(2, gaze_direction(&forest, (3, 2), (0, 1)));   // East
     }
 
-
     #[test]
-    fn highest_scenic_score() {
-        let forest = parse_input("src/treetop_tree_house/test_input.txt");
-        let scenic_forest = survey_forest(forest);
-        let highest_score = scenic_forest.iter().max().unwrap();
-        assert_eq!(8, *highest_score);
+    fn parsing_errors() {
+        let error = parse_input("303\n25x").unwrap_err();
+        assert_eq!((2, 3), (error.line, error.column));
+
+        for empty in ["", "\n"] {
+            assert_eq!("expected a map of tree heights", parse_input(empty).unwrap_err().message);
+        }
     }
 }
\ No newline at end of file
@@ -1,12 +1,59 @@
-use crate::tuning_trouble::solve::{solve_first_star, solve_second_star};
+use aoc_core::{Answer, ParseError, Solver};
+use crate::tuning_trouble::solve::{parse_input, solve_first_star, solve_second_star};
+use crate::YEAR;
 
 mod solve;
 
-pub fn tuning_trouble_solution() {
-    println!("--- Day 6: Tuning Trouble ---");
-    let result = solve_first_star();
-    println!("\t1st Star Solution = {}", result);
+pub struct TuningTrouble;
 
-    let result = solve_second_star();
-    println!("\t2nd Star Solution = {}", result);
-}
\ No newline at end of file
+impl Solver for TuningTrouble {
+    type Input = String;
+
+    fn year(&self) -> u32 {
+        YEAR
+    }
+
+    fn day(&self) -> u32 {
+        6
+    }
+
+    fn title(&self) -> &'static str {
+        "Tuning Trouble"
+    }
+
+    fn input_path(&self) -> &'static str {
+        concat!(env!("CARGO_MANIFEST_DIR"), "/src/tuning_trouble/input.txt")
+    }
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Result<Answer, String> {
+        Ok(solve_first_star(input).into())
+    }
+
+    fn part2(&self, input: &Self::Input) -> Result<Answer, String> {
+        Ok(solve_second_star(input).into())
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_core::verify;
+
+    #[test]
+    fn solved() {
+        verify::assert_solved(TuningTrouble);
+    }
+
+    aoc_core::examples!(TuningTrouble, {
+        example: "test_input.txt",
+        example2: "test_input2.txt",
+        example3: "test_input3.txt",
+        example4: "test_input4.txt",
+        example5: "test_input5.txt",
+    });
+}
@@ -1,42 +1,54 @@
 use std::collections::HashSet;
-use std::fs;
+use aoc_core::ParseError;
 
 
-pub fn solve_first_star() -> usize {
-    let datastream = parse_input("src/tuning_trouble/input.txt");
+/// Size of the start-of-packet marker.
+const PACKET_MARKER: usize = 4;
+
+/// Size of the start-of-message marker.
+const MESSAGE_MARKER: usize = 14;
 
-    find_start_marker(datastream, 4)
-}
 
+pub fn solve_first_star(datastream: &str) -> usize {
+    find_start_marker(datastream, PACKET_MARKER).expect("parsing checks for markers")
+}
 
-pub fn solve_second_star() -> usize {
-    let datastream = parse_input("src/tuning_trouble/input.txt");
 
-    find_start_marker(datastream, 14)
+pub fn solve_second_star(datastream: &str) -> usize {
+    find_start_marker(datastream, MESSAGE_MARKER).expect("parsing checks for markers")
 }
 
 
-fn find_start_marker(datastream: String, marker_size: usize) -> usize {
+/// Finds the number of characters read up to the end of the first marker of different characters.
+fn find_start_marker(datastream: &str, marker_size: usize) -> Option<usize> {
     let datastream_chars = datastream.chars().collect::<Vec<_>>();
 
     let window = datastream_chars.windows(marker_size);
 
-    for marker in window {
+    for (start, marker) in window.enumerate() {
         let unique_chars = HashSet::<&char>::from_iter(marker.iter());
 
         if unique_chars.len() == marker_size {
             // Found the packet marker, calculate start offset based on marker location
-            let marker_pattern: String = marker.iter().collect();
-            return datastream.find(&marker_pattern).unwrap() + marker_size;
+            return Some(start + marker_size);
         }
     }
 
-    panic!("Error: No marker detected for size = {}", marker_size);
+    None
 }
 
 
-fn parse_input(file_path: &str) -> String {
-    fs::read_to_string(file_path).expect("Input file local to project")
+pub fn parse_input(input: &str) -> Result<String, ParseError> {
+    let datastream = input.trim_end();
+
+    // a start-of-message marker also starts with a start-of-packet marker
+    if find_start_marker(datastream, MESSAGE_MARKER).is_none() {
+        let message =
+            format!("no start-of-message marker of {} different characters", MESSAGE_MARKER);
+        return Err(ParseError::end_of_input(input, message));
+    }
+
+    Ok(datastream.to_string())
 }
 
 
@@ -45,8 +57,10 @@ mod tests {
     use super::*;
 
     #[test]
-    fn solved() {
-        assert_eq!(1623, solve_first_star());
-        assert_eq!(3774, solve_second_star());
+    fn parsing_errors() {
+        assert_eq!(Ok("abcdefghijklmn".to_string()), parse_input("abcdefghijklmn\n"));
+
+        let error = parse_input("abcdefghijklma").unwrap_err();
+        assert_eq!("no start-of-message marker of 14 different characters", error.message);
     }
-}
\ No newline at end of file
+}
@@ -1,12 +1,55 @@
-use crate::day1::solve::{solve_first_star, solve_second_star};
+use crate::day1::solve::{parse_input, solve_first_star, solve_second_star, Calibration};
+use crate::YEAR;
+use aoc_core::{Answer, ParseError, Solver};
 
 mod solve;
 
-pub fn day1_solution() {
-    println!("--- Day 1: Trebuchet?! ---");
-    let result = solve_first_star();
-    println!("\t1st Star Solution = {}", result);
+pub struct Trebuchet;
 
-    let result = solve_second_star();
-    println!("\t2nd Star Solution = {}", result);
+impl Solver for Trebuchet {
+    type Input = Vec<Calibration>;
+
+    fn year(&self) -> u32 {
+        YEAR
+    }
+
+    fn day(&self) -> u32 {
+        1
+    }
+
+    fn title(&self) -> &'static str {
+        "Trebuchet?!"
+    }
+
+    fn input_path(&self) -> &'static str {
+        concat!(env!("CARGO_MANIFEST_DIR"), "/src/day1/input.txt")
+    }
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Result<Answer, String> {
+        solve_first_star(input).map(Answer::from)
+    }
+
+    fn part2(&self, input: &Self::Input) -> Result<Answer, String> {
+        Ok(solve_second_star(input).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_core::verify;
+
+    #[test]
+    fn solution() {
+        verify::assert_solved(Trebuchet);
+    }
+
+    aoc_core::examples!(Trebuchet, {
+        example: "test_input.txt",
+        example2: "test_input2.txt",
+    });
 }
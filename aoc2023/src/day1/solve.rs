@@ -1,74 +1,75 @@
+use aoc_core::ParseError;
 use nom::branch::alt;
 use nom::bytes::complete::{tag, take};
 use nom::character::complete::{anychar, digit1};
 use nom::error::Error;
 use nom::{multi::many_till, IResult};
-use std::fs;
 
-pub fn solve_first_star() -> i64 {
-    let input = parse_first_star_input("src/day1/input.txt");
-
-    input.iter().sum::<i64>()
+/// The calibration value of a line, read from its first and last digits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Calibration {
+    /// The value from numeric digits alone, missing when the line only spells its digits out.
+    pub digits: Option<i64>,
+    /// The value from numeric or spelled out digits.
+    pub spelled: i64,
 }
 
-pub fn solve_second_star() -> i64 {
-    let input = parse_second_star_input("src/day1/input.txt");
-
-    input.iter().sum::<i64>()
+pub fn solve_first_star(calibrations: &[Calibration]) -> Result<i64, String> {
+    calibrations
+        .iter()
+        .enumerate()
+        .map(|(i, calibration)| {
+            calibration
+                .digits
+                .ok_or_else(|| format!("line {} only has spelled out digits", i + 1))
+        })
+        .sum()
 }
 
-fn parse_first_star_input(file_path: &str) -> Vec<i64> {
-    let content = fs::read_to_string(file_path).expect("Input file local to project");
-
-    let mut result: Vec<i64> = Vec::<i64>::new();
-
-    for line in content.lines() {
-        // extract first number
-        let (_rest, num1) = first_star_digit_parser(line).unwrap();
-
-        // extract second number
-        let line_rev = line.chars().rev().collect::<String>();
-        let (_rest, num2) = first_star_digit_parser(&line_rev).unwrap();
-
-        let combined_number = num1.to_owned() + num2;
-
-        // push to vector
-        result.push(combined_number.parse::<i64>().unwrap());
-    }
+pub fn solve_second_star(calibrations: &[Calibration]) -> i64 {
+    calibrations
+        .iter()
+        .map(|calibration| calibration.spelled)
+        .sum()
+}
 
-    result
+pub fn parse_input(input: &str) -> Result<Vec<Calibration>, ParseError> {
+    input
+        .lines()
+        .map(|line| {
+            let spelled = spelled_value(line)
+                .ok_or_else(|| ParseError::at(input, line, "expected a digit"))?;
+
+            Ok(Calibration {
+                digits: digits_value(line),
+                spelled,
+            })
+        })
+        .collect()
 }
 
-fn parse_second_star_input(file_path: &str) -> Vec<i64> {
-    let content = fs::read_to_string(file_path).expect("Input file local to project");
+/// The value of the first and last numeric digits of a line.
+fn digits_value(line: &str) -> Option<i64> {
+    // extract first digit
+    let (_rest, first) = first_star_digit_parser(line).ok()?;
 
-    let mut result: Vec<i64> = Vec::<i64>::new();
+    // extract last digit
+    let line_rev = line.chars().rev().collect::<String>();
+    let (_rest, last) = first_star_digit_parser(&line_rev).ok()?;
 
-    for line in content.lines() {
-        let mut number = Vec::<char>::new();
+    (first.to_owned() + last).parse::<i64>().ok()
+}
 
-        //extract first digit
-        if let Ok((_, digit)) = parse_digit(line) {
-            number.push(digit);
-        }
+/// The value of the first and last digits of a line, numeric or spelled out.
+fn spelled_value(line: &str) -> Option<i64> {
+    //extract first digit
+    let (_, first) = parse_digit(line).ok()?;
 
-        //extract last digit
-        let rev_line = line.chars().rev().collect::<String>();
-        if let Ok((_, digit)) = parse_digit_reverse(&rev_line) {
-            number.push(digit);
-        }
+    //extract last digit
+    let rev_line = line.chars().rev().collect::<String>();
+    let (_, last) = parse_digit_reverse(&rev_line).ok()?;
 
-        // convert to number and push
-        result.push(
-            number
-                .into_iter()
-                .collect::<String>()
-                .parse::<i64>()
-                .unwrap(),
-        );
-    }
-
-    result
+    [first, last].iter().collect::<String>().parse::<i64>().ok()
 }
 
 fn first_star_digit_parser(s: &str) -> IResult<&str, &str, Error<&str>> {
@@ -150,37 +151,31 @@ fn convert_digit_to_char(digit: &str) -> char {
 mod tests {
     use super::*;
 
-    #[test]
-    fn solution() {
-        assert_eq!(55029, solve_first_star());
-        assert_eq!(55686, solve_second_star()); // too low
-    }
-
     #[test]
     fn first_star_test_parse() {
-        let input = parse_first_star_input("src/day1/test_input.txt");
+        let input = parse_input(include_str!("test_input.txt")).unwrap();
 
-        assert_eq!(input, [12, 38, 15, 77]);
-    }
-
-    #[test]
-    fn first_star_test_solution() {
-        let input = parse_first_star_input("src/day1/test_input.txt");
-
-        assert_eq!(input.iter().sum::<i64>(), 142);
+        let values = input.iter().map(|calibration| calibration.digits);
+        assert!(values.eq([12, 38, 15, 77].map(Some)));
     }
 
     #[test]
     fn second_star_test_parse() {
-        let input = parse_second_star_input("src/day1/test_input2.txt");
+        let input = parse_input(include_str!("test_input2.txt")).unwrap();
 
-        assert_eq!(input, [29, 83, 13, 24, 42, 14, 76]);
+        let values = input.iter().map(|calibration| calibration.spelled);
+        assert!(values.eq([29, 83, 13, 24, 42, 14, 76]));
+        assert_eq!(
+            Err("line 2 only has spelled out digits".to_string()),
+            solve_first_star(&input)
+        );
     }
 
     #[test]
-    fn second_star_test_solution() {
-        let input = parse_second_star_input("src/day1/test_input2.txt");
+    fn parsing_errors() {
+        let error = parse_input("1abc2\npqr3stu8vwx\nxyzzy\n7").unwrap_err();
 
-        assert_eq!(input.iter().sum::<i64>(), 281);
+        assert_eq!((3, 1), (error.line, error.column));
+        assert_eq!("expected a digit", error.message);
     }
 }
@@ -1,12 +1,54 @@
-use crate::day2::solve::{solve_first_star, solve_second_star};
+use crate::day2::solve::{parse_input, solve_first_star, solve_second_star, Game};
+use crate::YEAR;
+use aoc_core::{Answer, ParseError, Solver};
 
 mod solve;
 
-pub fn day2_solution() {
-    println!("--- Day 2: Cube Conundrum ---");
-    let result = solve_first_star();
-    println!("\t1st Star Solution = {}", result);
+pub struct CubeConundrum;
 
-    let result = solve_second_star();
-    println!("\t2nd Star Solution = {}", result);
+impl Solver for CubeConundrum {
+    type Input = Vec<Game>;
+
+    fn year(&self) -> u32 {
+        YEAR
+    }
+
+    fn day(&self) -> u32 {
+        2
+    }
+
+    fn title(&self) -> &'static str {
+        "Cube Conundrum"
+    }
+
+    fn input_path(&self) -> &'static str {
+        concat!(env!("CARGO_MANIFEST_DIR"), "/src/day2/input.txt")
+    }
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Result<Answer, String> {
+        Ok(solve_first_star(input).into())
+    }
+
+    fn part2(&self, input: &Self::Input) -> Result<Answer, String> {
+        Ok(solve_second_star(input).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_core::verify;
+
+    #[test]
+    fn solution() {
+        verify::assert_solved(CubeConundrum);
+    }
+
+    aoc_core::examples!(CubeConundrum, {
+        example: "test_input.txt",
+    });
 }
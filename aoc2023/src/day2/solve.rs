@@ -1,18 +1,16 @@
+use aoc_core::ParseError;
 use nom::branch::alt;
 use nom::bytes::complete::{is_not, tag};
 use nom::character::complete::{digit1, space0};
-use nom::combinator::map_res;
-use nom::sequence::preceded;
+use nom::combinator::{all_consuming, map_res};
+use nom::sequence::{preceded, terminated};
 use nom::IResult;
 use std::cmp::max;
-use std::fs;
 
 use crate::day2::solve::Cube::{Blue, Green, Red};
 use nom::multi::{many0, separated_list0};
 
-pub fn solve_first_star() -> usize {
-    let games = parse_input("src/day2/input.txt");
-
+pub fn solve_first_star(games: &[Game]) -> usize {
     let red_limit = 12;
     let green_limit = 13;
     let blue_limit = 14;
@@ -20,17 +18,13 @@ pub fn solve_first_star() -> usize {
     let mut possible_games = Vec::<usize>::new();
 
     games.iter().for_each(|game| {
-        let impossible_game = game
-            .draws
-            .iter()
-            .map(|draw| {
-                draw.cubes.iter().any(|cube| match cube {
-                    Red(val) => *val > red_limit,
-                    Blue(val) => *val > blue_limit,
-                    Green(val) => *val > green_limit,
-                })
+        let impossible_game = game.draws.iter().any(|draw| {
+            draw.cubes.iter().any(|cube| match cube {
+                Red(val) => *val > red_limit,
+                Blue(val) => *val > blue_limit,
+                Green(val) => *val > green_limit,
             })
-            .any(|impossible| impossible == true);
+        });
 
         if !impossible_game {
             possible_games.push(game.id)
@@ -40,9 +34,7 @@ pub fn solve_first_star() -> usize {
     possible_games.iter().sum()
 }
 
-pub fn solve_second_star() -> usize {
-    let games = parse_input("src/day2/input.txt");
-
+pub fn solve_second_star(games: &[Game]) -> usize {
     let mut game_powers = Vec::<usize>::new();
 
     games.iter().for_each(|game| {
@@ -70,34 +62,32 @@ pub fn solve_second_star() -> usize {
     game_powers.iter().sum()
 }
 
-fn parse_input(file_path: &str) -> Vec<Game> {
-    let content = fs::read_to_string(file_path).expect("Input file local to project");
-
+pub fn parse_input(content: &str) -> Result<Vec<Game>, ParseError> {
     let mut result = Vec::<Game>::new();
 
     for line in content.lines() {
         // parse games
-        if let Ok((_, game)) = parse_game_result(line) {
-            result.push(game);
-        }
+        let (_, game) = all_consuming(parse_game_result)(line)
+            .map_err(|err| ParseError::from_nom(content, err))?;
+        result.push(game);
     }
 
-    result
+    Ok(result)
 }
 
 #[derive(PartialEq, Debug)]
-struct Game {
+pub struct Game {
     id: usize,
     draws: Vec<Draw>,
 }
 
 #[derive(PartialEq, Debug)]
-struct Draw {
+pub struct Draw {
     cubes: Vec<Cube>,
 }
 
 #[derive(PartialEq, PartialOrd, Debug)]
-enum Cube {
+pub enum Cube {
     Red(usize),
     Blue(usize),
     Green(usize),
@@ -116,29 +106,29 @@ fn parse_game_result(input: &str) -> IResult<&str, Game> {
 
 fn parse_red_cubes(input: &str) -> IResult<&str, Cube> {
     let (left, _) = space0(input)?;
-    let (left, num) = digit1(left)?;
+    let (left, num) = map_res(digit1, |s: &str| s.parse::<usize>())(left)?;
     let (left, _) = space0(left)?;
     let (left, _) = tag("red")(left)?;
     let (left, _) = many0(tag(","))(left)?;
-    Ok((left, Red(num.parse::<usize>().unwrap())))
+    Ok((left, Red(num)))
 }
 
 fn parse_blue_cubes(input: &str) -> IResult<&str, Cube> {
     let (left, _) = space0(input)?;
-    let (left, num) = digit1(left)?;
+    let (left, num) = map_res(digit1, |s: &str| s.parse::<usize>())(left)?;
     let (left, _) = space0(left)?;
     let (left, _) = tag("blue")(left)?;
     let (left, _) = many0(tag(","))(left)?;
-    Ok((left, Blue(num.parse::<usize>().unwrap())))
+    Ok((left, Blue(num)))
 }
 
 fn parse_green_cubes(input: &str) -> IResult<&str, Cube> {
     let (left, _) = space0(input)?;
-    let (left, num) = digit1(left)?;
+    let (left, num) = map_res(digit1, |s: &str| s.parse::<usize>())(left)?;
     let (left, _) = space0(left)?;
     let (left, _) = tag("green")(left)?;
     let (left, _) = many0(tag(","))(left)?;
-    Ok((left, Green(num.parse::<usize>().unwrap())))
+    Ok((left, Green(num)))
 }
 
 fn parse_draws(input: &str) -> IResult<&str, Vec<Draw>> {
@@ -149,12 +139,14 @@ fn parse_draws(input: &str) -> IResult<&str, Vec<Draw>> {
     let (left, draws) = separated_list0(tag(";"), is_not(";"))(left)?;
 
     // parse draws
-    draws.into_iter().for_each(|draw| {
-        let (_, cubes) =
-            many0(alt((parse_red_cubes, parse_blue_cubes, parse_green_cubes)))(draw).unwrap();
+    for draw in draws {
+        let (_, cubes) = all_consuming(terminated(
+            many0(alt((parse_red_cubes, parse_blue_cubes, parse_green_cubes))),
+            space0,
+        ))(draw)?;
 
         result.push(Draw { cubes });
-    });
+    }
 
     Ok((left, result))
 }
@@ -163,12 +155,6 @@ fn parse_draws(input: &str) -> IResult<&str, Vec<Draw>> {
 mod tests {
     use super::*;
 
-    #[test]
-    fn solution() {
-        assert_eq!(2265, solve_first_star());
-        assert_eq!(64097, solve_second_star());
-    }
-
     #[test]
     fn test_input_parser() {
         if let Ok((_, result)) =
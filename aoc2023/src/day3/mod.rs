@@ -1,12 +1,54 @@
-use crate::day3::solve::{solve_first_star, solve_second_star};
+use crate::day3::solve::{parse_input, solve_first_star, solve_second_star, Schematic};
+use crate::YEAR;
+use aoc_core::{Answer, ParseError, Solver};
 
 mod solve;
 
-pub fn day3_solution() {
-    println!("--- Day 3: Gear Ratios ---");
-    let result = solve_first_star();
-    println!("\t1st Star Solution = {}", result);
+pub struct GearRatios;
 
-    let result = solve_second_star();
-    println!("\t2nd Star Solution = {}", result);
-}
\ No newline at end of file
+impl Solver for GearRatios {
+    type Input = Schematic;
+
+    fn year(&self) -> u32 {
+        YEAR
+    }
+
+    fn day(&self) -> u32 {
+        3
+    }
+
+    fn title(&self) -> &'static str {
+        "Gear Ratios"
+    }
+
+    fn input_path(&self) -> &'static str {
+        concat!(env!("CARGO_MANIFEST_DIR"), "/src/day3/input.txt")
+    }
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Result<Answer, String> {
+        Ok(solve_first_star(input).into())
+    }
+
+    fn part2(&self, input: &Self::Input) -> Result<Answer, String> {
+        Ok(solve_second_star(input).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_core::verify;
+
+    #[test]
+    fn solution() {
+        verify::assert_solved(GearRatios);
+    }
+
+    aoc_core::examples!(GearRatios, {
+        example: "test_input.txt",
+    });
+}
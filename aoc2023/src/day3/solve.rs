@@ -1,28 +1,27 @@
-use nom::branch::alt;
-use nom::bytes::complete::tag;
-use nom::character::complete::digit1;
-use nom::multi::many0;
-use nom::IResult;
+use aoc_core::grid::{Grid, Position};
+use aoc_core::ParseError;
 use std::collections::HashSet;
-use std::fs;
+use std::ops::Range;
 
-pub fn solve_first_star() -> usize {
-    let (symbols, numbers) = parse_input("src/day3/input.txt");
+/// Characters marking the parts of the engine.
+const SYMBOLS: &str = "%*/+=#$&@-";
 
-    let part_numbers = identify_part_numbers(symbols, numbers);
+pub fn solve_first_star(schematic: &Schematic) -> usize {
+    let part_numbers = identify_part_numbers(schematic);
 
     part_numbers.iter().sum()
 }
 
-fn identify_part_numbers(symbols: Vec<Symbol>, numbers: Vec<Number>) -> Vec<usize> {
+fn identify_part_numbers(schematic: &Schematic) -> Vec<usize> {
     // extract symbol locations
-    let symbol_locations: HashSet<Point> = symbols.iter().map(|s| s.location.clone()).collect();
+    let symbol_locations: HashSet<Position> =
+        schematic.symbols.iter().map(|s| s.location).collect();
 
     let mut part_numbers = Vec::<usize>::new();
 
-    numbers.iter().for_each(|num| {
+    schematic.numbers.iter().for_each(|num| {
         if num
-            .proximate_points()
+            .proximate_points(&schematic.grid)
             .iter()
             .any(|x| symbol_locations.contains(x))
         {
@@ -32,25 +31,34 @@ fn identify_part_numbers(symbols: Vec<Symbol>, numbers: Vec<Number>) -> Vec<usiz
     part_numbers
 }
 
-pub fn solve_second_star() -> usize {
-    let (symbols, numbers) = parse_input("src/day3/input.txt");
-
-    let gear_ratios = calculate_gear_ratios(symbols, numbers);
+pub fn solve_second_star(schematic: &Schematic) -> usize {
+    let gear_ratios = calculate_gear_ratios(schematic);
 
     gear_ratios.iter().sum()
 }
 
-fn calculate_gear_ratios(symbols: Vec<Symbol>, numbers: Vec<Number>) -> Vec<usize> {
+fn calculate_gear_ratios(schematic: &Schematic) -> Vec<usize> {
     // extract gear locations
-    let possible_gears: Vec<&Symbol> = symbols.iter().filter(|&x| x.value == '*').collect();
+    let possible_gears: Vec<&Symbol> = schematic
+        .symbols
+        .iter()
+        .filter(|&x| x.value == '*')
+        .collect();
+
+    let proximate: Vec<(&Number, HashSet<Position>)> = schematic
+        .numbers
+        .iter()
+        .map(|num| (num, num.proximate_points(&schematic.grid)))
+        .collect();
 
     let mut gear_ratios = Vec::<usize>::new();
 
     // check each symbol if its a gear
     possible_gears.iter().for_each(|&gear| {
-        let parts: Vec<&Number> = numbers
+        let parts: Vec<&Number> = proximate
             .iter()
-            .filter(|&num| num.proximate_points().contains(&gear.location))
+            .filter(|(_, points)| points.contains(&gear.location))
+            .map(|&(num, _)| num)
             .collect();
 
         if parts.len() == 2 {
@@ -60,161 +68,94 @@ fn calculate_gear_ratios(symbols: Vec<Symbol>, numbers: Vec<Number>) -> Vec<usiz
     gear_ratios
 }
 
-#[derive(PartialEq, Eq, Debug, Hash, Clone)]
-struct Point {
-    x: usize,
-    y: usize,
+/// The engine schematic, with the symbols and numbers found on it.
+#[derive(PartialEq, Debug)]
+pub struct Schematic {
+    grid: Grid<char>,
+    symbols: Vec<Symbol>,
+    numbers: Vec<Number>,
 }
 
 #[derive(PartialEq, Debug)]
-struct Span {
-    start: Point,
-    end: Point,
+pub struct Span {
+    start: Position,
+    end: Position,
 }
 
 #[derive(PartialEq, Debug)]
-struct Symbol {
-    location: Point,
+pub struct Symbol {
+    location: Position,
     value: char,
 }
 
 #[derive(PartialEq, Debug)]
-struct Number {
+pub struct Number {
     location: Span,
     value: usize,
 }
 
 impl Number {
-    fn proximate_points(&self) -> Vec<Point> {
-        let mut result = Vec::<Point>::new();
-
-        // set up x,y coordinate extents
-        let mut x1 = self.location.start.x;
-        let x2 = self.location.end.x + 1;
-        let mut y1 = self.location.start.y;
-        let y2 = self.location.end.y + 1;
-
-        if x1 > 0 {
-            x1 -= 1
-        }
-        if y1 > 0 {
-            y1 -= 1
+    fn new(row: usize, cols: Range<usize>, value: usize) -> Self {
+        Number {
+            location: Span {
+                start: (row, cols.start),
+                end: (row, cols.end - 1),
+            },
+            value,
         }
+    }
 
-        // enumerate extents generating points
-        for x in x1..=x2 {
-            for y in y1..=y2 {
-                result.push(Point { x, y })
-            }
-        }
+    /// Positions on the schematic adjacent to the number, including diagonally.
+    fn proximate_points(&self, grid: &Grid<char>) -> HashSet<Position> {
+        let (row, start) = self.location.start;
+        let (_, end) = self.location.end;
 
-        result
+        (start..=end)
+            .flat_map(|col| grid.neighbours8((row, col)))
+            .filter(|&(r, col)| r != row || col < start || col > end)
+            .collect()
     }
 }
 
-fn parse_input(file_path: &str) -> (Vec<Symbol>, Vec<Number>) {
-    let content = fs::read_to_string(file_path).expect("Input file local to project");
+pub fn parse_input(content: &str) -> Result<Schematic, ParseError> {
+    let grid = Grid::parse(content, "schematic symbol", |c| {
+        (c == '.' || c.is_ascii_digit() || SYMBOLS.contains(c)).then_some(c)
+    })?;
 
     let mut symbols = Vec::<Symbol>::new();
     let mut numbers = Vec::<Number>::new();
 
-    // return symbols and their location
-    // return numbers and their span
-
-    let mut x;
-    let mut y = 0_usize;
+    // numbers are runs of digits along a row, any other character but `.` is a symbol
+    for (row, line) in grid.as_array().rows().into_iter().enumerate() {
+        let mut number: Option<(usize, usize)> = None;
 
-    for line in content.lines() {
-        // I need track location
-        // reset x coordinate
-        x = 0;
+        for (col, &c) in line.iter().enumerate() {
+            if let Some(digit) = c.to_digit(10) {
+                let (start, value) = number.unwrap_or((col, 0));
+                number = Some((start, value * 10 + digit as usize));
+                continue;
+            }
 
-        // many0 with parsers to completely consume line
-        let (_left, engine_schematic_line) = parse_engine_schematic_line(line).unwrap();
+            if let Some((start, value)) = number.take() {
+                numbers.push(Number::new(row, start..col, value));
+            }
 
-        // interpret parsed results
-        engine_schematic_line.iter().for_each(|&token| match token {
-            "%" | "*" | "/" | "+" | "=" | "#" | "$" | "&" | "@" | "-" => {
+            if c != '.' {
                 symbols.push(Symbol {
-                    location: Point { x, y },
-                    value: token.chars().next().unwrap(),
-                });
-
-                x += 1;
-            }
-            "." => {
-                // discard and increment x location by 1
-                x += 1;
-            }
-            num => {
-                numbers.push(Number {
-                    value: num.parse::<usize>().unwrap(),
-                    location: Span {
-                        start: Point { x, y },
-                        end: Point {
-                            x: x + num.len() - 1,
-                            y,
-                        },
-                    },
+                    location: (row, col),
+                    value: c,
                 });
-
-                // increment x location by the length of the number
-                x += num.len();
             }
-        });
-
-        // increment y coordinate
-        y += 1;
-    }
-
-    (symbols, numbers)
-}
-
-fn parse_engine_schematic_line(input: &str) -> IResult<&str, Vec<&str>> {
-    //many0(alt((tag("."), digit1, one_of("%"))))(input)
-    many0(alt((tag("."), digit1, parse_symbol)))(input)
-}
-
-fn parse_symbol(input: &str) -> IResult<&str, &str> {
-    alt((
-        tag("%"),
-        tag("*"),
-        tag("/"),
-        tag("+"),
-        tag("="),
-        tag("#"),
-        tag("$"),
-        tag("&"),
-        tag("@"),
-        tag("-"),
-    ))(input)
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn solution() {
-        assert_eq!(559667, solve_first_star());
-        assert_eq!(86841457, solve_second_star());
-    }
-
-    #[test]
-    fn test_solution_1() {
-        let (symbols, numbers) = parse_input("src/day3/test_input.txt");
-
-        let part_numbers = identify_part_numbers(symbols, numbers);
+        }
 
-        assert_eq!(4361_usize, part_numbers.iter().sum());
+        if let Some((start, value)) = number {
+            numbers.push(Number::new(row, start..line.len(), value));
+        }
     }
 
-    #[test]
-    fn test_solution_2() {
-        let (symbols, numbers) = parse_input("src/day3/test_input.txt");
-
-        let gear_ratios = calculate_gear_ratios(symbols, numbers);
-
-        assert_eq!(467835_usize, gear_ratios.iter().sum());
-    }
+    Ok(Schematic {
+        grid,
+        symbols,
+        numbers,
+    })
 }
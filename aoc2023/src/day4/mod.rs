@@ -1,12 +1,54 @@
-use crate::day4::solve::{solve_first_star, solve_second_star};
+use crate::day4::solve::{parse_input, solve_first_star, solve_second_star, ScratchCard};
+use crate::YEAR;
+use aoc_core::{Answer, ParseError, Solver};
 
 mod solve;
 
-pub fn day4_solution() {
-    println!("--- Day 4: Scratchcards ---");
-    let result = solve_first_star();
-    println!("\t1st Star Solution = {}", result);
+pub struct Scratchcards;
 
-    let result = solve_second_star();
-    println!("\t2nd Star Solution = {}", result);
-}
\ No newline at end of file
+impl Solver for Scratchcards {
+    type Input = Vec<ScratchCard>;
+
+    fn year(&self) -> u32 {
+        YEAR
+    }
+
+    fn day(&self) -> u32 {
+        4
+    }
+
+    fn title(&self) -> &'static str {
+        "Scratchcards"
+    }
+
+    fn input_path(&self) -> &'static str {
+        concat!(env!("CARGO_MANIFEST_DIR"), "/src/day4/input.txt")
+    }
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Result<Answer, String> {
+        Ok(solve_first_star(input).into())
+    }
+
+    fn part2(&self, input: &Self::Input) -> Result<Answer, String> {
+        Ok(solve_second_star(input).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_core::verify;
+
+    #[test]
+    fn solution() {
+        verify::assert_solved(Scratchcards);
+    }
+
+    aoc_core::examples!(Scratchcards, {
+        example: "test_input.txt",
+    });
+}
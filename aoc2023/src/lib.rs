@@ -0,0 +1,21 @@
+use aoc_core::Registry;
+
+use crate::day1::Trebuchet;
+use crate::day2::CubeConundrum;
+use crate::day3::GearRatios;
+use crate::day4::Scratchcards;
+
+mod day1;
+mod day2;
+mod day3;
+mod day4;
+
+pub const YEAR: u32 = 2023;
+
+/// Registers every solved 2023 puzzle with the registry.
+pub fn register(registry: &mut Registry) {
+    registry.register(Trebuchet);
+    registry.register(CubeConundrum);
+    registry.register(GearRatios);
+    registry.register(Scratchcards);
+}
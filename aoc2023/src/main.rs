@@ -1,17 +0,0 @@
-use crate::day1::day1_solution;
-use crate::day2::day2_solution;
-use crate::day3::day3_solution;
-use crate::day4::day4_solution;
-
-mod day1;
-mod day2;
-mod day3;
-mod day4;
-
-fn main() {
-    println!("*** Advent of Code 2023 ***");
-    day1_solution();
-    day2_solution();
-    day3_solution();
-    day4_solution();
-}